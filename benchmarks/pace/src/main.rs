@@ -89,9 +89,9 @@ fn create_data_provider(size: DataSize) -> Arc<dyn DataProvider + Send + Sync> {
 
     let path = Path::new("benchmarks/.data").join(filename);
 
-    let df = read_df(&path);
+    let df = read_df(&path).unwrap();
 
-    return Arc::from(InMemoryDataProvider::from_df(&df));
+    return Arc::from(InMemoryDataProvider::from_df(&df).unwrap());
 }
 
 fn create_ctx(data_provider: Arc<dyn DataProvider + Send + Sync>) -> Context {
@@ -114,7 +114,7 @@ impl PaceBenchmarkRunner {
             let _ctx = target.ctx.clone();
             let start_time = Instant::now();
 
-            for i in ctx.first_bar_index..=ctx.last_bar_index {
+            for i in ctx.first_bar_index..=ctx.last_bar_index() {
                 ctx.bar.index.set(i);
                 target.next(ctx.bar.close());
             }
@@ -129,7 +129,7 @@ impl PaceBenchmarkRunner {
             let _ctx = target.ctx.clone();
             let start_time = Instant::now();
 
-            for i in ctx.first_bar_index..=ctx.last_bar_index {
+            for i in ctx.first_bar_index..=ctx.last_bar_index() {
                 ctx.bar.index.set(i);
                 target.next(target.ctx.bar.close());
             }
@@ -144,7 +144,7 @@ impl PaceBenchmarkRunner {
             let _ctx = target.ctx.clone();
             let start_time = Instant::now();
 
-            for i in ctx.first_bar_index..=ctx.last_bar_index {
+            for i in ctx.first_bar_index..=ctx.last_bar_index() {
                 ctx.bar.index.set(i);
                 target.next(target.ctx.bar.close());
             }
@@ -159,7 +159,7 @@ impl PaceBenchmarkRunner {
             let _ctx = target.ctx.clone();
             let start_time = Instant::now();
 
-            for i in ctx.first_bar_index..=ctx.last_bar_index {
+            for i in ctx.first_bar_index..=ctx.last_bar_index() {
                 ctx.bar.index.set(i);
                 let close = target.ctx.bar.close();
                 target.next((close, close, close));
@@ -175,7 +175,7 @@ impl PaceBenchmarkRunner {
             let _ctx = target.ctx.clone();
             let start_time = Instant::now();
 
-            for i in ctx.first_bar_index..=ctx.last_bar_index {
+            for i in ctx.first_bar_index..=ctx.last_bar_index() {
                 ctx.bar.index.set(i);
                 target.next(());
             }
@@ -200,7 +200,7 @@ impl PaceBenchmarkRunner {
             let _ctx = target.ctx.clone();
             let start_time = Instant::now();
 
-            for i in ctx.first_bar_index..=ctx.last_bar_index {
+            for i in ctx.first_bar_index..=ctx.last_bar_index() {
                 ctx.bar.index.set(i);
                 target.next(());
             }
@@ -225,7 +225,7 @@ impl PaceBenchmarkRunner {
             let _ctx = target_macd.ctx.clone();
             let start_time = Instant::now();
 
-            for i in ctx.first_bar_index..=ctx.last_bar_index {
+            for i in ctx.first_bar_index..=ctx.last_bar_index() {
                 ctx.bar.index.set(i);
                 target_macd.next(());
                 target_rsi.next(target_rsi.ctx.bar.close());
@@ -13,6 +13,7 @@ use nersent_pace::{
     core::{
         context::Context,
         data_provider::DataProvider,
+        error::PaceError,
         in_memory_data_provider::InMemoryDataProvider,
         incremental::{Incremental, IncrementalDefault},
     },
@@ -26,11 +27,11 @@ use nersent_pace::{
     },
 };
 
-fn main() {
+fn main() -> Result<(), PaceError> {
     let data_path = Path::new("example/fixtures/btc_1d.csv");
-    let df = read_df(&data_path);
+    let df = read_df(&data_path)?;
 
-    let ctx = Context::new(InMemoryDataProvider::from_df(&df).to_arc());
+    let ctx = Context::new(InMemoryDataProvider::from_df(&df)?.to_arc());
 
     let mut strategy = Strategy::new(
         ctx.clone(),
@@ -75,4 +76,6 @@ fn main() {
     metrics.data.print_overview(currency);
     metrics.data.plot_net_equity((236, 100));
     metrics.data.print_summary(currency);
+
+    return Ok(());
 }
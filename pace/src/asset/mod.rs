@@ -1 +1,2 @@
+pub mod symbol_info;
 pub mod timeframe;
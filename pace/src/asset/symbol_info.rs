@@ -0,0 +1,60 @@
+/// Instrument specification.
+///
+/// Similar to PineScript `syminfo` namespace.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SymbolInfo {
+    /// Same as PineScript `syminfo.ticker`.
+    pub ticker: String,
+    /// Minimum price movement. `0.0` disables price rounding.
+    ///
+    /// Same as PineScript `syminfo.mintick`.
+    pub mintick: f64,
+    /// Value of a `1.0` price move for a single contract, also known as contract multiplier.
+    ///
+    /// Same as PineScript `syminfo.pointvalue`.
+    pub point_value: f64,
+    /// Minimum order quantity.
+    pub min_qty: f64,
+    /// Order quantity step. Quantities are rounded down to a multiple of it. `0.0` disables quantity rounding.
+    pub qty_step: f64,
+    /// Same as PineScript `syminfo.currency`.
+    pub currency: String,
+}
+
+impl Default for SymbolInfo {
+    fn default() -> Self {
+        return Self {
+            ticker: String::new(),
+            mintick: 0.0,
+            point_value: 1.0,
+            min_qty: 0.0,
+            qty_step: 0.0,
+            currency: "USD".to_string(),
+        };
+    }
+}
+
+impl SymbolInfo {
+    /// Rounds price to the nearest tick.
+    ///
+    /// Same as PineScript `math.round_to_mintick`.
+    pub fn round_to_mintick(&self, price: f64) -> f64 {
+        if self.mintick <= 0.0 {
+            return price;
+        }
+        return (price / self.mintick).round() * self.mintick;
+    }
+
+    /// Rounds quantity down to the nearest lot step. Returns `0.0` if quantity is smaller than `min_qty`.
+    pub fn round_qty(&self, qty: f64) -> f64 {
+        let mut qty = qty;
+        if self.qty_step > 0.0 {
+            // Small epsilon prevents `0.3 / 0.1` from flooring to `2.0`.
+            qty = ((qty / self.qty_step) + 1e-9).floor() * self.qty_step;
+        }
+        if qty < self.min_qty {
+            return 0.0;
+        }
+        return qty;
+    }
+}
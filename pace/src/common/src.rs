@@ -3,7 +3,7 @@ use crate::core::{context::Context, incremental::Incremental};
 /// Any data source provider;
 pub type AnySrc = Box<dyn Incremental<(), Option<f64>>>;
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub enum SrcKind {
     Open,
     High,
@@ -13,6 +13,8 @@ pub enum SrcKind {
     OHLC4,
     HLC3,
     HL2,
+    /// Auxiliary series of the data provider, e.g. funding rate or open interest.
+    Series(String),
 }
 
 pub fn ohlc4(open: f64, high: f64, low: f64, close: f64) -> f64 {
@@ -37,8 +39,8 @@ impl Src {
     pub fn new(ctx: Context, kind: SrcKind) -> Self {
         return Self {
             ctx: ctx.clone(),
+            delegate: Self::create_delegate(ctx.clone(), kind.clone()),
             kind,
-            delegate: Self::create_delegate(ctx.clone(), kind),
         };
     }
 
@@ -67,6 +69,7 @@ impl Src {
             SrcKind::HL2 => {
                 Box::new(move || Some(hl2(ctx.bar.high().unwrap(), ctx.bar.low().unwrap())))
             }
+            SrcKind::Series(name) => Box::new(move || ctx.bar.series(&name)),
         }
     }
 }
@@ -1,2 +1,3 @@
 mod fixnan_test;
+mod src_test;
 mod window_validator_test;
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::src::{Src, SrcKind},
+        core::{
+            context::Context, data_provider::DataProvider, error::PaceError,
+            in_memory_data_provider::InMemoryDataProvider, incremental::Incremental,
+        },
+    };
+
+    fn create_ctx() -> Context {
+        let mut data = InMemoryDataProvider::from_values(vec![Some(1.0), Some(2.0), Some(3.0)]);
+        data.add_series("funding_rate", vec![Some(0.01), None, Some(-0.02)])
+            .unwrap();
+        data.add_series("open_interest", vec![Some(100.0), Some(110.0), Some(90.0)])
+            .unwrap();
+        return Context::new(data.to_arc());
+    }
+
+    #[test]
+    fn series_src() {
+        let ctx = create_ctx();
+        let mut src = Src::new(ctx.clone(), SrcKind::Series("funding_rate".to_string()));
+        let mut missing = Src::new(ctx.clone(), SrcKind::Series("sentiment".to_string()));
+        let mut values = Vec::new();
+
+        for _ in ctx.clone() {
+            values.push(src.next(()));
+            assert_eq!(missing.next(()), None);
+        }
+
+        assert_eq!(values, vec![Some(0.01), None, Some(-0.02)]);
+    }
+
+    #[test]
+    fn context_series() {
+        let ctx = create_ctx();
+        assert_eq!(
+            ctx.data.get_series_names(),
+            vec!["funding_rate", "open_interest"]
+        );
+
+        for tick in ctx.clone() {
+            if tick == 2 {
+                assert_eq!(ctx.bar.series("open_interest"), Some(90.0));
+                assert_eq!(ctx.series("open_interest", 1), Some(110.0));
+                assert_eq!(ctx.series("open_interest", 3), None);
+                assert_eq!(
+                    *ctx.series_values("open_interest", 3),
+                    [Some(100.0), Some(110.0), Some(90.0)]
+                );
+                assert!(ctx.series_values("sentiment", 2).is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn series_length_mismatch() {
+        let mut data = InMemoryDataProvider::from_values(vec![Some(1.0), Some(2.0)]);
+        let error = data
+            .add_series("funding_rate", vec![Some(0.01)])
+            .unwrap_err();
+        assert!(matches!(error, PaceError::InvalidData(_)));
+    }
+}
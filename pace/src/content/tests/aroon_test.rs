@@ -30,7 +30,9 @@ mod tests {
     #[test]
     fn length_14() {
         let (df, ctx) = Fixture::load_ctx(&format_path("length_14.csv"));
-        let expected = df.merge_two_columns("_target_up_", "_target_down_");
+        let expected = df
+            .merge_two_columns("_target_up_", "_target_down_")
+            .unwrap();
         _test(
             &mut Aroon::new(ctx.clone(), AroonConfig { length: 14 }),
             &expected,
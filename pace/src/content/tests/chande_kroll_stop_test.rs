@@ -50,12 +50,14 @@ mod tests {
     #[test]
     fn p_10_x_1_q_9() {
         let (df, ctx) = Fixture::load_ctx(&format_path("p_10_x_1_q_9.csv"));
-        let expected = df.merge_four_columns(
-            "_target_first_high_stop_",
-            "_target_first_low_stop_",
-            "_target_stop_short_",
-            "_target_stop_long_",
-        );
+        let expected = df
+            .merge_four_columns(
+                "_target_first_high_stop_",
+                "_target_first_low_stop_",
+                "_target_stop_short_",
+                "_target_stop_long_",
+            )
+            .unwrap();
         _test(
             &mut ChandeKrollStop::new(
                 ctx.clone(),
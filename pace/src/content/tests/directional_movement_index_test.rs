@@ -46,7 +46,9 @@ mod tests {
     #[test]
     fn length_14_lensig_14() {
         let (df, ctx) = Fixture::load_ctx(&format_path("length_14_lensig_14.csv"));
-        let expected = df.merge_three_columns("_target_plus_", "_target_minus_", "_target_adx_");
+        let expected = df
+            .merge_three_columns("_target_plus_", "_target_minus_", "_target_adx_")
+            .unwrap();
         _test(
             &mut DirectionalMovementIndex::new(
                 ctx.clone(),
@@ -62,7 +64,9 @@ mod tests {
     #[test]
     fn length_3_lensig_3() {
         let (df, ctx) = Fixture::load_ctx(&format_path("length_3_lensig_3.csv"));
-        let expected = df.merge_three_columns("_target_plus_", "_target_minus_", "_target_adx_");
+        let expected = df
+            .merge_three_columns("_target_plus_", "_target_minus_", "_target_adx_")
+            .unwrap();
         _test(
             &mut DirectionalMovementIndex::new(
                 ctx.clone(),
@@ -78,7 +82,9 @@ mod tests {
     #[test]
     fn length_14_lensig_3() {
         let (df, ctx) = Fixture::load_ctx(&format_path("length_14_lensig_3.csv"));
-        let expected = df.merge_three_columns("_target_plus_", "_target_minus_", "_target_adx_");
+        let expected = df
+            .merge_three_columns("_target_plus_", "_target_minus_", "_target_adx_")
+            .unwrap();
         _test(
             &mut DirectionalMovementIndex::new(
                 ctx.clone(),
@@ -94,7 +100,9 @@ mod tests {
     #[test]
     fn length_3_lensig_14() {
         let (df, ctx) = Fixture::load_ctx(&format_path("length_3_lensig_14.csv"));
-        let expected = df.merge_three_columns("_target_plus_", "_target_minus_", "_target_adx_");
+        let expected = df
+            .merge_three_columns("_target_plus_", "_target_minus_", "_target_adx_")
+            .unwrap();
         _test(
             &mut DirectionalMovementIndex::new(
                 ctx.clone(),
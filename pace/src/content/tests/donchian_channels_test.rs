@@ -44,7 +44,9 @@ mod tests {
     #[test]
     fn length_14() {
         let (df, ctx) = Fixture::load_ctx(&format_path("length_14.csv"));
-        let expected = df.merge_three_columns("_target_upper_", "_target_basis_", "_target_lower_");
+        let expected = df
+            .merge_three_columns("_target_upper_", "_target_basis_", "_target_lower_")
+            .unwrap();
         _test(
             &mut DonchianChannels::new(ctx.clone(), DonchianChannelsConfig { length: 14 }),
             &expected,
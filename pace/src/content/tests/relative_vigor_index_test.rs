@@ -41,7 +41,9 @@ mod tests {
     #[test]
     fn length_14() {
         let (df, ctx) = Fixture::load_ctx(&format_path("length_14.csv"));
-        let expected = df.merge_two_columns("_target_rvi_", "_target_sig_");
+        let expected = df
+            .merge_two_columns("_target_rvi_", "_target_sig_")
+            .unwrap();
         _test(
             &mut RelativeVigorIndex::new(ctx.clone(), RelativeVigorIndexConfig { length: 14 }),
             &expected,
@@ -51,7 +53,9 @@ mod tests {
     #[test]
     fn length_1() {
         let (df, ctx) = Fixture::load_ctx(&format_path("length_1.csv"));
-        let expected = df.merge_two_columns("_target_rvi_", "_target_sig_");
+        let expected = df
+            .merge_two_columns("_target_rvi_", "_target_sig_")
+            .unwrap();
         _test(
             &mut RelativeVigorIndex::new(ctx.clone(), RelativeVigorIndexConfig { length: 1 }),
             &expected,
@@ -61,7 +65,9 @@ mod tests {
     #[test]
     fn length_2() {
         let (df, ctx) = Fixture::load_ctx(&format_path("length_2.csv"));
-        let expected = df.merge_two_columns("_target_rvi_", "_target_sig_");
+        let expected = df
+            .merge_two_columns("_target_rvi_", "_target_sig_")
+            .unwrap();
         _test(
             &mut RelativeVigorIndex::new(ctx.clone(), RelativeVigorIndexConfig { length: 2 }),
             &expected,
@@ -71,7 +77,9 @@ mod tests {
     #[test]
     fn length_3() {
         let (df, ctx) = Fixture::load_ctx(&format_path("length_3.csv"));
-        let expected = df.merge_two_columns("_target_rvi_", "_target_sig_");
+        let expected = df
+            .merge_two_columns("_target_rvi_", "_target_sig_")
+            .unwrap();
         _test(
             &mut RelativeVigorIndex::new(ctx.clone(), RelativeVigorIndexConfig { length: 3 }),
             &expected,
@@ -81,7 +89,9 @@ mod tests {
     #[test]
     fn length_365() {
         let (df, ctx) = Fixture::load_ctx(&format_path("length_365.csv"));
-        let expected = df.merge_two_columns("_target_rvi_", "_target_sig_");
+        let expected = df
+            .merge_two_columns("_target_rvi_", "_target_sig_")
+            .unwrap();
         _test(
             &mut RelativeVigorIndex::new(ctx.clone(), RelativeVigorIndexConfig { length: 365 }),
             &expected,
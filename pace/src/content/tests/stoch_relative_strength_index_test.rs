@@ -47,7 +47,7 @@ mod tests {
     fn length_14_stoch_length_14_k_3_d_3_close() {
         let (df, ctx) =
             Fixture::load_ctx(&format_path("length_14_stoch_length_14_k_3_d_3_close.csv"));
-        let expected = df.merge_two_columns("_target_k_", "_target_d_");
+        let expected = df.merge_two_columns("_target_k_", "_target_d_").unwrap();
         _test(
             &mut StochRelativeStrengthIndex::new(
                 ctx.clone(),
@@ -67,7 +67,7 @@ mod tests {
     fn length_2_stoch_length_2_k_3_d_3_close() {
         let (df, ctx) =
             Fixture::load_ctx(&format_path("length_2_stoch_length_2_k_3_d_3_close.csv"));
-        let expected = df.merge_two_columns("_target_k_", "_target_d_");
+        let expected = df.merge_two_columns("_target_k_", "_target_d_").unwrap();
         _test(
             &mut StochRelativeStrengthIndex::new(
                 ctx.clone(),
@@ -87,7 +87,7 @@ mod tests {
     fn length_2_stoch_length_2_k_14_d_14_close() {
         let (df, ctx) =
             Fixture::load_ctx(&format_path("length_2_stoch_length_2_k_14_d_14_close.csv"));
-        let expected = df.merge_two_columns("_target_k_", "_target_d_");
+        let expected = df.merge_two_columns("_target_k_", "_target_d_").unwrap();
         _test(
             &mut StochRelativeStrengthIndex::new(
                 ctx.clone(),
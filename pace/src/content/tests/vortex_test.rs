@@ -38,7 +38,9 @@ mod tests {
     #[test]
     fn length_14() {
         let (df, ctx) = Fixture::load_ctx(&format_path("length_14.csv"));
-        let expected = df.merge_two_columns("_target_plus_", "_target_minus_");
+        let expected = df
+            .merge_two_columns("_target_plus_", "_target_minus_")
+            .unwrap();
         _test(
             &mut Vortex::new(ctx.clone(), VortexConfig { length: 14 }),
             &expected,
@@ -48,7 +50,9 @@ mod tests {
     #[test]
     fn length_2() {
         let (df, ctx) = Fixture::load_ctx(&format_path("length_2.csv"));
-        let expected = df.merge_two_columns("_target_plus_", "_target_minus_");
+        let expected = df
+            .merge_two_columns("_target_plus_", "_target_minus_")
+            .unwrap();
         _test(
             &mut Vortex::new(ctx.clone(), VortexConfig { length: 2 }),
             &expected,
@@ -1,5 +1,5 @@
 use std::{
-    borrow::{Borrow, BorrowMut},
+    borrow::{Borrow, BorrowMut, Cow},
     cell::{Cell, RefCell, RefMut, UnsafeCell},
     rc::Rc,
     sync::Arc,
@@ -8,6 +8,8 @@ use std::{
 
 use chrono::NaiveDateTime;
 
+use crate::asset::symbol_info::SymbolInfo;
+
 use super::data_provider::DataProvider;
 
 pub struct Bar {
@@ -73,6 +75,11 @@ impl Bar {
     pub fn volume(&self) -> Option<f64> {
         return self.data.get_volume(self.index.get());
     }
+
+    /// Current value of auxiliary series `name`, e.g. funding rate or open interest.
+    pub fn series(&self, name: &str) -> Option<f64> {
+        return self.data.get_series(name, self.index.get());
+    }
 }
 
 pub struct Context {
@@ -80,20 +87,26 @@ pub struct Context {
     pub bar: Bar,
     // First bar index. Starts with 0, unless `start_tick` was set differently.
     pub first_bar_index: usize,
-    /// Bar index of the last chart bar.
-    /// Same as PineScript `last_bar_index`.
-    pub last_bar_index: usize,
-    /// The total number of ticks between first and last bars.
+    /// The total number of ticks between first and last bars, when the context was created.
     pub bars: usize,
+    /// Instrument specification. Same as PineScript `syminfo`.
+    pub syminfo: Rc<SymbolInfo>,
     is_running: Rc<Cell<bool>>,
 }
 
 /// Execution state across shared across all components.
 impl Context {
     pub fn new(data: Arc<dyn DataProvider + 'static + Send + Sync>) -> Self {
+        return Self::build(data, SymbolInfo::default());
+    }
+
+    pub fn build(data: Arc<dyn DataProvider + 'static + Send + Sync>, syminfo: SymbolInfo) -> Self {
         let first_bar_index = data.get_start_tick();
-        let last_bar_index = data.get_end_tick();
-        let bars = last_bar_index - first_bar_index + 1;
+        let bars = if data.is_empty() {
+            0
+        } else {
+            data.get_end_tick() - first_bar_index + 1
+        };
 
         let bar = Bar {
             data: Arc::clone(&data),
@@ -103,9 +116,9 @@ impl Context {
         return Self {
             data,
             first_bar_index,
-            last_bar_index,
             bar,
             bars,
+            syminfo: Rc::new(syminfo),
             is_running: Rc::new(Cell::new(false)),
         };
     }
@@ -115,16 +128,23 @@ impl Context {
         return Self {
             data: Arc::clone(&self.data),
             first_bar_index: self.first_bar_index,
-            last_bar_index: self.last_bar_index,
             bars: self.bars,
             bar: Bar {
                 index: Rc::clone(&self.bar.index),
                 data: Arc::clone(&self.data),
             },
+            syminfo: Rc::clone(&self.syminfo),
             is_running: Rc::clone(&self.is_running),
         };
     }
 
+    /// Bar index of the last chart bar. Read from `data` on every call, so bars appended to a streaming provider are included.
+    ///
+    /// Same as PineScript `last_bar_index`.
+    pub fn last_bar_index(&self) -> usize {
+        return self.data.get_end_tick();
+    }
+
     /// Returns **`N`** previous high price.
     pub fn high(&self, n: usize) -> Option<f64> {
         let tick = self.bar.index.get();
@@ -162,34 +182,51 @@ impl Context {
     }
 
     /// Returns a list of **`N`** previous open prices.
-    pub fn opens(&self, length: usize) -> &[Option<f64>] {
+    pub fn opens(&self, length: usize) -> Cow<'_, [Option<f64>]> {
         let tick = self.bar.index.get();
         return self.data.get_open_for_range(tick - (length - 1), tick);
     }
 
     /// Returns a list of **`N`** previous high prices.
-    pub fn highs(&self, length: usize) -> &[Option<f64>] {
+    pub fn highs(&self, length: usize) -> Cow<'_, [Option<f64>]> {
         let tick = self.bar.index.get();
         return self.data.get_high_for_range(tick - (length - 1), tick);
     }
 
     /// Returns a list of **`N`** previous low prices.
-    pub fn lows(&self, length: usize) -> &[Option<f64>] {
+    pub fn lows(&self, length: usize) -> Cow<'_, [Option<f64>]> {
         let tick = self.bar.index.get();
         return self.data.get_low_for_range(tick - (length - 1), tick);
     }
 
     /// Returns a list of **`N`** previous close prices.
-    pub fn closes(&self, length: usize) -> &[Option<f64>] {
+    pub fn closes(&self, length: usize) -> Cow<'_, [Option<f64>]> {
         let tick = self.bar.index.get();
         return self.data.get_close_for_range(tick - (length - 1), tick);
     }
 
     /// Returns a list of **`N`** previous volumes.
-    pub fn volumes(&self, length: usize) -> &[Option<f64>] {
+    pub fn volumes(&self, length: usize) -> Cow<'_, [Option<f64>]> {
         let tick = self.bar.index.get();
         return self.data.get_volume_for_range(tick - (length - 1), tick);
     }
+
+    /// Returns **`N`** previous value of auxiliary series `name`.
+    pub fn series(&self, name: &str, n: usize) -> Option<f64> {
+        let tick = self.bar.index.get();
+        if tick < n {
+            return None;
+        }
+        return self.data.get_series(name, tick - n);
+    }
+
+    /// Returns a list of **`N`** previous values of auxiliary series `name`.
+    pub fn series_values(&self, name: &str, length: usize) -> Cow<'_, [Option<f64>]> {
+        let tick = self.bar.index.get();
+        return self
+            .data
+            .get_series_for_range(name, tick - (length - 1), tick);
+    }
 }
 
 impl Iterator for Context {
@@ -197,17 +234,20 @@ impl Iterator for Context {
 
     fn next(&mut self) -> Option<Self::Item> {
         if !self.is_running.get() {
+            if self.data.is_empty() {
+                return None;
+            }
             self.is_running.set(true);
             return Some(self.first_bar_index);
         }
 
+        // Stays on the last bar, so iteration can resume once bars are appended.
         let current_index = self.bar.index.get() + 1;
-        self.bar.index.set(current_index);
-
-        if current_index <= self.last_bar_index {
-            return Some(current_index);
+        if current_index > self.last_bar_index() {
+            return None;
         }
+        self.bar.index.set(current_index);
 
-        return None;
+        return Some(current_index);
     }
 }
@@ -1,20 +1,42 @@
-use std::{sync::Arc, time::Duration};
+use std::{borrow::Cow, sync::Arc, time::Duration};
 
 /// OHLCV data provider.
 pub trait DataProvider: 'static {
     fn get_start_tick(&self) -> usize;
     fn get_end_tick(&self) -> usize;
+    /// Returns `true` if there are no bars, e.g. in a streaming provider before the first bar is appended.
+    fn is_empty(&self) -> bool {
+        return false;
+    }
     fn get_open(&self, index: usize) -> Option<f64>;
     fn get_high(&self, index: usize) -> Option<f64>;
     fn get_low(&self, index: usize) -> Option<f64>;
     fn get_close(&self, index: usize) -> Option<f64>;
     fn get_volume(&self, index: usize) -> Option<f64>;
     fn get_time(&self, index: usize) -> Option<Duration>;
-    fn get_open_for_range(&self, start_index: usize, end_index: usize) -> &[Option<f64>];
-    fn get_high_for_range(&self, start_index: usize, end_index: usize) -> &[Option<f64>];
-    fn get_low_for_range(&self, start_index: usize, end_index: usize) -> &[Option<f64>];
-    fn get_close_for_range(&self, start_index: usize, end_index: usize) -> &[Option<f64>];
-    fn get_volume_for_range(&self, start_index: usize, end_index: usize) -> &[Option<f64>];
+    /// Values from `start_index` to `end_index`, inclusive. Borrowed if the provider stores them contiguously.
+    fn get_open_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]>;
+    fn get_high_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]>;
+    fn get_low_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]>;
+    fn get_close_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]>;
+    fn get_volume_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]>;
+    /// Names of auxiliary series, like funding rate, open interest or sentiment.
+    fn get_series_names(&self) -> Vec<&str> {
+        return Vec::new();
+    }
+    /// Value of auxiliary series `name`. Returns `None` if the series doesn't exist.
+    fn get_series(&self, _name: &str, _index: usize) -> Option<f64> {
+        return None;
+    }
+    /// Values of auxiliary series `name`. Returns an empty slice if the series doesn't exist.
+    fn get_series_for_range(
+        &self,
+        _name: &str,
+        _start_index: usize,
+        _end_index: usize,
+    ) -> Cow<'_, [Option<f64>]> {
+        return Cow::Borrowed(&[]);
+    }
     fn to_arc(self) -> Arc<dyn DataProvider + Send + Sync>
     where
         Self: Sized + Send + Sync,
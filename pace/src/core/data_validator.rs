@@ -0,0 +1,486 @@
+use std::{collections::HashMap, time::Duration};
+
+use super::{
+    data_provider::DataProvider,
+    error::{PaceError, PaceResult},
+    in_memory_data_provider::InMemoryDataProvider,
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DataIssueKind {
+    /// Open, high, low or close is missing.
+    MissingPrice,
+    /// Open, high, low or close is zero or negative.
+    NonPositivePrice,
+    HighBelowLow,
+    OpenOutOfRange,
+    CloseOutOfRange,
+    MissingVolume,
+    ZeroVolume,
+    NegativeVolume,
+    MissingTime,
+    /// Bar has the same time as the previous bar.
+    DuplicateTime,
+    /// Bar time is earlier than time of the previous bar.
+    OutOfOrderTime,
+    /// Time since the previous bar is larger than `gap_multiplier` times the bar interval.
+    Gap,
+}
+
+impl DataIssueKind {
+    /// Returns `true` if prices of the bar can't be used.
+    pub fn is_invalid_price(&self) -> bool {
+        return matches!(
+            self,
+            DataIssueKind::MissingPrice
+                | DataIssueKind::NonPositivePrice
+                | DataIssueKind::HighBelowLow
+                | DataIssueKind::OpenOutOfRange
+                | DataIssueKind::CloseOutOfRange
+        );
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DataIssue {
+    pub index: usize,
+    pub kind: DataIssueKind,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DataValidationReport {
+    pub bars: usize,
+    /// Expected time between bars. Either configured, or the most common interval.
+    pub interval: Option<Duration>,
+    pub issues: Vec<DataIssue>,
+}
+
+impl DataValidationReport {
+    pub fn is_valid(&self) -> bool {
+        return self.issues.is_empty();
+    }
+
+    pub fn count(&self, kind: DataIssueKind) -> usize {
+        return self
+            .issues
+            .iter()
+            .filter(|issue| issue.kind == kind)
+            .count();
+    }
+
+    /// Issues of bar `index`.
+    pub fn issues_at(&self, index: usize) -> Vec<DataIssueKind> {
+        return self
+            .issues
+            .iter()
+            .filter(|issue| issue.index == index)
+            .map(|issue| issue.kind)
+            .collect();
+    }
+
+    /// Number of issues of every kind.
+    pub fn summary(&self) -> Vec<(DataIssueKind, usize)> {
+        let mut summary: Vec<(DataIssueKind, usize)> = Vec::new();
+        for issue in &self.issues {
+            match summary.iter_mut().find(|(kind, _)| *kind == issue.kind) {
+                Some((_, count)) => *count += 1,
+                None => summary.push((issue.kind, 1)),
+            }
+        }
+        return summary;
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DataValidatorConfig {
+    /// Expected time between bars. If `None`, the most common interval is used.
+    pub interval: Option<Duration>,
+    /// Reports a gap if time since the previous bar is larger than `gap_multiplier * interval`.
+    pub gap_multiplier: f64,
+    /// Reports zero and missing volume.
+    pub check_volume: bool,
+}
+
+impl Default for DataValidatorConfig {
+    fn default() -> Self {
+        return Self {
+            interval: None,
+            gap_multiplier: 1.5,
+            check_volume: true,
+        };
+    }
+}
+
+/// Most common positive time difference between consecutive bars.
+pub fn detect_interval(time: &[Option<Duration>]) -> Option<Duration> {
+    let mut counts: HashMap<Duration, usize> = HashMap::new();
+    for window in time.windows(2) {
+        if let (Some(prev), Some(current)) = (window[0], window[1]) {
+            if current > prev {
+                *counts.entry(current - prev).or_insert(0) += 1;
+            }
+        }
+    }
+    return counts
+        .into_iter()
+        .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.cmp(a)))
+        .map(|(interval, _)| interval);
+}
+
+/// Checks every bar of `data` for inconsistent prices, volume and time.
+pub fn validate_data(data: &dyn DataProvider, config: DataValidatorConfig) -> DataValidationReport {
+    let start = data.get_start_tick();
+    let end = data.get_end_tick();
+    let time: Vec<Option<Duration>> = (start..=end).map(|i| data.get_time(i)).collect();
+    let interval = config.interval.or_else(|| detect_interval(&time));
+
+    let mut issues: Vec<DataIssue> = Vec::new();
+    let mut last_time: Option<Duration> = None;
+
+    for index in start..=end {
+        let mut push = |kind: DataIssueKind| issues.push(DataIssue { index, kind });
+
+        match (
+            data.get_open(index),
+            data.get_high(index),
+            data.get_low(index),
+            data.get_close(index),
+        ) {
+            (Some(open), Some(high), Some(low), Some(close)) => {
+                if open <= 0.0 || high <= 0.0 || low <= 0.0 || close <= 0.0 {
+                    push(DataIssueKind::NonPositivePrice);
+                }
+                if high < low {
+                    push(DataIssueKind::HighBelowLow);
+                } else {
+                    if open > high || open < low {
+                        push(DataIssueKind::OpenOutOfRange);
+                    }
+                    if close > high || close < low {
+                        push(DataIssueKind::CloseOutOfRange);
+                    }
+                }
+            }
+            _ => push(DataIssueKind::MissingPrice),
+        }
+
+        if config.check_volume {
+            match data.get_volume(index) {
+                Some(volume) if volume < 0.0 => push(DataIssueKind::NegativeVolume),
+                Some(0.0) => push(DataIssueKind::ZeroVolume),
+                None => push(DataIssueKind::MissingVolume),
+                _ => {}
+            }
+        } else if data.get_volume(index).is_some_and(|volume| volume < 0.0) {
+            push(DataIssueKind::NegativeVolume);
+        }
+
+        match data.get_time(index) {
+            Some(time) => {
+                if let Some(last_time) = last_time {
+                    if time == last_time {
+                        push(DataIssueKind::DuplicateTime);
+                    } else if time < last_time {
+                        push(DataIssueKind::OutOfOrderTime);
+                    } else if let Some(interval) = interval {
+                        if (time - last_time).as_secs_f64()
+                            > interval.as_secs_f64() * config.gap_multiplier
+                        {
+                            push(DataIssueKind::Gap);
+                        }
+                    }
+                }
+                last_time = Some(last_time.map_or(time, |last_time| last_time.max(time)));
+            }
+            None => push(DataIssueKind::MissingTime),
+        }
+    }
+
+    return DataValidationReport {
+        bars: end + 1 - start,
+        interval,
+        issues,
+    };
+}
+
+/// How bars with invalid prices are repaired.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DataRepairPolicy {
+    /// Removes the bar.
+    Drop,
+    /// Replaces prices with the previous close, volume with `0`. Invalid bars without a previous bar are removed.
+    ForwardFill,
+    /// Interpolates prices and volume linearly between the neighbouring valid bars.
+    Interpolate,
+}
+
+/// How bars are inserted into gaps.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DataGapFill {
+    /// Inserts flat bars at the previous close, with `0` volume.
+    ForwardFill,
+    /// Inserts bars with values interpolated linearly between bars around the gap.
+    Interpolate,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DataRepairConfig {
+    /// Repairs bars with missing or inconsistent prices. If `None`, they are kept.
+    pub invalid_bars: Option<DataRepairPolicy>,
+    /// Sorts bars by time and keeps the last of bars with the same time.
+    pub reorder: bool,
+    /// Inserts bars into gaps. Requires `interval` or a detectable interval. If `None`, gaps are kept.
+    pub fill_gaps: Option<DataGapFill>,
+    /// Expected time between bars. If `None`, the most common interval is used.
+    pub interval: Option<Duration>,
+}
+
+impl Default for DataRepairConfig {
+    fn default() -> Self {
+        return Self {
+            invalid_bars: Some(DataRepairPolicy::Drop),
+            reorder: true,
+            fill_gaps: None,
+            interval: None,
+        };
+    }
+}
+
+#[derive(Clone)]
+struct DataRow {
+    time: Option<Duration>,
+    values: Vec<Option<f64>>,
+}
+
+const OPEN: usize = 0;
+const HIGH: usize = 1;
+const LOW: usize = 2;
+const CLOSE: usize = 3;
+const VOLUME: usize = 4;
+
+impl DataRow {
+    fn is_valid(&self) -> bool {
+        let (open, high, low, close) = match (
+            self.values[OPEN],
+            self.values[HIGH],
+            self.values[LOW],
+            self.values[CLOSE],
+        ) {
+            (Some(open), Some(high), Some(low), Some(close)) => (open, high, low, close),
+            _ => return false,
+        };
+        return low > 0.0
+            && high >= low
+            && open >= low
+            && open <= high
+            && close >= low
+            && close <= high;
+    }
+
+    fn prices(&self) -> DataPrices {
+        return [
+            self.values[OPEN],
+            self.values[HIGH],
+            self.values[LOW],
+            self.values[CLOSE],
+            self.values[VOLUME],
+        ];
+    }
+
+    /// Flat bar at `price`. Auxiliary series keep their values.
+    fn flat(&self, time: Option<Duration>, price: Option<f64>) -> Self {
+        let mut values = self.values.clone();
+        values[OPEN] = price;
+        values[HIGH] = price;
+        values[LOW] = price;
+        values[CLOSE] = price;
+        values[VOLUME] = Some(0.0);
+        return Self { time, values };
+    }
+
+    /// Values at `ratio` between `self` and `next`.
+    fn lerp(&self, next: &DataRow, ratio: f64, time: Option<Duration>) -> Self {
+        let values = self
+            .values
+            .iter()
+            .zip(next.values.iter())
+            .map(|(a, b)| match (a, b) {
+                (Some(a), Some(b)) => Some(a + (b - a) * ratio),
+                _ => *a,
+            })
+            .collect();
+        return Self { time, values };
+    }
+}
+
+/// Returns a repaired copy of `data`. Auxiliary series are preserved.
+pub fn repair_data(
+    data: &dyn DataProvider,
+    config: DataRepairConfig,
+) -> PaceResult<InMemoryDataProvider> {
+    let series_names: Vec<String> = data
+        .get_series_names()
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+
+    let mut rows: Vec<DataRow> = (data.get_start_tick()..=data.get_end_tick())
+        .map(|index| {
+            let mut values = vec![
+                data.get_open(index),
+                data.get_high(index),
+                data.get_low(index),
+                data.get_close(index),
+                data.get_volume(index),
+            ];
+            values.extend(series_names.iter().map(|name| data.get_series(name, index)));
+            DataRow {
+                time: data.get_time(index),
+                values,
+            }
+        })
+        .collect();
+
+    if config.reorder && rows.iter().all(|row| row.time.is_some()) {
+        rows.sort_by_key(|row| row.time);
+        // Keeps the last of rows with the same time.
+        rows.reverse();
+        rows.dedup_by_key(|row| row.time);
+        rows.reverse();
+    }
+
+    if let Some(policy) = config.invalid_bars {
+        rows = repair_invalid_rows(rows, policy);
+    }
+
+    if let Some(fill) = config.fill_gaps {
+        let time: Vec<Option<Duration>> = rows.iter().map(|row| row.time).collect();
+        if let Some(interval) = config.interval.or_else(|| detect_interval(&time)) {
+            rows = fill_gaps(rows, fill, interval);
+        }
+    }
+
+    if rows.is_empty() {
+        return Err(PaceError::InvalidData(
+            "No valid bars left after repair".to_string(),
+        ));
+    }
+
+    let column = |i: usize| -> Vec<Option<f64>> { rows.iter().map(|row| row.values[i]).collect() };
+
+    let mut repaired = InMemoryDataProvider::new(
+        column(OPEN),
+        column(HIGH),
+        column(LOW),
+        column(CLOSE),
+        column(VOLUME),
+        rows.iter().map(|row| row.time).collect(),
+    );
+    for (i, name) in series_names.iter().enumerate() {
+        repaired.add_series(name, column(VOLUME + 1 + i))?;
+    }
+
+    return Ok(repaired);
+}
+
+fn repair_invalid_rows(rows: Vec<DataRow>, policy: DataRepairPolicy) -> Vec<DataRow> {
+    let is_valid: Vec<bool> = rows.iter().map(|row| row.is_valid()).collect();
+
+    return match policy {
+        DataRepairPolicy::Drop => rows.into_iter().filter(|row| row.is_valid()).collect(),
+        DataRepairPolicy::ForwardFill => {
+            let mut repaired: Vec<DataRow> = Vec::with_capacity(rows.len());
+            for (row, is_valid) in rows.into_iter().zip(is_valid) {
+                if is_valid {
+                    repaired.push(row);
+                } else if let Some(prev) = repaired.last() {
+                    let close = prev.values[CLOSE];
+                    repaired.push(row.flat(row.time, close));
+                }
+            }
+            repaired
+        }
+        DataRepairPolicy::Interpolate => {
+            let valid: Vec<usize> = (0..rows.len()).filter(|i| is_valid[*i]).collect();
+            if valid.is_empty() {
+                return Vec::new();
+            }
+            let mut rows = rows;
+            // Every gap is interpolated once, between the valid rows around it.
+            for gap in 0..=valid.len() {
+                let prev = gap.checked_sub(1).map(|gap| valid[gap]);
+                let next = valid.get(gap).copied();
+                let start = prev.map_or(0, |prev| prev + 1);
+                let end = next.unwrap_or(rows.len());
+                let prev_prices = prev.map(|prev| rows[prev].prices());
+                let next_prices = next.map(|next| rows[next].prices());
+
+                for (offset, row) in rows[start..end].iter_mut().enumerate() {
+                    let prices = match (prev_prices, next_prices) {
+                        (Some(prev_prices), Some(next_prices)) => {
+                            let ratio = (offset + 1) as f64 / (end - start + 1) as f64;
+                            lerp_prices(prev_prices, next_prices, ratio)
+                        }
+                        (Some(prev_prices), None) => flat_prices(prev_prices[CLOSE]),
+                        (None, Some(next_prices)) => flat_prices(next_prices[OPEN]),
+                        (None, None) => unreachable!(),
+                    };
+                    // Auxiliary series keep their own values.
+                    row.values[..=VOLUME].copy_from_slice(&prices);
+                }
+            }
+            rows
+        }
+    };
+}
+
+type DataPrices = [Option<f64>; VOLUME + 1];
+
+/// Prices at `price`, with `0` volume.
+fn flat_prices(price: Option<f64>) -> DataPrices {
+    return [price, price, price, price, Some(0.0)];
+}
+
+/// Prices at `ratio` between `prev` and `next`.
+fn lerp_prices(prev: DataPrices, next: DataPrices, ratio: f64) -> DataPrices {
+    return std::array::from_fn(|i| match (prev[i], next[i]) {
+        (Some(a), Some(b)) => Some(a + (b - a) * ratio),
+        _ => prev[i],
+    });
+}
+
+fn fill_gaps(rows: Vec<DataRow>, fill: DataGapFill, interval: Duration) -> Vec<DataRow> {
+    let mut filled: Vec<DataRow> = Vec::with_capacity(rows.len());
+    let mut rows = rows.into_iter().peekable();
+
+    while let Some(row) = rows.next() {
+        let prev = filled.len();
+        filled.push(row);
+
+        let next = match rows.peek() {
+            Some(next) => next,
+            None => break,
+        };
+        if let (Some(prev_time), Some(time)) = (filled[prev].time, next.time) {
+            let mut next_time = prev_time + interval;
+            while next_time < time {
+                let inserted = match fill {
+                    DataGapFill::ForwardFill => {
+                        filled[prev].flat(Some(next_time), filled[prev].values[CLOSE])
+                    }
+                    DataGapFill::Interpolate => {
+                        let ratio = (next_time - prev_time).as_secs_f64()
+                            / (time - prev_time).as_secs_f64();
+                        let mut inserted = filled[prev].lerp(next, ratio, Some(next_time));
+                        inserted.values[VOLUME] = Some(0.0);
+                        inserted
+                    }
+                };
+                filled.push(inserted);
+                next_time += interval;
+            }
+        }
+    }
+
+    return filled;
+}
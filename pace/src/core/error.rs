@@ -0,0 +1,99 @@
+use std::{fmt, path::PathBuf};
+
+use polars::prelude::PolarsError;
+
+/// Errors returned by Pace loaders, savers and constructors.
+#[derive(Debug)]
+pub enum PaceError {
+    /// File could not be opened, created or written.
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// File extension is not supported by the reader or writer.
+    UnsupportedFileType {
+        path: PathBuf,
+        extension: Option<String>,
+    },
+    /// Required column is not present in the data frame.
+    MissingColumn(String),
+    /// Value could not be converted to the required type.
+    InvalidValue {
+        column: String,
+        index: Option<usize>,
+        value: Option<String>,
+        expected: String,
+    },
+    /// Data is present, but not usable, e.g. columns of different length.
+    InvalidData(String),
+    Polars(PolarsError),
+    #[cfg(feature = "store")]
+    Sqlite(rusqlite::Error),
+}
+
+pub type PaceResult<T> = Result<T, PaceError>;
+
+impl fmt::Display for PaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            PaceError::Io { path, source } => {
+                write!(f, "Failed to access \"{}\": {}", path.display(), source)
+            }
+            PaceError::UnsupportedFileType { path, extension } => match extension {
+                Some(extension) => write!(
+                    f,
+                    "Unsupported file type \".{}\" of \"{}\"",
+                    extension,
+                    path.display()
+                ),
+                None => write!(f, "Missing file extension of \"{}\"", path.display()),
+            },
+            PaceError::MissingColumn(column) => write!(f, "Missing column \"{}\"", column),
+            PaceError::InvalidValue {
+                column,
+                index,
+                value,
+                expected,
+            } => {
+                write!(f, "Invalid value")?;
+                if let Some(value) = value {
+                    write!(f, " {}", value)?;
+                }
+                write!(f, " in column \"{}\"", column)?;
+                if let Some(index) = index {
+                    write!(f, " at row {}", index)?;
+                }
+                write!(f, ", expected {}", expected)
+            }
+            PaceError::InvalidData(message) => write!(f, "Invalid data: {}", message),
+            PaceError::Polars(error) => write!(f, "{}", error),
+            #[cfg(feature = "store")]
+            PaceError::Sqlite(error) => write!(f, "{}", error),
+        };
+    }
+}
+
+impl std::error::Error for PaceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        return match self {
+            PaceError::Io { source, .. } => Some(source),
+            PaceError::Polars(error) => Some(error),
+            #[cfg(feature = "store")]
+            PaceError::Sqlite(error) => Some(error),
+            _ => None,
+        };
+    }
+}
+
+impl From<PolarsError> for PaceError {
+    fn from(error: PolarsError) -> Self {
+        return PaceError::Polars(error);
+    }
+}
+
+#[cfg(feature = "store")]
+impl From<rusqlite::Error> for PaceError {
+    fn from(error: rusqlite::Error) -> Self {
+        return PaceError::Sqlite(error);
+    }
+}
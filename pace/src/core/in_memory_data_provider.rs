@@ -1,8 +1,9 @@
-use std::{sync::Arc, time::Duration};
+use std::{borrow::Cow, collections::BTreeMap, time::Duration};
 
-use polars::prelude::DataFrame;
-
-use super::data_provider::DataProvider;
+use super::{
+    data_provider::DataProvider,
+    error::{PaceError, PaceResult},
+};
 
 /// Implements `DataProvider`. Stores all data in memory.
 pub struct InMemoryDataProvider {
@@ -12,6 +13,7 @@ pub struct InMemoryDataProvider {
     close: Vec<Option<f64>>,
     volume: Vec<Option<f64>>,
     time: Vec<Option<Duration>>,
+    series: BTreeMap<String, Vec<Option<f64>>>,
     start_tick: usize,
     end_tick: usize,
 }
@@ -49,24 +51,44 @@ impl DataProvider for InMemoryDataProvider {
         return self.time[index];
     }
 
-    fn get_open_for_range(&self, start_index: usize, end_index: usize) -> &[Option<f64>] {
-        return &self.open[start_index..end_index + 1];
+    fn get_open_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return Cow::Borrowed(&self.open[start_index..end_index + 1]);
+    }
+
+    fn get_high_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return Cow::Borrowed(&self.high[start_index..end_index + 1]);
+    }
+
+    fn get_low_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return Cow::Borrowed(&self.low[start_index..end_index + 1]);
     }
 
-    fn get_high_for_range(&self, start_index: usize, end_index: usize) -> &[Option<f64>] {
-        return &self.high[start_index..end_index + 1];
+    fn get_close_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return Cow::Borrowed(&self.close[start_index..end_index + 1]);
     }
 
-    fn get_low_for_range(&self, start_index: usize, end_index: usize) -> &[Option<f64>] {
-        return &self.low[start_index..end_index + 1];
+    fn get_volume_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return Cow::Borrowed(&self.volume[start_index..end_index + 1]);
     }
 
-    fn get_close_for_range(&self, start_index: usize, end_index: usize) -> &[Option<f64>] {
-        return &self.close[start_index..end_index + 1];
+    fn get_series_names(&self) -> Vec<&str> {
+        return self.series.keys().map(|name| name.as_str()).collect();
     }
 
-    fn get_volume_for_range(&self, start_index: usize, end_index: usize) -> &[Option<f64>] {
-        return &self.volume[start_index..end_index + 1];
+    fn get_series(&self, name: &str, index: usize) -> Option<f64> {
+        return self.series.get(name).and_then(|values| values[index]);
+    }
+
+    fn get_series_for_range(
+        &self,
+        name: &str,
+        start_index: usize,
+        end_index: usize,
+    ) -> Cow<'_, [Option<f64>]> {
+        return match self.series.get(name) {
+            Some(values) => Cow::Borrowed(&values[start_index..end_index + 1]),
+            None => Cow::Borrowed(&[]),
+        };
     }
 }
 
@@ -91,9 +113,24 @@ impl InMemoryDataProvider {
             start_tick,
             end_tick,
             time,
+            series: BTreeMap::new(),
         };
     }
 
+    /// Adds auxiliary series `name`, replacing an existing one. Must have one value per bar.
+    pub fn add_series(&mut self, name: &str, values: Vec<Option<f64>>) -> PaceResult<()> {
+        if values.len() != self.close.len() {
+            return Err(PaceError::InvalidData(format!(
+                "Series \"{}\" has {} values, expected {}",
+                name,
+                values.len(),
+                self.close.len()
+            )));
+        }
+        self.series.insert(name.to_string(), values);
+        return Ok(());
+    }
+
     pub fn from_values(values: Vec<Option<f64>>) -> Self {
         return Self {
             open: values.clone(),
@@ -104,6 +141,7 @@ impl InMemoryDataProvider {
             start_tick: 0,
             end_tick: values.len() - 1,
             time: vec![None; values.len()],
+            series: BTreeMap::new(),
         };
     }
 }
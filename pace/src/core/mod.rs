@@ -1,5 +1,11 @@
 pub mod context;
 pub mod data_provider;
+pub mod data_validator;
+pub mod error;
 pub mod in_memory_data_provider;
 pub mod incremental;
+pub mod streaming_data_provider;
+pub mod tick_aggregator;
 pub mod timeframe;
+
+mod tests;
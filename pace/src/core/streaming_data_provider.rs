@@ -0,0 +1,185 @@
+use std::{borrow::Cow, sync::RwLock, time::Duration};
+
+use super::{
+    data_provider::DataProvider,
+    error::{PaceError, PaceResult},
+};
+
+/// Bar appended to `StreamingDataProvider`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StreamingBar {
+    pub open: Option<f64>,
+    pub high: Option<f64>,
+    pub low: Option<f64>,
+    pub close: Option<f64>,
+    pub volume: Option<f64>,
+    pub time: Option<Duration>,
+    /// Values of auxiliary series, in the order of `StreamingDataProvider` series names.
+    pub series: Vec<Option<f64>>,
+}
+
+#[derive(Default)]
+struct StreamingData {
+    open: Vec<Option<f64>>,
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    volume: Vec<Option<f64>>,
+    time: Vec<Option<Duration>>,
+    series: Vec<Vec<Option<f64>>>,
+}
+
+/// Implements `DataProvider` over bars appended while it's shared, e.g. by `TickAggregator.push_into`.
+///
+/// `Context` reads the last bar index on every step, so bars appended after its creation are iterated by the next `ctx.next()`. Ranges are returned as copies.
+pub struct StreamingDataProvider {
+    series_names: Vec<String>,
+    data: RwLock<StreamingData>,
+}
+
+impl StreamingDataProvider {
+    /// Creates an empty data provider with auxiliary series `series_names`.
+    pub fn new(series_names: &[&str]) -> Self {
+        return Self {
+            series_names: series_names.iter().map(|name| name.to_string()).collect(),
+            data: RwLock::new(StreamingData {
+                series: vec![Vec::new(); series_names.len()],
+                ..StreamingData::default()
+            }),
+        };
+    }
+
+    /// Appends a bar. Returns an error if it doesn't have one value per auxiliary series.
+    pub fn push(&self, bar: StreamingBar) -> PaceResult<()> {
+        if bar.series.len() != self.series_names.len() {
+            return Err(PaceError::InvalidData(format!(
+                "Bar has {} series values, expected {}",
+                bar.series.len(),
+                self.series_names.len()
+            )));
+        }
+        let mut data = self.data.write().unwrap();
+        data.open.push(bar.open);
+        data.high.push(bar.high);
+        data.low.push(bar.low);
+        data.close.push(bar.close);
+        data.volume.push(bar.volume);
+        data.time.push(bar.time);
+        for (values, value) in data.series.iter_mut().zip(bar.series) {
+            values.push(value);
+        }
+        return Ok(());
+    }
+
+    pub fn len(&self) -> usize {
+        return self.data.read().unwrap().close.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    fn series_index(&self, name: &str) -> Option<usize> {
+        return self.series_names.iter().position(|series| series == name);
+    }
+
+    fn get(
+        &self,
+        column: impl Fn(&StreamingData) -> &Vec<Option<f64>>,
+        index: usize,
+    ) -> Option<f64> {
+        return column(&self.data.read().unwrap())
+            .get(index)
+            .copied()
+            .flatten();
+    }
+
+    fn get_range(
+        &self,
+        column: impl Fn(&StreamingData) -> &Vec<Option<f64>>,
+        start_index: usize,
+        end_index: usize,
+    ) -> Cow<'_, [Option<f64>]> {
+        return Cow::Owned(column(&self.data.read().unwrap())[start_index..=end_index].to_vec());
+    }
+}
+
+impl DataProvider for StreamingDataProvider {
+    fn get_start_tick(&self) -> usize {
+        return 0;
+    }
+
+    /// `0` if there are no bars yet. See `is_empty`.
+    fn get_end_tick(&self) -> usize {
+        return self.len().saturating_sub(1);
+    }
+
+    fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    fn get_open(&self, index: usize) -> Option<f64> {
+        return self.get(|data| &data.open, index);
+    }
+
+    fn get_high(&self, index: usize) -> Option<f64> {
+        return self.get(|data| &data.high, index);
+    }
+
+    fn get_low(&self, index: usize) -> Option<f64> {
+        return self.get(|data| &data.low, index);
+    }
+
+    fn get_close(&self, index: usize) -> Option<f64> {
+        return self.get(|data| &data.close, index);
+    }
+
+    fn get_volume(&self, index: usize) -> Option<f64> {
+        return self.get(|data| &data.volume, index);
+    }
+
+    fn get_time(&self, index: usize) -> Option<Duration> {
+        return self.data.read().unwrap().time.get(index).copied().flatten();
+    }
+
+    fn get_open_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return self.get_range(|data| &data.open, start_index, end_index);
+    }
+
+    fn get_high_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return self.get_range(|data| &data.high, start_index, end_index);
+    }
+
+    fn get_low_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return self.get_range(|data| &data.low, start_index, end_index);
+    }
+
+    fn get_close_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return self.get_range(|data| &data.close, start_index, end_index);
+    }
+
+    fn get_volume_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return self.get_range(|data| &data.volume, start_index, end_index);
+    }
+
+    fn get_series_names(&self) -> Vec<&str> {
+        return self.series_names.iter().map(|name| name.as_str()).collect();
+    }
+
+    fn get_series(&self, name: &str, index: usize) -> Option<f64> {
+        let series = self.series_index(name)?;
+        return self.get(|data| &data.series[series], index);
+    }
+
+    fn get_series_for_range(
+        &self,
+        name: &str,
+        start_index: usize,
+        end_index: usize,
+    ) -> Cow<'_, [Option<f64>]> {
+        return match self.series_index(name) {
+            Some(series) => self.get_range(|data| &data.series[series], start_index, end_index),
+            None => Cow::Borrowed(&[]),
+        };
+    }
+}
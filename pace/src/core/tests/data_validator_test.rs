@@ -0,0 +1,300 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::core::{
+        data_provider::DataProvider,
+        data_validator::{
+            detect_interval, repair_data, validate_data, DataGapFill, DataIssue, DataIssueKind,
+            DataRepairConfig, DataRepairPolicy, DataValidatorConfig,
+        },
+        in_memory_data_provider::InMemoryDataProvider,
+    };
+
+    fn secs(values: &[u64]) -> Vec<Option<Duration>> {
+        return values
+            .iter()
+            .map(|value| Some(Duration::from_secs(*value)))
+            .collect();
+    }
+
+    /// Bars from `(open, high, low, close)` with volume `1`.
+    fn bars(prices: &[(f64, f64, f64, f64)], time: &[u64]) -> InMemoryDataProvider {
+        return InMemoryDataProvider::new(
+            prices.iter().map(|bar| Some(bar.0)).collect(),
+            prices.iter().map(|bar| Some(bar.1)).collect(),
+            prices.iter().map(|bar| Some(bar.2)).collect(),
+            prices.iter().map(|bar| Some(bar.3)).collect(),
+            vec![Some(1.0); prices.len()],
+            secs(time),
+        );
+    }
+
+    fn closes(data: &InMemoryDataProvider) -> Vec<Option<f64>> {
+        return (data.get_start_tick()..=data.get_end_tick())
+            .map(|i| data.get_close(i))
+            .collect();
+    }
+
+    fn times(data: &InMemoryDataProvider) -> Vec<Option<Duration>> {
+        return (data.get_start_tick()..=data.get_end_tick())
+            .map(|i| data.get_time(i))
+            .collect();
+    }
+
+    #[test]
+    fn detects_most_common_interval() {
+        assert_eq!(
+            detect_interval(&secs(&[0, 60, 120, 300, 360])),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(detect_interval(&secs(&[0])), None);
+    }
+
+    #[test]
+    fn valid_data() {
+        let data = bars(&[(1.0, 2.0, 0.5, 1.5), (1.5, 1.6, 1.4, 1.6)], &[0, 60]);
+        let report = validate_data(&data, DataValidatorConfig::default());
+        assert!(report.is_valid());
+        assert_eq!(report.bars, 2);
+        assert_eq!(report.interval, Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn reports_price_issues() {
+        let data = InMemoryDataProvider::new(
+            vec![Some(1.0), Some(1.0), Some(3.0), Some(1.0), Some(0.0), None],
+            vec![
+                Some(2.0),
+                Some(0.5),
+                Some(2.0),
+                Some(2.0),
+                Some(2.0),
+                Some(1.0),
+            ],
+            vec![
+                Some(0.5),
+                Some(2.0),
+                Some(0.5),
+                Some(0.5),
+                Some(0.0),
+                Some(1.0),
+            ],
+            vec![
+                Some(1.5),
+                Some(1.0),
+                Some(1.0),
+                Some(2.5),
+                Some(1.0),
+                Some(1.0),
+            ],
+            vec![Some(1.0); 6],
+            secs(&[0, 60, 120, 180, 240, 300]),
+        );
+
+        let report = validate_data(&data, DataValidatorConfig::default());
+        assert_eq!(report.issues_at(0), vec![]);
+        assert_eq!(report.issues_at(1), vec![DataIssueKind::HighBelowLow]);
+        assert_eq!(report.issues_at(2), vec![DataIssueKind::OpenOutOfRange]);
+        assert_eq!(report.issues_at(3), vec![DataIssueKind::CloseOutOfRange]);
+        assert_eq!(report.issues_at(4), vec![DataIssueKind::NonPositivePrice]);
+        assert_eq!(report.issues_at(5), vec![DataIssueKind::MissingPrice]);
+        assert!(report
+            .issues
+            .iter()
+            .all(|issue| issue.kind.is_invalid_price()));
+    }
+
+    #[test]
+    fn reports_volume_issues() {
+        let data = InMemoryDataProvider::new(
+            vec![Some(1.0); 4],
+            vec![Some(1.0); 4],
+            vec![Some(1.0); 4],
+            vec![Some(1.0); 4],
+            vec![Some(1.0), Some(0.0), Some(-1.0), None],
+            secs(&[0, 60, 120, 180]),
+        );
+
+        let report = validate_data(&data, DataValidatorConfig::default());
+        assert_eq!(
+            report.issues,
+            vec![
+                DataIssue {
+                    index: 1,
+                    kind: DataIssueKind::ZeroVolume
+                },
+                DataIssue {
+                    index: 2,
+                    kind: DataIssueKind::NegativeVolume
+                },
+                DataIssue {
+                    index: 3,
+                    kind: DataIssueKind::MissingVolume
+                },
+            ]
+        );
+
+        let report = validate_data(
+            &data,
+            DataValidatorConfig {
+                check_volume: false,
+                ..DataValidatorConfig::default()
+            },
+        );
+        assert_eq!(report.summary(), vec![(DataIssueKind::NegativeVolume, 1)]);
+    }
+
+    #[test]
+    fn reports_time_issues() {
+        let data = bars(&[(1.0, 1.0, 1.0, 1.0); 7], &[0, 60, 60, 180, 120, 240, 300]);
+
+        let report = validate_data(&data, DataValidatorConfig::default());
+        assert_eq!(report.issues_at(2), vec![DataIssueKind::DuplicateTime]);
+        assert_eq!(report.issues_at(3), vec![DataIssueKind::Gap]);
+        assert_eq!(report.issues_at(4), vec![DataIssueKind::OutOfOrderTime]);
+        // Compared with the latest time, not the out of order bar.
+        assert_eq!(report.issues_at(5), vec![]);
+        assert_eq!(report.count(DataIssueKind::Gap), 1);
+    }
+
+    #[test]
+    fn gap_uses_configured_interval() {
+        let data = bars(&[(1.0, 1.0, 1.0, 1.0); 3], &[0, 60, 180]);
+
+        let report = validate_data(
+            &data,
+            DataValidatorConfig {
+                interval: Some(Duration::from_secs(120)),
+                ..DataValidatorConfig::default()
+            },
+        );
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn repair_reorders_and_drops_duplicates() {
+        let data = bars(
+            &[
+                (1.0, 1.0, 1.0, 1.0),
+                (3.0, 3.0, 3.0, 3.0),
+                (2.0, 2.0, 2.0, 2.0),
+                (4.0, 4.0, 4.0, 4.0),
+            ],
+            &[0, 120, 60, 120],
+        );
+
+        let repaired = repair_data(&data, DataRepairConfig::default()).unwrap();
+        assert_eq!(closes(&repaired), vec![Some(1.0), Some(2.0), Some(4.0)]);
+        assert_eq!(times(&repaired), secs(&[0, 60, 120]));
+        assert!(validate_data(&repaired, DataValidatorConfig::default()).is_valid());
+    }
+
+    fn with_invalid_bar() -> InMemoryDataProvider {
+        return bars(
+            &[
+                (1.0, 1.0, 1.0, 1.0),
+                (2.0, 1.0, 3.0, 2.0),
+                (3.0, 3.0, 3.0, 3.0),
+            ],
+            &[0, 60, 120],
+        );
+    }
+
+    #[test]
+    fn repair_drops_invalid_bars() {
+        let repaired = repair_data(&with_invalid_bar(), DataRepairConfig::default()).unwrap();
+        assert_eq!(closes(&repaired), vec![Some(1.0), Some(3.0)]);
+        assert_eq!(times(&repaired), secs(&[0, 120]));
+    }
+
+    #[test]
+    fn repair_forward_fills_invalid_bars() {
+        let repaired = repair_data(
+            &with_invalid_bar(),
+            DataRepairConfig {
+                invalid_bars: Some(DataRepairPolicy::ForwardFill),
+                ..DataRepairConfig::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(closes(&repaired), vec![Some(1.0), Some(1.0), Some(3.0)]);
+        assert_eq!(repaired.get_high(1), Some(1.0));
+        assert_eq!(repaired.get_volume(1), Some(0.0));
+        assert_eq!(times(&repaired), secs(&[0, 60, 120]));
+    }
+
+    #[test]
+    fn repair_interpolates_invalid_bars() {
+        let repaired = repair_data(
+            &with_invalid_bar(),
+            DataRepairConfig {
+                invalid_bars: Some(DataRepairPolicy::Interpolate),
+                ..DataRepairConfig::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(closes(&repaired), vec![Some(1.0), Some(2.0), Some(3.0)]);
+        assert_eq!(repaired.get_low(1), Some(2.0));
+        assert_eq!(repaired.get_volume(1), Some(1.0));
+    }
+
+    #[test]
+    fn repair_fills_gaps() {
+        let data = bars(
+            &[
+                (1.0, 1.0, 1.0, 1.0),
+                (1.0, 1.0, 1.0, 1.0),
+                (4.0, 4.0, 4.0, 4.0),
+            ],
+            &[0, 60, 240],
+        );
+
+        let config = DataRepairConfig {
+            fill_gaps: Some(DataGapFill::ForwardFill),
+            ..DataRepairConfig::default()
+        };
+        let repaired = repair_data(&data, config).unwrap();
+        assert_eq!(times(&repaired), secs(&[0, 60, 120, 180, 240]));
+        assert_eq!(
+            closes(&repaired),
+            vec![Some(1.0), Some(1.0), Some(1.0), Some(1.0), Some(4.0)]
+        );
+        assert_eq!(repaired.get_volume(2), Some(0.0));
+
+        let repaired = repair_data(
+            &data,
+            DataRepairConfig {
+                fill_gaps: Some(DataGapFill::Interpolate),
+                ..config
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            closes(&repaired),
+            vec![Some(1.0), Some(1.0), Some(2.0), Some(3.0), Some(4.0)]
+        );
+        assert!(validate_data(&repaired, DataValidatorConfig::default())
+            .issues
+            .iter()
+            .all(|issue| issue.kind == DataIssueKind::ZeroVolume));
+    }
+
+    #[test]
+    fn repair_keeps_series() {
+        let mut data = with_invalid_bar();
+        data.add_series("funding", vec![Some(0.1), Some(0.2), Some(0.3)])
+            .unwrap();
+
+        let repaired = repair_data(&data, DataRepairConfig::default()).unwrap();
+        assert_eq!(repaired.get_series_names(), vec!["funding"]);
+        assert_eq!(repaired.get_series("funding", 0), Some(0.1));
+        assert_eq!(repaired.get_series("funding", 1), Some(0.3));
+    }
+
+    #[test]
+    fn repair_without_valid_bars() {
+        let data = bars(&[(1.0, 0.5, 2.0, 1.0)], &[0]);
+        assert!(repair_data(&data, DataRepairConfig::default()).is_err());
+    }
+}
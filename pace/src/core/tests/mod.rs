@@ -0,0 +1,2 @@
+mod data_validator_test;
+mod tick_aggregator_test;
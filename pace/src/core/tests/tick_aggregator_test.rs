@@ -0,0 +1,245 @@
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use crate::{
+        core::{
+            context::Context,
+            data_provider::DataProvider,
+            error::PaceError,
+            incremental::Incremental,
+            streaming_data_provider::{StreamingBar, StreamingDataProvider},
+            tick_aggregator::{
+                aggregate_ticks, AggregatedBar, BarKind, Tick, TickAggregator,
+                AGGREGATED_BAR_SERIES,
+            },
+        },
+        ta::simple_moving_average::Sma,
+    };
+
+    fn tick(seconds: u64, price: f64, size: f64) -> Tick {
+        return Tick {
+            time: Duration::from_secs(seconds),
+            price,
+            size,
+        };
+    }
+
+    fn ticks() -> Vec<Tick> {
+        return vec![
+            tick(0, 10.0, 1.0),
+            tick(20, 12.0, 2.0),
+            tick(59, 11.0, 1.0),
+            tick(60, 11.5, 4.0),
+            tick(150, 9.0, 1.0),
+            tick(170, 10.0, 3.0),
+        ];
+    }
+
+    #[test]
+    fn time_bars() {
+        let data = aggregate_ticks(ticks(), BarKind::Time(Duration::from_secs(60))).unwrap();
+
+        // 60-120s has a single tick and 120-180s is closed at the end of the ticks.
+        assert_eq!(data.get_end_tick(), 2);
+        assert_eq!(data.get_series_names(), AGGREGATED_BAR_SERIES.to_vec());
+
+        assert_eq!(data.get_time(0), Some(Duration::from_secs(0)));
+        assert_eq!(data.get_open(0), Some(10.0));
+        assert_eq!(data.get_high(0), Some(12.0));
+        assert_eq!(data.get_low(0), Some(10.0));
+        assert_eq!(data.get_close(0), Some(11.0));
+        assert_eq!(data.get_volume(0), Some(4.0));
+        assert_eq!(data.get_series("trades", 0), Some(3.0));
+        assert_eq!(data.get_series("vwap", 0), Some((10.0 + 24.0 + 11.0) / 4.0));
+
+        assert_eq!(data.get_time(1), Some(Duration::from_secs(60)));
+        assert_eq!(data.get_close(1), Some(11.5));
+        assert_eq!(data.get_series("trades", 1), Some(1.0));
+
+        // Empty interval 120-180s is skipped.
+        assert_eq!(data.get_time(2), Some(Duration::from_secs(120)));
+        assert_eq!(data.get_open(2), Some(9.0));
+        assert_eq!(data.get_series("vwap", 2), Some(39.0 / 4.0));
+    }
+
+    #[test]
+    fn tick_bars() {
+        let data = aggregate_ticks(ticks(), BarKind::Tick(4)).unwrap();
+
+        assert_eq!(data.get_end_tick(), 1);
+        assert_eq!(data.get_time(0), Some(Duration::from_secs(0)));
+        assert_eq!(data.get_close(0), Some(11.5));
+        assert_eq!(data.get_volume(0), Some(8.0));
+        assert_eq!(data.get_series("trades", 0), Some(4.0));
+        assert_eq!(data.get_time(1), Some(Duration::from_secs(150)));
+        assert_eq!(data.get_series("trades", 1), Some(2.0));
+    }
+
+    #[test]
+    fn volume_bars() {
+        let data = aggregate_ticks(ticks(), BarKind::Volume(3.0)).unwrap();
+
+        // Ticks are not split, so the second bar has volume 4.
+        assert_eq!(data.get_end_tick(), 2);
+        assert_eq!(data.get_volume(0), Some(3.0));
+        assert_eq!(data.get_close(0), Some(12.0));
+        assert_eq!(data.get_volume(1), Some(5.0));
+        assert_eq!(data.get_time(1), Some(Duration::from_secs(59)));
+        assert_eq!(data.get_volume(2), Some(4.0));
+        assert_eq!(data.get_series("trades", 2), Some(2.0));
+    }
+
+    #[test]
+    fn dollar_bars() {
+        let data = aggregate_ticks(ticks(), BarKind::Dollar(40.0)).unwrap();
+
+        // Traded values are 10, 24, 11 | 46 | 9, 30.
+        assert_eq!(data.get_end_tick(), 2);
+        assert_eq!(data.get_series("trades", 0), Some(3.0));
+        assert_eq!(data.get_series("vwap", 0), Some(45.0 / 4.0));
+        assert_eq!(data.get_time(1), Some(Duration::from_secs(60)));
+        assert_eq!(data.get_volume(1), Some(4.0));
+        assert_eq!(data.get_series("vwap", 1), Some(11.5));
+        assert_eq!(data.get_time(2), Some(Duration::from_secs(150)));
+        assert_eq!(data.get_series("vwap", 2), Some(39.0 / 4.0));
+    }
+
+    #[test]
+    fn incremental_into_streaming_provider() {
+        let data = Arc::new(StreamingDataProvider::new(&AGGREGATED_BAR_SERIES));
+        let mut aggregator = TickAggregator::new(BarKind::Tick(2)).unwrap();
+
+        let mut closed: Vec<AggregatedBar> = Vec::new();
+        for tick in ticks().into_iter().take(5) {
+            closed.extend(aggregator.push_into(tick, &data).unwrap());
+        }
+        assert_eq!(closed.len(), 2);
+        assert_eq!(aggregator.current().unwrap().trades, 1);
+
+        assert_eq!(data.get_end_tick(), 1);
+        assert_eq!(data.get_close(1), Some(11.5));
+        assert_eq!(data.get_series("trades", 1), Some(2.0));
+        assert_eq!(*data.get_volume_for_range(0, 1), [Some(3.0), Some(5.0)]);
+
+        let ctx = Context::new(data.clone());
+        assert_eq!(ctx.last_bar_index(), 1);
+    }
+
+    #[test]
+    fn context_follows_streaming_provider() {
+        let data = Arc::new(StreamingDataProvider::new(&AGGREGATED_BAR_SERIES));
+        let mut aggregator = TickAggregator::new(BarKind::Tick(2)).unwrap();
+        let mut ctx = Context::new(data.clone());
+        let mut sma = Sma::new(ctx.clone(), 2);
+
+        assert!(data.is_empty());
+        assert_eq!(ctx.bars, 0);
+        assert_eq!(ctx.next(), None);
+
+        let mut bars = Vec::new();
+        let mut values = Vec::new();
+        for tick in ticks() {
+            aggregator.push_into(tick, &data).unwrap();
+            while let Some(bar) = ctx.next() {
+                bars.push(bar);
+                values.push(sma.next(ctx.bar.close()));
+            }
+        }
+
+        // Closes of bars of 2 ticks are 12, 11.5 and 10.
+        assert_eq!(bars, vec![0, 1, 2]);
+        assert_eq!(values, vec![None, Some(11.75), Some(10.75)]);
+        assert_eq!(ctx.last_bar_index(), 2);
+    }
+
+    #[test]
+    fn push_into_other_series() {
+        let mut aggregator = TickAggregator::new(BarKind::Tick(1)).unwrap();
+
+        for series in [&["funding_rate"][..], &["vwap", "trades"]] {
+            let data = StreamingDataProvider::new(series);
+            let error = aggregator
+                .push_into(tick(0, 10.0, 1.0), &data)
+                .err()
+                .unwrap();
+            assert!(matches!(error, PaceError::InvalidData(_)));
+            assert!(data.is_empty());
+        }
+        assert!(aggregator.current().is_none());
+    }
+
+    #[test]
+    fn streaming_provider() {
+        let data = StreamingDataProvider::new(&["funding_rate"]);
+        assert!(data.is_empty());
+        assert_eq!(data.get_close(0), None);
+
+        let bar = StreamingBar {
+            open: Some(1.0),
+            high: Some(2.0),
+            low: Some(0.5),
+            close: Some(1.5),
+            volume: None,
+            time: Some(Duration::from_secs(60)),
+            series: vec![Some(0.01)],
+        };
+        data.push(bar.clone()).unwrap();
+        data.push(StreamingBar {
+            close: Some(1.8),
+            series: vec![None],
+            ..bar.clone()
+        })
+        .unwrap();
+
+        assert_eq!(data.len(), 2);
+        assert_eq!(data.get_end_tick(), 1);
+        assert_eq!(data.get_volume(0), None);
+        assert_eq!(*data.get_close_for_range(0, 1), [Some(1.5), Some(1.8)]);
+        assert_eq!(
+            *data.get_series_for_range("funding_rate", 0, 1),
+            [Some(0.01), None]
+        );
+        assert!(data.get_series_for_range("sentiment", 0, 1).is_empty());
+
+        let error = data
+            .push(StreamingBar {
+                series: Vec::new(),
+                ..bar
+            })
+            .err()
+            .unwrap();
+        assert!(matches!(error, PaceError::InvalidData(_)));
+    }
+
+    #[test]
+    fn invalid_ticks() {
+        assert!(matches!(
+            TickAggregator::new(BarKind::Volume(0.0)).err().unwrap(),
+            PaceError::InvalidData(_)
+        ));
+        assert!(matches!(
+            TickAggregator::new(BarKind::Time(Duration::ZERO))
+                .err()
+                .unwrap(),
+            PaceError::InvalidData(_)
+        ));
+
+        let mut aggregator = TickAggregator::new(BarKind::Tick(10)).unwrap();
+        aggregator.push(tick(60, 10.0, 1.0)).unwrap();
+        let error = aggregator.push(tick(30, 10.0, 1.0)).err().unwrap();
+        assert!(matches!(
+            error,
+            PaceError::InvalidValue { index: Some(1), ref column, .. } if column == "time"
+        ));
+        let error = aggregator.push(tick(60, f64::NAN, 1.0)).err().unwrap();
+        assert!(matches!(error, PaceError::InvalidValue { ref column, .. } if column == "price"));
+        let error = aggregator.push(tick(60, 10.0, -1.0)).err().unwrap();
+        assert!(matches!(error, PaceError::InvalidValue { ref column, .. } if column == "size"));
+
+        let error = aggregate_ticks(Vec::new(), BarKind::Tick(10))
+            .err()
+            .unwrap();
+        assert!(matches!(error, PaceError::InvalidData(_)));
+    }
+}
@@ -0,0 +1,265 @@
+use std::time::Duration;
+
+use super::{
+    data_provider::DataProvider,
+    error::{PaceError, PaceResult},
+    in_memory_data_provider::InMemoryDataProvider,
+    streaming_data_provider::{StreamingBar, StreamingDataProvider},
+};
+
+/// Auxiliary series of aggregated bars.
+pub const AGGREGATED_BAR_SERIES: [&str; 2] = ["trades", "vwap"];
+
+/// Single trade print.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Tick {
+    /// Time since epoch.
+    pub time: Duration,
+    pub price: f64,
+    pub size: f64,
+}
+
+/// When a bar is closed.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BarKind {
+    /// Bars of a fixed interval, aligned to epoch. Intervals without ticks produce no bar.
+    Time(Duration),
+    /// Bars of a number of ticks.
+    Tick(usize),
+    /// Bars closed once traded size reaches the threshold.
+    Volume(f64),
+    /// Bars closed once traded value (price * size) reaches the threshold.
+    Dollar(f64),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct AggregatedBar {
+    /// Start of the interval for time bars, time of the first tick otherwise.
+    pub time: Duration,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// Total size.
+    pub volume: f64,
+    /// Total price * size.
+    pub dollar_volume: f64,
+    /// Number of ticks.
+    pub trades: usize,
+}
+
+impl AggregatedBar {
+    fn new(time: Duration, tick: &Tick) -> Self {
+        return Self {
+            time,
+            open: tick.price,
+            high: tick.price,
+            low: tick.price,
+            close: tick.price,
+            volume: tick.size,
+            dollar_volume: tick.price * tick.size,
+            trades: 1,
+        };
+    }
+
+    fn add(&mut self, tick: &Tick) {
+        self.high = self.high.max(tick.price);
+        self.low = self.low.min(tick.price);
+        self.close = tick.price;
+        self.volume += tick.size;
+        self.dollar_volume += tick.price * tick.size;
+        self.trades += 1;
+    }
+
+    /// Volume-weighted average price. Same as close if volume is zero.
+    pub fn vwap(&self) -> f64 {
+        if self.volume == 0.0 {
+            return self.close;
+        }
+        return self.dollar_volume / self.volume;
+    }
+
+    /// Values of `AGGREGATED_BAR_SERIES`.
+    pub fn series(&self) -> [f64; 2] {
+        return [self.trades as f64, self.vwap()];
+    }
+
+    pub fn to_streaming_bar(&self) -> StreamingBar {
+        return StreamingBar {
+            open: Some(self.open),
+            high: Some(self.high),
+            low: Some(self.low),
+            close: Some(self.close),
+            volume: Some(self.volume),
+            time: Some(self.time),
+            series: self.series().into_iter().map(Some).collect(),
+        };
+    }
+}
+
+/// Aggregates ticks into bars. Ticks must be sorted by time. A tick is never split between bars, so threshold bars may exceed the threshold.
+pub struct TickAggregator {
+    pub kind: BarKind,
+    bar: Option<AggregatedBar>,
+    last_time: Option<Duration>,
+    ticks: usize,
+}
+
+impl TickAggregator {
+    /// Returns an error if the interval or threshold is not positive.
+    pub fn new(kind: BarKind) -> PaceResult<Self> {
+        let is_valid = match kind {
+            BarKind::Time(interval) => !interval.is_zero(),
+            BarKind::Tick(count) => count > 0,
+            BarKind::Volume(threshold) | BarKind::Dollar(threshold) => {
+                threshold.is_finite() && threshold > 0.0
+            }
+        };
+        if !is_valid {
+            return Err(PaceError::InvalidData(format!(
+                "Invalid bar kind {:?}, expected a positive interval or threshold",
+                kind
+            )));
+        }
+        return Ok(Self {
+            kind,
+            bar: None,
+            last_time: None,
+            ticks: 0,
+        });
+    }
+
+    /// Bar that is not closed yet.
+    pub fn current(&self) -> Option<&AggregatedBar> {
+        return self.bar.as_ref();
+    }
+
+    fn validate(&self, tick: &Tick) -> PaceResult<()> {
+        let invalid = |column: &str, value: String, expected: &str| PaceError::InvalidValue {
+            column: column.to_string(),
+            index: Some(self.ticks),
+            value: Some(value),
+            expected: expected.to_string(),
+        };
+        if !tick.price.is_finite() {
+            return Err(invalid("price", tick.price.to_string(), "finite price"));
+        }
+        if !tick.size.is_finite() || tick.size < 0.0 {
+            return Err(invalid("size", tick.size.to_string(), "non-negative size"));
+        }
+        if self
+            .last_time
+            .is_some_and(|last_time| tick.time < last_time)
+        {
+            return Err(invalid(
+                "time",
+                format!("{:?}", tick.time),
+                "time not earlier than the previous tick",
+            ));
+        }
+        return Ok(());
+    }
+
+    /// Adds a tick. Returns the bar closed by it, if any.
+    ///
+    /// Time bars are closed by the first tick of the next interval, other bars by the tick reaching the threshold.
+    pub fn push(&mut self, tick: Tick) -> PaceResult<Option<AggregatedBar>> {
+        self.validate(&tick)?;
+        self.ticks += 1;
+        self.last_time = Some(tick.time);
+
+        if let BarKind::Time(interval) = self.kind {
+            let start = Duration::from_nanos(
+                (tick.time.as_nanos() / interval.as_nanos() * interval.as_nanos()) as u64,
+            );
+            return match &mut self.bar {
+                Some(bar) if bar.time == start => {
+                    bar.add(&tick);
+                    Ok(None)
+                }
+                _ => Ok(self.bar.replace(AggregatedBar::new(start, &tick))),
+            };
+        }
+
+        let bar = match &mut self.bar {
+            Some(bar) => {
+                bar.add(&tick);
+                bar
+            }
+            None => self.bar.insert(AggregatedBar::new(tick.time, &tick)),
+        };
+        let is_closed = match self.kind {
+            BarKind::Tick(count) => bar.trades >= count,
+            BarKind::Volume(threshold) => bar.volume >= threshold,
+            BarKind::Dollar(threshold) => bar.dollar_volume >= threshold,
+            BarKind::Time(_) => false,
+        };
+        if is_closed {
+            return Ok(self.bar.take());
+        }
+        return Ok(None);
+    }
+
+    /// Closes and returns the current bar.
+    pub fn flush(&mut self) -> Option<AggregatedBar> {
+        return self.bar.take();
+    }
+
+    /// Adds a tick and appends the bar closed by it to `data`.
+    ///
+    /// Returns an error without adding the tick if series of `data` are not `AGGREGATED_BAR_SERIES`.
+    pub fn push_into(
+        &mut self,
+        tick: Tick,
+        data: &StreamingDataProvider,
+    ) -> PaceResult<Option<AggregatedBar>> {
+        if data.get_series_names() != AGGREGATED_BAR_SERIES {
+            return Err(PaceError::InvalidData(format!(
+                "Expected series {:?} of aggregated bars, got {:?}",
+                AGGREGATED_BAR_SERIES,
+                data.get_series_names()
+            )));
+        }
+
+        let bar = self.push(tick)?;
+        if let Some(bar) = &bar {
+            data.push(bar.to_streaming_bar())?;
+        }
+        return Ok(bar);
+    }
+}
+
+/// Aggregates ticks sorted by time into bars, including the last bar that is not complete.
+///
+/// Bars have `AGGREGATED_BAR_SERIES`. Returns an error if there are no ticks.
+pub fn aggregate_ticks(
+    ticks: impl IntoIterator<Item = Tick>,
+    kind: BarKind,
+) -> PaceResult<InMemoryDataProvider> {
+    let mut aggregator = TickAggregator::new(kind)?;
+    let mut bars: Vec<AggregatedBar> = Vec::new();
+    for tick in ticks {
+        bars.extend(aggregator.push(tick)?);
+    }
+    bars.extend(aggregator.flush());
+
+    if bars.is_empty() {
+        return Err(PaceError::InvalidData("No ticks to aggregate".to_string()));
+    }
+
+    let mut data = InMemoryDataProvider::new(
+        bars.iter().map(|bar| Some(bar.open)).collect(),
+        bars.iter().map(|bar| Some(bar.high)).collect(),
+        bars.iter().map(|bar| Some(bar.low)).collect(),
+        bars.iter().map(|bar| Some(bar.close)).collect(),
+        bars.iter().map(|bar| Some(bar.volume)).collect(),
+        bars.iter().map(|bar| Some(bar.time)).collect(),
+    );
+    for (index, name) in AGGREGATED_BAR_SERIES.iter().enumerate() {
+        data.add_series(
+            name,
+            bars.iter().map(|bar| Some(bar.series()[index])).collect(),
+        )?;
+    }
+    return Ok(data);
+}
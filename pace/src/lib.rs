@@ -5,6 +5,8 @@ pub mod core;
 pub mod pinescript;
 pub mod polars;
 pub mod statistics;
+#[cfg(feature = "store")]
+pub mod store;
 pub mod strategy;
 pub mod ta;
 pub mod testing;
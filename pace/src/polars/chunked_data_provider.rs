@@ -0,0 +1,484 @@
+use std::{
+    borrow::Cow,
+    io::Cursor,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use memmap2::Mmap;
+use polars::{
+    export::arrow::{
+        datatypes::{Field, Schema},
+        io::{ipc, parquet},
+    },
+    prelude::{DataFrame, PolarsError},
+};
+
+use crate::{
+    core::{
+        data_provider::DataProvider,
+        error::{PaceError, PaceResult},
+        in_memory_data_provider::InMemoryDataProvider,
+    },
+    utils::fs::get_filename_extension,
+};
+
+use super::{
+    in_memory_data_provider::df_column,
+    io::{io_error, open_file, unsupported_file_type},
+    schema::{detect_time_unit, DataFrameSchema},
+};
+
+/// File split into chunks that can be read independently, e.g. Parquet row groups or Arrow IPC record batches.
+trait ChunkSource: Send + Sync {
+    fn chunk_count(&self) -> usize;
+    /// Reads chunk `index` with `columns` only.
+    fn read_chunk(&self, index: usize, columns: &[String]) -> PaceResult<DataFrame>;
+    /// Number of rows of every chunk.
+    fn chunk_lengths(&self) -> PaceResult<Vec<usize>>;
+}
+
+fn arrow_error(error: polars::export::arrow::error::Error) -> PaceError {
+    return PaceError::Polars(PolarsError::from(error));
+}
+
+/// Fields of `schema` named in `columns`, in order of the file. All fields if `columns` is empty.
+fn project(schema: &Schema, columns: &[String]) -> (Vec<usize>, Vec<Field>) {
+    return schema
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| columns.is_empty() || columns.contains(&field.name))
+        .map(|(i, field)| (i, field.clone()))
+        .unzip();
+}
+
+struct ParquetChunks {
+    path: PathBuf,
+    metadata: parquet::read::FileMetaData,
+    schema: Schema,
+}
+
+impl ParquetChunks {
+    fn open(path: &Path) -> PaceResult<Self> {
+        let mut file = open_file(path)?;
+        let metadata = parquet::read::read_metadata(&mut file).map_err(arrow_error)?;
+        let schema = parquet::read::infer_schema(&metadata).map_err(arrow_error)?;
+        return Ok(Self {
+            path: path.to_path_buf(),
+            metadata,
+            schema,
+        });
+    }
+}
+
+impl ChunkSource for ParquetChunks {
+    fn chunk_count(&self) -> usize {
+        return self.metadata.row_groups.len();
+    }
+
+    fn chunk_lengths(&self) -> PaceResult<Vec<usize>> {
+        return Ok(self
+            .metadata
+            .row_groups
+            .iter()
+            .map(|row_group| row_group.num_rows())
+            .collect());
+    }
+
+    fn read_chunk(&self, index: usize, columns: &[String]) -> PaceResult<DataFrame> {
+        let (_, fields) = project(&self.schema, columns);
+        let reader = parquet::read::FileReader::new(
+            open_file(&self.path)?,
+            vec![self.metadata.row_groups[index].clone()],
+            Schema::from(fields.clone()),
+            None,
+            None,
+            None,
+        );
+
+        let mut df: Option<DataFrame> = None;
+        for chunk in reader {
+            let chunk = DataFrame::try_from((chunk.map_err(arrow_error)?, fields.as_slice()))?;
+            match &mut df {
+                Some(df) => {
+                    df.vstack_mut(&chunk)?;
+                }
+                None => df = Some(chunk),
+            }
+        }
+        return df.ok_or_else(|| {
+            PaceError::InvalidData(format!(
+                "Row group {} of \"{}\" is empty",
+                index,
+                self.path.display()
+            ))
+        });
+    }
+}
+
+struct IpcChunks {
+    mmap: Mmap,
+    metadata: ipc::read::FileMetadata,
+    dictionaries: ipc::read::Dictionaries,
+}
+
+impl IpcChunks {
+    fn open(path: &Path) -> PaceResult<Self> {
+        let file = open_file(path)?;
+        // SAFETY: The file must not be modified while the provider exists, same as for any memory-mapped reader.
+        let mmap = unsafe { Mmap::map(&file) }.map_err(|source| io_error(path, source))?;
+        let mut reader = Cursor::new(&mmap[..]);
+        let metadata = ipc::read::read_file_metadata(&mut reader).map_err(arrow_error)?;
+        let dictionaries =
+            ipc::read::read_file_dictionaries(&mut reader, &metadata, &mut Vec::new())
+                .map_err(arrow_error)?;
+        return Ok(Self {
+            mmap,
+            metadata,
+            dictionaries,
+        });
+    }
+
+    fn read_batch(&self, index: usize, projection: &[usize]) -> PaceResult<DataFrame> {
+        let chunk = ipc::read::read_batch(
+            &mut Cursor::new(&self.mmap[..]),
+            &self.dictionaries,
+            &self.metadata,
+            Some(projection),
+            None,
+            index,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .map_err(arrow_error)?;
+        let fields: Vec<Field> = projection
+            .iter()
+            .map(|i| self.metadata.schema.fields[*i].clone())
+            .collect();
+        return Ok(DataFrame::try_from((chunk, fields.as_slice()))?);
+    }
+}
+
+impl ChunkSource for IpcChunks {
+    fn chunk_count(&self) -> usize {
+        return self.metadata.blocks.len();
+    }
+
+    /// Record batch lengths are not stored in the footer, so the first column of every batch is read.
+    fn chunk_lengths(&self) -> PaceResult<Vec<usize>> {
+        return (0..self.chunk_count())
+            .map(|index| Ok(self.read_batch(index, &[0])?.height()))
+            .collect();
+    }
+
+    fn read_chunk(&self, index: usize, columns: &[String]) -> PaceResult<DataFrame> {
+        let (projection, _) = project(&self.metadata.schema, columns);
+        return self.read_batch(index, &projection);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ChunkedDataProviderConfig {
+    /// Maximum number of chunks kept in memory. Least recently used chunks are dropped first.
+    pub max_chunks: usize,
+    /// If `None`, columns are detected from the first chunk with `DataFrameSchema::detect`.
+    pub schema: Option<DataFrameSchema>,
+}
+
+impl Default for ChunkedDataProviderConfig {
+    fn default() -> Self {
+        return Self {
+            max_chunks: 4,
+            schema: None,
+        };
+    }
+}
+
+/// Implements `DataProvider`. Reads Parquet row groups or record batches of a memory-mapped Arrow IPC file on demand,
+/// keeping at most `max_chunks` of them in memory.
+///
+/// Bars should be accessed mostly in order, like in a backtest. Ranges are returned as copies.
+///
+/// Panics if a chunk can't be read after the provider was opened, e.g. when the file was removed.
+pub struct ChunkedDataProvider {
+    path: PathBuf,
+    source: Box<dyn ChunkSource>,
+    schema: DataFrameSchema,
+    /// Columns read from every chunk.
+    columns: Vec<String>,
+    /// Index of every non-empty chunk in the file.
+    chunks: Vec<usize>,
+    /// First tick of every non-empty chunk.
+    offsets: Vec<usize>,
+    end_tick: usize,
+    max_chunks: usize,
+    /// Loaded chunks, least recently used first.
+    cache: Mutex<Vec<(usize, Arc<InMemoryDataProvider>)>>,
+}
+
+impl ChunkedDataProvider {
+    /// Opens a Parquet or Arrow IPC file, depending on the extension.
+    pub fn open(path: &Path, config: ChunkedDataProviderConfig) -> PaceResult<Self> {
+        return match get_filename_extension(path) {
+            Some("parquet") => Self::open_parquet(path, config),
+            Some("arrow") | Some("ipc") | Some("feather") => Self::open_ipc(path, config),
+            _ => Err(unsupported_file_type(path)),
+        };
+    }
+
+    /// Opens a Parquet file. Every row group is a chunk.
+    pub fn open_parquet(path: &Path, config: ChunkedDataProviderConfig) -> PaceResult<Self> {
+        return Self::from_source(path, Box::new(ParquetChunks::open(path)?), config);
+    }
+
+    /// Memory-maps an uncompressed or compressed Arrow IPC file. Every record batch is a chunk.
+    pub fn open_ipc(path: &Path, config: ChunkedDataProviderConfig) -> PaceResult<Self> {
+        return Self::from_source(path, Box::new(IpcChunks::open(path)?), config);
+    }
+
+    fn from_source(
+        path: &Path,
+        source: Box<dyn ChunkSource>,
+        config: ChunkedDataProviderConfig,
+    ) -> PaceResult<Self> {
+        let lengths = source.chunk_lengths()?;
+        let chunks: Vec<usize> = (0..lengths.len()).filter(|i| lengths[*i] > 0).collect();
+        if chunks.is_empty() {
+            return Err(PaceError::InvalidData(format!(
+                "\"{}\" has no rows",
+                path.display()
+            )));
+        }
+
+        let schema = match config.schema {
+            Some(schema)
+                if schema.series.is_some()
+                    && (schema.time.is_none() || schema.time_unit.is_some()) =>
+            {
+                schema
+            }
+            schema => {
+                let df = source.read_chunk(chunks[0], &[])?;
+                let mut schema = match schema {
+                    Some(schema) => schema,
+                    None => DataFrameSchema::detect(&df)?,
+                };
+                // Series and time unit are fixed, so every chunk is read the same way.
+                if schema.series.is_none() {
+                    schema.series = Some(
+                        schema
+                            .series_columns(&df)
+                            .iter()
+                            .map(|name| name.to_string())
+                            .collect(),
+                    );
+                }
+                if let (Some(time), None) = (&schema.time, schema.time_unit) {
+                    schema.time_unit = detect_time_unit(df_column(&df, time)?)?;
+                }
+                schema
+            }
+        };
+        let mut columns: Vec<String> = schema
+            .columns()
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        columns.extend(schema.series.iter().flatten().cloned());
+
+        let mut offsets: Vec<usize> = Vec::with_capacity(chunks.len());
+        let mut len = 0;
+        for chunk in &chunks {
+            offsets.push(len);
+            len += lengths[*chunk];
+        }
+
+        let provider = Self {
+            path: path.to_path_buf(),
+            source,
+            schema,
+            columns,
+            chunks,
+            offsets,
+            end_tick: len - 1,
+            max_chunks: config.max_chunks.max(1),
+            cache: Mutex::new(Vec::new()),
+        };
+        // Validates columns.
+        provider.load_chunk(0)?;
+
+        return Ok(provider);
+    }
+
+    pub fn chunk_count(&self) -> usize {
+        return self.chunks.len();
+    }
+
+    /// Chunks currently in memory, least recently used first.
+    pub fn loaded_chunks(&self) -> Vec<usize> {
+        return self
+            .cache
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(chunk, _)| *chunk)
+            .collect();
+    }
+
+    fn load_chunk(&self, chunk: usize) -> PaceResult<Arc<InMemoryDataProvider>> {
+        let mut cache = self.cache.lock().unwrap();
+        if let Some(position) = cache.iter().position(|(loaded, _)| *loaded == chunk) {
+            let entry = cache.remove(position);
+            let data = entry.1.clone();
+            cache.push(entry);
+            return Ok(data);
+        }
+
+        let df = self.source.read_chunk(self.chunks[chunk], &self.columns)?;
+        let data = Arc::new(InMemoryDataProvider::from_df_with_schema(
+            &df,
+            &self.schema,
+        )?);
+        cache.push((chunk, data.clone()));
+        if cache.len() > self.max_chunks {
+            cache.remove(0);
+        }
+        return Ok(data);
+    }
+
+    /// Returns the chunk containing `index` and its first tick.
+    fn chunk(&self, index: usize) -> (Arc<InMemoryDataProvider>, usize) {
+        assert!(
+            index <= self.end_tick,
+            "Index {} is out of range of {} bars",
+            index,
+            self.end_tick + 1
+        );
+        let chunk = self.offsets.partition_point(|offset| *offset <= index) - 1;
+        let data = self.load_chunk(chunk).unwrap_or_else(|error| {
+            panic!(
+                "Failed to read chunk {} of \"{}\": {}",
+                chunk,
+                self.path.display(),
+                error
+            )
+        });
+        return (data, self.offsets[chunk]);
+    }
+
+    fn get<T>(&self, index: usize, get: impl Fn(&InMemoryDataProvider, usize) -> T) -> T {
+        let (data, offset) = self.chunk(index);
+        return get(&data, index - offset);
+    }
+
+    fn get_range(
+        &self,
+        start_index: usize,
+        end_index: usize,
+        get: impl for<'a> Fn(&'a InMemoryDataProvider, usize, usize) -> Cow<'a, [Option<f64>]>,
+    ) -> Cow<'_, [Option<f64>]> {
+        let mut values: Vec<Option<f64>> = Vec::with_capacity(end_index + 1 - start_index);
+        let mut index = start_index;
+        while index <= end_index {
+            let (data, offset) = self.chunk(index);
+            let chunk_end = end_index.min(offset + data.get_end_tick());
+            values.extend_from_slice(&get(&data, index - offset, chunk_end - offset));
+            index = chunk_end + 1;
+        }
+        return Cow::Owned(values);
+    }
+}
+
+impl DataProvider for ChunkedDataProvider {
+    fn get_start_tick(&self) -> usize {
+        return 0;
+    }
+
+    fn get_end_tick(&self) -> usize {
+        return self.end_tick;
+    }
+
+    fn get_open(&self, index: usize) -> Option<f64> {
+        return self.get(index, |data, i| data.get_open(i));
+    }
+
+    fn get_high(&self, index: usize) -> Option<f64> {
+        return self.get(index, |data, i| data.get_high(i));
+    }
+
+    fn get_low(&self, index: usize) -> Option<f64> {
+        return self.get(index, |data, i| data.get_low(i));
+    }
+
+    fn get_close(&self, index: usize) -> Option<f64> {
+        return self.get(index, |data, i| data.get_close(i));
+    }
+
+    fn get_volume(&self, index: usize) -> Option<f64> {
+        return self.get(index, |data, i| data.get_volume(i));
+    }
+
+    fn get_time(&self, index: usize) -> Option<Duration> {
+        return self.get(index, |data, i| data.get_time(i));
+    }
+
+    fn get_open_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return self.get_range(start_index, end_index, |data, start, end| {
+            data.get_open_for_range(start, end)
+        });
+    }
+
+    fn get_high_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return self.get_range(start_index, end_index, |data, start, end| {
+            data.get_high_for_range(start, end)
+        });
+    }
+
+    fn get_low_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return self.get_range(start_index, end_index, |data, start, end| {
+            data.get_low_for_range(start, end)
+        });
+    }
+
+    fn get_close_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return self.get_range(start_index, end_index, |data, start, end| {
+            data.get_close_for_range(start, end)
+        });
+    }
+
+    fn get_volume_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return self.get_range(start_index, end_index, |data, start, end| {
+            data.get_volume_for_range(start, end)
+        });
+    }
+
+    fn get_series_names(&self) -> Vec<&str> {
+        return self
+            .schema
+            .series
+            .iter()
+            .flatten()
+            .map(|name| name.as_str())
+            .collect();
+    }
+
+    fn get_series(&self, name: &str, index: usize) -> Option<f64> {
+        return self.get(index, |data, i| data.get_series(name, i));
+    }
+
+    fn get_series_for_range(
+        &self,
+        name: &str,
+        start_index: usize,
+        end_index: usize,
+    ) -> Cow<'_, [Option<f64>]> {
+        if !self.get_series_names().contains(&name) {
+            return Cow::Borrowed(&[]);
+        }
+        return self.get_range(start_index, end_index, |data, start, end| {
+            data.get_series_for_range(name, start, end)
+        });
+    }
+}
@@ -1,35 +1,38 @@
-use std::{ffi::OsStr, path::Path, time::Duration};
+use polars::prelude::DataFrame;
 
-use polars::{
-    prelude::{
-        CsvReader, CsvWriter, DataFrame, DataType, IsFloat, ParquetReader, ParquetWriter,
-        SerReader, SerWriter, TimeUnit,
-    },
-    series::Series,
-};
+use crate::core::error::PaceResult;
+
+use super::{in_memory_data_provider::df_column, series::SeriesCastUtils};
 
-use crate::{
-    strategy::trade::{trade_direction_from_f64, TradeDirection},
-    utils::fs::{ensure_dir, get_filename_extension},
+pub use super::io::{
+    read_df, read_df_csv, read_df_ipc, read_df_ipc_stream, read_df_ndjson, read_df_parquet,
+    save_df, save_df_csv, save_df_csv_gzip, save_df_ipc, save_df_ipc_stream, save_df_ndjson,
+    save_df_parquet,
 };
 
-use super::series::SeriesCastUtils;
+/// Values of four columns at the same row.
+pub type FourColumnValues = (Option<f64>, Option<f64>, Option<f64>, Option<f64>);
 
+/// Zips float columns into tuples. Returns an error if a column is missing or is not numeric.
 pub trait DataFrameUtils {
-    fn merge_two_columns(&self, col1: &str, col2: &str) -> Vec<Option<(Option<f64>, Option<f64>)>>;
+    fn merge_two_columns(
+        &self,
+        col1: &str,
+        col2: &str,
+    ) -> PaceResult<Vec<Option<(Option<f64>, Option<f64>)>>>;
     fn merge_three_columns(
         &self,
         col1: &str,
         col2: &str,
         col3: &str,
-    ) -> Vec<Option<(Option<f64>, Option<f64>, Option<f64>)>>;
+    ) -> PaceResult<Vec<Option<(Option<f64>, Option<f64>, Option<f64>)>>>;
     fn merge_four_columns(
         &self,
         col1: &str,
         col2: &str,
         col3: &str,
         col4: &str,
-    ) -> Vec<Option<(Option<f64>, Option<f64>, Option<f64>, Option<f64>)>>;
+    ) -> PaceResult<Vec<Option<FourColumnValues>>>;
 }
 
 impl DataFrameUtils for DataFrame {
@@ -37,15 +40,15 @@ impl DataFrameUtils for DataFrame {
         &self,
         first: &str,
         second: &str,
-    ) -> Vec<Option<(Option<f64>, Option<f64>)>> {
-        let first_values = self.column(first).unwrap().to_f64();
-        let second_values = self.column(second).unwrap().to_f64();
+    ) -> PaceResult<Vec<Option<(Option<f64>, Option<f64>)>>> {
+        let first_values = df_column(self, first)?.to_f64()?;
+        let second_values = df_column(self, second)?.to_f64()?;
         let arr: Vec<Option<(Option<f64>, Option<f64>)>> = first_values
             .iter()
             .zip(second_values.iter())
             .map(|(first, second)| Some((*first, *second)))
             .collect();
-        return arr;
+        return Ok(arr);
     }
 
     fn merge_three_columns(
@@ -53,17 +56,17 @@ impl DataFrameUtils for DataFrame {
         first: &str,
         second: &str,
         third: &str,
-    ) -> Vec<Option<(Option<f64>, Option<f64>, Option<f64>)>> {
-        let first_values = self.column(first).unwrap().to_f64();
-        let second_values = self.column(second).unwrap().to_f64();
-        let third_values = self.column(third).unwrap().to_f64();
+    ) -> PaceResult<Vec<Option<(Option<f64>, Option<f64>, Option<f64>)>>> {
+        let first_values = df_column(self, first)?.to_f64()?;
+        let second_values = df_column(self, second)?.to_f64()?;
+        let third_values = df_column(self, third)?.to_f64()?;
         let arr: Vec<Option<(Option<f64>, Option<f64>, Option<f64>)>> = first_values
             .iter()
             .zip(second_values.iter())
             .zip(third_values.iter())
             .map(|((first, second), third)| Some((*first, *second, *third)))
             .collect();
-        return arr;
+        return Ok(arr);
     }
 
     fn merge_four_columns(
@@ -72,63 +75,18 @@ impl DataFrameUtils for DataFrame {
         second: &str,
         third: &str,
         fourth: &str,
-    ) -> Vec<Option<(Option<f64>, Option<f64>, Option<f64>, Option<f64>)>> {
-        let first_values = self.column(first).unwrap().to_f64();
-        let second_values = self.column(second).unwrap().to_f64();
-        let third_values = self.column(third).unwrap().to_f64();
-        let fourth_values = self.column(fourth).unwrap().to_f64();
-        let arr: Vec<Option<(Option<f64>, Option<f64>, Option<f64>, Option<f64>)>> = first_values
+    ) -> PaceResult<Vec<Option<FourColumnValues>>> {
+        let first_values = df_column(self, first)?.to_f64()?;
+        let second_values = df_column(self, second)?.to_f64()?;
+        let third_values = df_column(self, third)?.to_f64()?;
+        let fourth_values = df_column(self, fourth)?.to_f64()?;
+        let arr: Vec<Option<FourColumnValues>> = first_values
             .iter()
             .zip(second_values.iter())
             .zip(third_values.iter())
             .zip(fourth_values.iter())
             .map(|(((first, second), third), fourth)| Some((*first, *second, *third, *fourth)))
             .collect();
-        return arr;
-    }
-}
-
-pub fn read_df_csv(path: &Path) -> DataFrame {
-    let mut file = std::fs::File::open(path).unwrap();
-    let df = CsvReader::new(&mut file).finish().unwrap();
-    return df;
-}
-
-pub fn read_df_parquet(path: &Path) -> DataFrame {
-    let mut file = std::fs::File::open(path).unwrap();
-    let df = ParquetReader::new(&mut file).finish().unwrap();
-    return df;
-}
-
-pub fn read_df(path: &Path) -> DataFrame {
-    let extension = get_filename_extension(path);
-    match extension {
-        Some("parquet") => read_df_parquet(path),
-        Some("csv") => read_df_csv(path),
-        Some(&_) => panic!("Unsupported file type"),
-        None => panic!("Unsupported file type"),
+        return Ok(arr);
     }
 }
-
-pub fn save_df(df: &mut DataFrame, path: &Path) {
-    let extension = get_filename_extension(path);
-
-    match extension {
-        Some("parquet") => save_df_parquet(df, path),
-        Some("csv") => save_df_csv(df, path),
-        Some(&_) => panic!("Unsupported file type"),
-        None => panic!("Unsupported file type"),
-    };
-}
-
-pub fn save_df_csv(df: &mut DataFrame, path: &Path) {
-    ensure_dir(path.parent().unwrap());
-    let mut file = std::fs::File::create(path).unwrap();
-    CsvWriter::new(&mut file).finish(df).unwrap();
-}
-
-pub fn save_df_parquet(df: &mut DataFrame, path: &Path) {
-    ensure_dir(path.parent().unwrap());
-    let mut file = std::fs::File::create(path).unwrap();
-    ParquetWriter::new(&mut file).finish(df).unwrap();
-}
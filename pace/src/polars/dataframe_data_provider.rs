@@ -0,0 +1,251 @@
+use std::{borrow::Cow, collections::BTreeMap, time::Duration};
+
+use polars::{
+    export::arrow::array::{Array, PrimitiveArray},
+    prelude::{DataFrame, DataType},
+    series::Series,
+};
+
+use crate::core::{
+    data_provider::DataProvider,
+    error::{PaceError, PaceResult},
+};
+
+use super::{
+    in_memory_data_provider::df_column,
+    schema::{series_to_time, DataFrameSchema, TimestampUnit},
+    series::cast,
+};
+
+/// Float64 Arrow array sharing buffers with the data frame. Other types are cast once, and columns with several chunks are merged.
+fn float_array(series: &Series) -> PaceResult<PrimitiveArray<f64>> {
+    let series = match series.dtype() {
+        DataType::Float64 => series.clone(),
+        _ => cast(series, &DataType::Float64)?,
+    };
+    let values = series.f64()?.rechunk();
+    return Ok(values
+        .downcast_iter()
+        .next()
+        .cloned()
+        .unwrap_or_else(|| PrimitiveArray::new_empty(values.dtype().to_arrow())));
+}
+
+/// Null and NaN values are `None`, same as in `SeriesCastUtils.to_f64`.
+fn float_value(array: &PrimitiveArray<f64>, index: usize) -> Option<f64> {
+    if !array.is_valid(index) {
+        return None;
+    }
+    let value = array.value(index);
+    return if value.is_nan() { None } else { Some(value) };
+}
+
+fn float_range(
+    array: &PrimitiveArray<f64>,
+    start_index: usize,
+    end_index: usize,
+) -> Vec<Option<f64>> {
+    return (start_index..=end_index)
+        .map(|index| float_value(array, index))
+        .collect();
+}
+
+enum TimeColumn {
+    Missing,
+    /// Numeric timestamps, converted on access.
+    Numeric(PrimitiveArray<f64>, TimestampUnit),
+    /// Polars `Datetime`, sharing the Int64 buffer with the data frame, converted on access.
+    Datetime(PrimitiveArray<i64>, TimestampUnit),
+    /// Dates and datetime strings, parsed once.
+    Parsed(Vec<Option<Duration>>),
+}
+
+/// Implements `DataProvider`. Reads values directly from Float64 Arrow buffers of a polars `DataFrame`, with validity bitmaps for `None`,
+/// without copying them into `Vec<Option<f64>>` like `InMemoryDataProvider::from_df`.
+///
+/// Columns of other types are cast once. Ranges are returned as copies.
+pub struct DataFrameDataProvider {
+    open: PrimitiveArray<f64>,
+    high: PrimitiveArray<f64>,
+    low: PrimitiveArray<f64>,
+    close: PrimitiveArray<f64>,
+    volume: Option<PrimitiveArray<f64>>,
+    time: TimeColumn,
+    series: BTreeMap<String, PrimitiveArray<f64>>,
+    end_tick: usize,
+}
+
+impl DataFrameDataProvider {
+    /// Creates a data provider from a data frame with columns detected by `DataFrameSchema::detect`.
+    pub fn from_df(df: &DataFrame) -> PaceResult<Self> {
+        return Self::from_df_with_schema(df, &DataFrameSchema::detect(df)?);
+    }
+
+    /// Creates a data provider from columns named in `schema`. Numeric columns not named in `schema` are used as auxiliary series, unless `schema.series` is set.
+    pub fn from_df_with_schema(df: &DataFrame, schema: &DataFrameSchema) -> PaceResult<Self> {
+        if df.height() == 0 {
+            return Err(PaceError::InvalidData("Data frame has no rows".to_string()));
+        }
+
+        let volume = match &schema.volume {
+            Some(volume) => Some(float_array(df_column(df, volume)?)?),
+            None => None,
+        };
+
+        let time = match &schema.time {
+            Some(name) => {
+                let time = df_column(df, name)?;
+                match time.dtype() {
+                    DataType::Date | DataType::Utf8 => TimeColumn::Parsed(series_to_time(
+                        time,
+                        schema.time_unit,
+                        schema.datetime_format.as_deref(),
+                    )?),
+                    DataType::Datetime(unit, _) => {
+                        let physical = time.to_physical_repr();
+                        let values = physical.i64()?.rechunk();
+                        let values = values.downcast_iter().next().cloned().unwrap();
+                        TimeColumn::Datetime(values, TimestampUnit::from(*unit))
+                    }
+                    _ => {
+                        let values = float_array(time)?;
+                        let unit = schema.time_unit.unwrap_or_else(|| {
+                            (0..values.len())
+                                .find_map(|index| float_value(&values, index))
+                                .map_or(TimestampUnit::Seconds, TimestampUnit::detect)
+                        });
+                        TimeColumn::Numeric(values, unit)
+                    }
+                }
+            }
+            None => TimeColumn::Missing,
+        };
+        let negative_time = match &time {
+            TimeColumn::Numeric(values, _) => (0..values.len())
+                .find(|index| float_value(values, *index).is_some_and(|value| value < 0.0))
+                .map(|index| (index, values.value(index).to_string())),
+            TimeColumn::Datetime(values, _) => (0..values.len())
+                .find(|index| values.is_valid(*index) && values.value(*index) < 0)
+                .map(|index| (index, values.value(index).to_string())),
+            _ => None,
+        };
+        if let Some((index, value)) = negative_time {
+            return Err(PaceError::InvalidValue {
+                column: schema.time.clone().unwrap_or_default(),
+                index: Some(index),
+                value: Some(value),
+                expected: "non-negative timestamp".to_string(),
+            });
+        }
+
+        let mut series: BTreeMap<String, PrimitiveArray<f64>> = BTreeMap::new();
+        for name in schema.series_columns(df) {
+            series.insert(name.to_string(), float_array(df_column(df, name)?)?);
+        }
+
+        return Ok(Self {
+            open: float_array(df_column(df, &schema.open)?)?,
+            high: float_array(df_column(df, &schema.high)?)?,
+            low: float_array(df_column(df, &schema.low)?)?,
+            close: float_array(df_column(df, &schema.close)?)?,
+            volume,
+            time,
+            series,
+            end_tick: df.height() - 1,
+        });
+    }
+}
+
+impl DataProvider for DataFrameDataProvider {
+    fn get_start_tick(&self) -> usize {
+        return 0;
+    }
+
+    fn get_end_tick(&self) -> usize {
+        return self.end_tick;
+    }
+
+    fn get_open(&self, index: usize) -> Option<f64> {
+        return float_value(&self.open, index);
+    }
+
+    fn get_high(&self, index: usize) -> Option<f64> {
+        return float_value(&self.high, index);
+    }
+
+    fn get_low(&self, index: usize) -> Option<f64> {
+        return float_value(&self.low, index);
+    }
+
+    fn get_close(&self, index: usize) -> Option<f64> {
+        return float_value(&self.close, index);
+    }
+
+    fn get_volume(&self, index: usize) -> Option<f64> {
+        return self
+            .volume
+            .as_ref()
+            .and_then(|volume| float_value(volume, index));
+    }
+
+    fn get_time(&self, index: usize) -> Option<Duration> {
+        return match &self.time {
+            TimeColumn::Missing => None,
+            TimeColumn::Numeric(values, unit) => float_value(values, index)
+                .and_then(|value| Duration::try_from_secs_f64(value / unit.per_second()).ok()),
+            TimeColumn::Datetime(values, unit) => {
+                if !values.is_valid(index) {
+                    return None;
+                }
+                unit.duration(values.value(index))
+            }
+            TimeColumn::Parsed(values) => values[index],
+        };
+    }
+
+    fn get_open_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return Cow::Owned(float_range(&self.open, start_index, end_index));
+    }
+
+    fn get_high_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return Cow::Owned(float_range(&self.high, start_index, end_index));
+    }
+
+    fn get_low_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return Cow::Owned(float_range(&self.low, start_index, end_index));
+    }
+
+    fn get_close_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return Cow::Owned(float_range(&self.close, start_index, end_index));
+    }
+
+    fn get_volume_for_range(&self, start_index: usize, end_index: usize) -> Cow<'_, [Option<f64>]> {
+        return match &self.volume {
+            Some(volume) => Cow::Owned(float_range(volume, start_index, end_index)),
+            None => Cow::Owned(vec![None; end_index + 1 - start_index]),
+        };
+    }
+
+    fn get_series_names(&self) -> Vec<&str> {
+        return self.series.keys().map(|name| name.as_str()).collect();
+    }
+
+    fn get_series(&self, name: &str, index: usize) -> Option<f64> {
+        return self
+            .series
+            .get(name)
+            .and_then(|values| float_value(values, index));
+    }
+
+    fn get_series_for_range(
+        &self,
+        name: &str,
+        start_index: usize,
+        end_index: usize,
+    ) -> Cow<'_, [Option<f64>]> {
+        return match self.series.get(name) {
+            Some(values) => Cow::Owned(float_range(values, start_index, end_index)),
+            None => Cow::Borrowed(&[]),
+        };
+    }
+}
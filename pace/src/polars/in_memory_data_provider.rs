@@ -1,20 +1,57 @@
-use std::sync::Arc;
+use polars::prelude::{DataFrame, Series};
 
-use polars::prelude::DataFrame;
+use crate::core::{
+    error::{PaceError, PaceResult},
+    in_memory_data_provider::InMemoryDataProvider,
+};
 
-use crate::core::{data_provider::DataProvider, in_memory_data_provider::InMemoryDataProvider};
+use super::{
+    schema::{series_to_time, DataFrameSchema},
+    series::SeriesCastUtils,
+};
 
-use super::series::SeriesCastUtils;
+/// Returns column `name`, or `PaceError::MissingColumn`.
+pub fn df_column<'a>(df: &'a DataFrame, name: &str) -> PaceResult<&'a Series> {
+    return df
+        .column(name)
+        .map_err(|_| PaceError::MissingColumn(name.to_string()));
+}
 
 impl InMemoryDataProvider {
-    pub fn from_df(df: &DataFrame) -> Self {
-        let open = df.column("open").unwrap().to_f64();
-        let high = df.column("high").unwrap().to_f64();
-        let low = df.column("low").unwrap().to_f64();
-        let close = df.column("close").unwrap().to_f64();
-        let volume = df.column("volume").unwrap().to_f64();
-        let time = df.column("time").unwrap().to_duration();
-
-        return Self::new(open, high, low, close, volume, time);
+    /// Creates a data provider from a data frame with columns detected by `DataFrameSchema::detect`.
+    pub fn from_df(df: &DataFrame) -> PaceResult<Self> {
+        return Self::from_df_with_schema(df, &DataFrameSchema::detect(df)?);
+    }
+
+    /// Creates a data provider from columns named in `schema`. Numeric columns not named in `schema` are loaded as auxiliary series, unless `schema.series` is set.
+    pub fn from_df_with_schema(df: &DataFrame, schema: &DataFrameSchema) -> PaceResult<Self> {
+        if df.height() == 0 {
+            return Err(PaceError::InvalidData("Data frame has no rows".to_string()));
+        }
+
+        let open = df_column(df, &schema.open)?.to_f64()?;
+        let high = df_column(df, &schema.high)?.to_f64()?;
+        let low = df_column(df, &schema.low)?.to_f64()?;
+        let close = df_column(df, &schema.close)?.to_f64()?;
+        let volume = match &schema.volume {
+            Some(volume) => df_column(df, volume)?.to_f64()?,
+            None => vec![None; df.height()],
+        };
+        let time = match &schema.time {
+            Some(time) => series_to_time(
+                df_column(df, time)?,
+                schema.time_unit,
+                schema.datetime_format.as_deref(),
+            )?,
+            None => vec![None; df.height()],
+        };
+
+        let mut data = Self::new(open, high, low, close, volume, time);
+
+        for name in schema.series_columns(df) {
+            data.add_series(name, df_column(df, name)?.to_f64()?)?;
+        }
+
+        return Ok(data);
     }
 }
@@ -1,52 +1,286 @@
-use std::path::Path;
+use std::{
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use polars::{
+    prelude::{
+        CsvReader, CsvWriter, DataFrame, DataType, IpcReader, IpcStreamReader, IpcStreamWriter,
+        IpcWriter, NamedFrom, ParquetReader, ParquetWriter, SerReader, SerWriter,
+    },
+    series::Series,
+};
+use serde_json::{Map, Number, Value};
 
-use polars::prelude::{
-    CsvReader, CsvWriter, DataFrame, ParquetReader, ParquetWriter, SerReader, SerWriter,
+use crate::{
+    core::error::{PaceError, PaceResult},
+    utils::fs::get_filename_extension,
 };
 
-use crate::utils::fs::{ensure_dir, get_filename_extension};
+pub(crate) fn open_file(path: &Path) -> PaceResult<std::fs::File> {
+    return std::fs::File::open(path).map_err(|source| PaceError::Io {
+        path: path.to_path_buf(),
+        source,
+    });
+}
+
+/// Creates a file and all of its parent directories.
+fn create_file(path: &Path) -> PaceResult<std::fs::File> {
+    let to_error = |source| PaceError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent).map_err(to_error)?;
+    }
+    return std::fs::File::create(path).map_err(to_error);
+}
+
+pub(crate) fn io_error(path: &Path, source: std::io::Error) -> PaceError {
+    return PaceError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+}
+
+pub(crate) fn unsupported_file_type(path: &Path) -> PaceError {
+    return PaceError::UnsupportedFileType {
+        path: path.to_path_buf(),
+        extension: get_filename_extension(path).map(|extension| extension.to_string()),
+    };
+}
+
+/// Reads a CSV file. Gzip and zlib compressed files are decompressed.
+pub fn read_df_csv(path: &Path) -> PaceResult<DataFrame> {
+    let mut file = open_file(path)?;
+    let df = CsvReader::new(&mut file).finish()?;
+    return Ok(df);
+}
+
+pub fn read_df_parquet(path: &Path) -> PaceResult<DataFrame> {
+    let mut file = open_file(path)?;
+    let df = ParquetReader::new(&mut file).finish()?;
+    return Ok(df);
+}
+
+/// Returns `true` for a gzip compressed CSV file, e.g. `data.csv.gz`.
+pub fn is_csv_gzip(path: &Path) -> bool {
+    if !matches!(get_filename_extension(path), Some("gz") | Some("gzip")) {
+        return false;
+    }
+    return path
+        .file_stem()
+        .is_some_and(|stem| get_filename_extension(Path::new(stem)) == Some("csv"));
+}
+
+/// Reads an Arrow IPC file, also known as Feather v2. LZ4 and ZSTD compressed files are supported.
+pub fn read_df_ipc(path: &Path) -> PaceResult<DataFrame> {
+    let file = open_file(path)?;
+    let df = IpcReader::new(file).finish()?;
+    return Ok(df);
+}
+
+/// Reads an Arrow IPC stream.
+pub fn read_df_ipc_stream(path: &Path) -> PaceResult<DataFrame> {
+    let file = open_file(path)?;
+    let df = IpcStreamReader::new(file).finish()?;
+    return Ok(df);
+}
+
+/// Reads newline-delimited JSON with one object per line.
+///
+/// Columns are ordered by first appearance. Missing keys and `null` become null.
+/// A column is `Int64` if all values are integers, `Float64` if all are numbers, `Boolean` if all are booleans, and `Utf8` otherwise.
+pub fn read_df_ndjson(path: &Path) -> PaceResult<DataFrame> {
+    let reader = BufReader::new(open_file(path)?);
+    let mut names: Vec<String> = Vec::new();
+    let mut rows: Vec<Map<String, Value>> = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|source| io_error(path, source))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row: Map<String, Value> = serde_json::from_str(&line).map_err(|error| {
+            PaceError::InvalidData(format!(
+                "\"{}\" line {}: {}",
+                path.display(),
+                index + 1,
+                error
+            ))
+        })?;
+        for name in row.keys() {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        rows.push(row);
+    }
 
-pub fn read_df_csv(path: &Path) -> DataFrame {
-    let mut file = std::fs::File::open(path).unwrap();
-    let df = CsvReader::new(&mut file).finish().unwrap();
-    return df;
+    let columns = names
+        .iter()
+        .map(|name| json_to_series(name, rows.iter().map(|row| row.get(name)).collect()))
+        .collect();
+    let df = DataFrame::new(columns)?;
+    return Ok(df);
 }
 
-pub fn read_df_parquet(path: &Path) -> DataFrame {
-    let mut file = std::fs::File::open(path).unwrap();
-    let df = ParquetReader::new(&mut file).finish().unwrap();
-    return df;
+fn json_to_series(name: &str, values: Vec<Option<&Value>>) -> Series {
+    let values: Vec<Option<&Value>> = values
+        .into_iter()
+        .map(|value| value.filter(|value| !value.is_null()))
+        .collect();
+    let present = || values.iter().flatten();
+
+    if present().next().is_some() && present().all(|value| value.is_i64()) {
+        let values: Vec<Option<i64>> = values.iter().map(|v| v.and_then(Value::as_i64)).collect();
+        return Series::new(name, values);
+    }
+    if present().all(|value| value.is_number()) {
+        let values: Vec<Option<f64>> = values.iter().map(|v| v.and_then(Value::as_f64)).collect();
+        return Series::new(name, values);
+    }
+    if present().all(|value| value.is_boolean()) {
+        let values: Vec<Option<bool>> = values.iter().map(|v| v.and_then(Value::as_bool)).collect();
+        return Series::new(name, values);
+    }
+    let values: Vec<Option<String>> = values
+        .iter()
+        .map(|value| {
+            value.map(|value| match value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            })
+        })
+        .collect();
+    return Series::new(name, values);
 }
 
-pub fn read_df(path: &Path) -> DataFrame {
+/// Reads a file, depending on the extension:
+/// - `csv`, or gzip compressed `csv.gz`
+/// - `parquet`
+/// - `arrow`, `ipc` and `feather` - Arrow IPC file
+/// - `arrows` - Arrow IPC stream
+/// - `ndjson` and `jsonl` - newline-delimited JSON
+pub fn read_df(path: &Path) -> PaceResult<DataFrame> {
+    if is_csv_gzip(path) {
+        return read_df_csv(path);
+    }
     let extension = get_filename_extension(path);
-    match extension {
+    return match extension {
         Some("parquet") => read_df_parquet(path),
         Some("csv") => read_df_csv(path),
-        Some(&_) => panic!("Unsupported file type"),
-        None => panic!("Unsupported file type"),
-    }
+        Some("arrow") | Some("ipc") | Some("feather") => read_df_ipc(path),
+        Some("arrows") => read_df_ipc_stream(path),
+        Some("ndjson") | Some("jsonl") => read_df_ndjson(path),
+        _ => Err(unsupported_file_type(path)),
+    };
 }
 
-pub fn save_df(df: &mut DataFrame, path: &Path) {
+/// Saves a file in a format depending on the extension, same as in `read_df`. Parent directories are created if needed.
+pub fn save_df(df: &mut DataFrame, path: &Path) -> PaceResult<()> {
+    if is_csv_gzip(path) {
+        return save_df_csv_gzip(df, path);
+    }
     let extension = get_filename_extension(path);
-
-    match extension {
+    return match extension {
         Some("parquet") => save_df_parquet(df, path),
         Some("csv") => save_df_csv(df, path),
-        Some(&_) => panic!("Unsupported file type"),
-        None => panic!("Unsupported file type"),
+        Some("arrow") | Some("ipc") | Some("feather") => save_df_ipc(df, path),
+        Some("arrows") => save_df_ipc_stream(df, path),
+        Some("ndjson") | Some("jsonl") => save_df_ndjson(df, path),
+        _ => Err(unsupported_file_type(path)),
     };
 }
 
-pub fn save_df_csv(df: &mut DataFrame, path: &Path) {
-    ensure_dir(path.parent().unwrap());
-    let mut file = std::fs::File::create(path).unwrap();
-    CsvWriter::new(&mut file).finish(df).unwrap();
+pub fn save_df_csv(df: &mut DataFrame, path: &Path) -> PaceResult<()> {
+    let mut file = create_file(path)?;
+    CsvWriter::new(&mut file).finish(df)?;
+    return Ok(());
 }
 
-pub fn save_df_parquet(df: &mut DataFrame, path: &Path) {
-    ensure_dir(path.parent().unwrap());
-    let mut file = std::fs::File::create(path).unwrap();
-    ParquetWriter::new(&mut file).finish(df).unwrap();
+pub fn save_df_parquet(df: &mut DataFrame, path: &Path) -> PaceResult<()> {
+    let mut file = create_file(path)?;
+    ParquetWriter::new(&mut file).finish(df)?;
+    return Ok(());
+}
+
+pub fn save_df_csv_gzip(df: &mut DataFrame, path: &Path) -> PaceResult<()> {
+    let file = create_file(path)?;
+    let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    CsvWriter::new(&mut encoder).finish(df)?;
+    encoder.finish().map_err(|source| io_error(path, source))?;
+    return Ok(());
+}
+
+/// Saves an uncompressed Arrow IPC file, readable as Feather v2.
+pub fn save_df_ipc(df: &mut DataFrame, path: &Path) -> PaceResult<()> {
+    let mut file = create_file(path)?;
+    IpcWriter::new(&mut file).finish(df)?;
+    return Ok(());
+}
+
+pub fn save_df_ipc_stream(df: &mut DataFrame, path: &Path) -> PaceResult<()> {
+    let mut file = create_file(path)?;
+    IpcStreamWriter::new(&mut file).finish(df)?;
+    return Ok(());
+}
+
+/// Saves newline-delimited JSON with one object per row. Nulls and NaNs are written as `null`.
+pub fn save_df_ndjson(df: &mut DataFrame, path: &Path) -> PaceResult<()> {
+    let names = df.get_column_names();
+    let columns = df
+        .get_columns()
+        .iter()
+        .map(series_to_json)
+        .collect::<PaceResult<Vec<Vec<Value>>>>()?;
+
+    let mut writer = BufWriter::new(create_file(path)?);
+    for row in 0..df.height() {
+        let object: Map<String, Value> = names
+            .iter()
+            .zip(&columns)
+            .map(|(name, values)| (name.to_string(), values[row].clone()))
+            .collect();
+        writeln!(writer, "{}", Value::Object(object)).map_err(|source| io_error(path, source))?;
+    }
+    writer.flush().map_err(|source| io_error(path, source))?;
+    return Ok(());
+}
+
+fn series_to_json(series: &Series) -> PaceResult<Vec<Value>> {
+    let dtype = series.dtype();
+    let values: Vec<Value> = match dtype {
+        DataType::Boolean => series
+            .bool()?
+            .into_iter()
+            .map(|value| value.map_or(Value::Null, Value::Bool))
+            .collect(),
+        dtype if dtype.is_integer() => series
+            .cast(&DataType::Int64)?
+            .i64()?
+            .into_iter()
+            .map(|value| value.map_or(Value::Null, Value::from))
+            .collect(),
+        dtype if dtype.is_numeric() => series
+            .cast(&DataType::Float64)?
+            .f64()?
+            .into_iter()
+            .map(|value| {
+                value
+                    .and_then(Number::from_f64)
+                    .map_or(Value::Null, Value::Number)
+            })
+            .collect(),
+        _ => series
+            .cast(&DataType::Utf8)?
+            .utf8()?
+            .into_iter()
+            .map(|value| value.map_or(Value::Null, |value| Value::String(value.to_string())))
+            .collect(),
+    };
+    return Ok(values);
 }
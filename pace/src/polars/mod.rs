@@ -1,4 +1,10 @@
+pub mod chunked_data_provider;
 pub mod dataframe;
+pub mod dataframe_data_provider;
 pub mod in_memory_data_provider;
 pub mod io;
+pub mod schema;
 pub mod series;
+pub mod vendor;
+
+mod tests;
@@ -0,0 +1,299 @@
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use polars::{
+    prelude::{DataFrame, DataType, TimeUnit},
+    series::Series,
+};
+
+use crate::core::error::{PaceError, PaceResult};
+
+/// Unit of a numeric timestamp.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimestampUnit {
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl TimestampUnit {
+    /// Guesses unit of a timestamp after 1973 from its magnitude.
+    pub fn detect(timestamp: f64) -> Self {
+        let timestamp = timestamp.abs();
+        if timestamp < 1e11 {
+            return TimestampUnit::Seconds;
+        }
+        if timestamp < 1e14 {
+            return TimestampUnit::Milliseconds;
+        }
+        if timestamp < 1e17 {
+            return TimestampUnit::Microseconds;
+        }
+        return TimestampUnit::Nanoseconds;
+    }
+
+    pub fn per_second(&self) -> f64 {
+        return match self {
+            TimestampUnit::Seconds => 1.0,
+            TimestampUnit::Milliseconds => 1e3,
+            TimestampUnit::Microseconds => 1e6,
+            TimestampUnit::Nanoseconds => 1e9,
+        };
+    }
+
+    /// Time since epoch of an integer `timestamp`, without float rounding. `None` for negative timestamps.
+    pub fn duration(&self, timestamp: i64) -> Option<Duration> {
+        let timestamp = u64::try_from(timestamp).ok()?;
+        return Some(match self {
+            TimestampUnit::Seconds => Duration::from_secs(timestamp),
+            TimestampUnit::Milliseconds => Duration::from_millis(timestamp),
+            TimestampUnit::Microseconds => Duration::from_micros(timestamp),
+            TimestampUnit::Nanoseconds => Duration::from_nanos(timestamp),
+        });
+    }
+}
+
+impl From<TimeUnit> for TimestampUnit {
+    fn from(unit: TimeUnit) -> Self {
+        return match unit {
+            TimeUnit::Milliseconds => TimestampUnit::Milliseconds,
+            TimeUnit::Microseconds => TimestampUnit::Microseconds,
+            TimeUnit::Nanoseconds => TimestampUnit::Nanoseconds,
+        };
+    }
+}
+
+/// Column names and time format of a data frame with OHLCV data.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameSchema {
+    pub open: String,
+    pub high: String,
+    pub low: String,
+    pub close: String,
+    /// If `None`, volume is missing on every bar.
+    pub volume: Option<String>,
+    /// If `None`, time is missing on every bar.
+    pub time: Option<String>,
+    /// Unit of a numeric time column. If `None`, it is detected from the magnitude of the first timestamp.
+    ///
+    /// Ignored for polars `Datetime` and `Date` columns, which carry their own unit.
+    pub time_unit: Option<TimestampUnit>,
+    /// `chrono` format of a string time column, e.g. `"%Y-%m-%d %H:%M"`. If `None`, RFC 3339 and ISO 8601 formats are tried.
+    pub datetime_format: Option<String>,
+    /// Columns loaded as auxiliary series, e.g. funding rate or open interest. If `None`, all other numeric columns are loaded.
+    pub series: Option<Vec<String>>,
+}
+
+impl Default for DataFrameSchema {
+    fn default() -> Self {
+        return Self {
+            open: "open".to_string(),
+            high: "high".to_string(),
+            low: "low".to_string(),
+            close: "close".to_string(),
+            volume: Some("volume".to_string()),
+            time: Some("time".to_string()),
+            time_unit: Some(TimestampUnit::Seconds),
+            datetime_format: None,
+            series: None,
+        };
+    }
+}
+
+const OPEN_ALIASES: &[&str] = &["open", "o", "open_price"];
+const HIGH_ALIASES: &[&str] = &["high", "h", "high_price"];
+const LOW_ALIASES: &[&str] = &["low", "l", "low_price"];
+const CLOSE_ALIASES: &[&str] = &["close", "c", "close_price", "price"];
+const VOLUME_ALIASES: &[&str] = &["volume", "vol", "v", "tick_volume", "base_volume"];
+const TIME_ALIASES: &[&str] = &[
+    "time",
+    "timestamp",
+    "datetime",
+    "date",
+    "open_time",
+    "ts",
+    "t",
+];
+
+/// Formats tried when `datetime_format` is not set.
+const DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+    "%Y.%m.%d %H:%M:%S",
+    "%Y.%m.%d %H:%M",
+];
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%Y.%m.%d", "%Y/%m/%d", "%Y%m%d"];
+
+impl DataFrameSchema {
+    /// Names of OHLCV and time columns.
+    pub fn columns(&self) -> Vec<&str> {
+        let mut columns = vec![
+            self.open.as_str(),
+            self.high.as_str(),
+            self.low.as_str(),
+            self.close.as_str(),
+        ];
+        columns.extend(self.volume.as_deref());
+        columns.extend(self.time.as_deref());
+        return columns;
+    }
+
+    /// Names of columns loaded as auxiliary series.
+    pub fn series_columns<'a>(&'a self, df: &'a DataFrame) -> Vec<&'a str> {
+        if let Some(series) = &self.series {
+            return series.iter().map(|name| name.as_str()).collect();
+        }
+        let columns = self.columns();
+        return df
+            .get_columns()
+            .iter()
+            .filter(|column| column.dtype().is_numeric() && !columns.contains(&column.name()))
+            .map(|column| column.name())
+            .collect();
+    }
+
+    /// Detects column names, ignoring case, from common aliases like `Open`, `o`, `timestamp` or `Date`.
+    ///
+    /// Time unit is detected from data. Returns an error if any of open, high, low or close is missing.
+    pub fn detect(df: &DataFrame) -> PaceResult<Self> {
+        let names = df.get_column_names();
+        let find = |aliases: &[&str]| -> Option<String> {
+            return aliases.iter().find_map(|alias| {
+                names
+                    .iter()
+                    .find(|name| name.trim().eq_ignore_ascii_case(alias))
+                    .map(|name| name.to_string())
+            });
+        };
+        let require = |aliases: &[&str]| -> PaceResult<String> {
+            return find(aliases).ok_or_else(|| PaceError::MissingColumn(aliases[0].to_string()));
+        };
+
+        return Ok(Self {
+            open: require(OPEN_ALIASES)?,
+            high: require(HIGH_ALIASES)?,
+            low: require(LOW_ALIASES)?,
+            close: require(CLOSE_ALIASES)?,
+            volume: find(VOLUME_ALIASES),
+            time: find(TIME_ALIASES),
+            time_unit: None,
+            datetime_format: None,
+            series: None,
+        });
+    }
+}
+
+/// Converts a time column to durations since epoch.
+///
+/// Supports numeric timestamps, polars `Datetime` and `Date` columns and datetime strings.
+pub fn series_to_time(
+    series: &Series,
+    unit: Option<TimestampUnit>,
+    datetime_format: Option<&str>,
+) -> PaceResult<Vec<Option<Duration>>> {
+    let invalid_value = |index: usize, value: String, expected: &str| PaceError::InvalidValue {
+        column: series.name().to_string(),
+        index: Some(index),
+        value: Some(value),
+        expected: expected.to_string(),
+    };
+
+    let (series, unit) = match series.dtype() {
+        DataType::Datetime(time_unit, _) => (
+            series.cast(&DataType::Int64)?,
+            Some(TimestampUnit::from(*time_unit)),
+        ),
+        DataType::Date => {
+            let days = series.cast(&DataType::Int32)?.cast(&DataType::Float64)?;
+            ((days * 86400.0), Some(TimestampUnit::Seconds))
+        }
+        DataType::Utf8 => {
+            return series
+                .utf8()?
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| match value {
+                    Some(value) => parse_datetime(value, datetime_format)
+                        .and_then(|datetime| u64::try_from(datetime.timestamp_millis()).ok())
+                        .map(Duration::from_millis)
+                        .map(Some)
+                        .ok_or_else(|| {
+                            invalid_value(
+                                index,
+                                value.to_string(),
+                                datetime_format.unwrap_or("ISO 8601 datetime"),
+                            )
+                        }),
+                    None => Ok(None),
+                })
+                .collect();
+        }
+        _ => (series.clone(), unit),
+    };
+
+    let unit = match unit {
+        Some(unit) => unit,
+        None => detect_time_unit(&series)?.unwrap_or(TimestampUnit::Seconds),
+    };
+    let values = series.cast(&DataType::Float64)?;
+    let values = values.f64()?;
+
+    return values
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| match value {
+            Some(value) if value.is_nan() => Ok(None),
+            Some(value) => Duration::try_from_secs_f64(value / unit.per_second())
+                .map(Some)
+                .map_err(|_| invalid_value(index, value.to_string(), "non-negative timestamp")),
+            None => Ok(None),
+        })
+        .collect();
+}
+
+/// Unit of a numeric time column, detected from the magnitude of its first timestamp. `None` if the column is not numeric or has no timestamps.
+pub fn detect_time_unit(series: &Series) -> PaceResult<Option<TimestampUnit>> {
+    if !series.dtype().is_numeric() {
+        return Ok(None);
+    }
+    let values = series.cast(&DataType::Float64)?;
+    return Ok(values
+        .f64()?
+        .into_iter()
+        .flatten()
+        .find(|value| !value.is_nan())
+        .map(TimestampUnit::detect));
+}
+
+/// Parses a datetime string. Datetimes with a timezone are converted to UTC, others are assumed to be UTC.
+pub fn parse_datetime(value: &str, format: Option<&str>) -> Option<NaiveDateTime> {
+    let value = value.trim();
+
+    if let Some(format) = format {
+        return NaiveDateTime::parse_from_str(value, format)
+            .ok()
+            .or_else(|| {
+                NaiveDate::parse_from_str(value, format)
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+            });
+    }
+
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Some(datetime.naive_utc());
+    }
+
+    return DATETIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            DATE_FORMATS.iter().find_map(|format| {
+                NaiveDate::parse_from_str(value, format)
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+            })
+        });
+}
@@ -1,30 +1,56 @@
-use std::{ffi::OsStr, path::Path, time::Duration};
+use std::time::Duration;
 
 use polars::{
-    prelude::{
-        CsvReader, CsvWriter, DataFrame, DataType, IsFloat, ParquetReader, ParquetWriter,
-        SerReader, SerWriter, TimeUnit,
-    },
+    prelude::{DataType, IsFloat},
     series::Series,
 };
 
-use crate::strategy::trade::{trade_direction_from_f64, TradeDirection};
+use crate::{
+    core::error::{PaceError, PaceResult},
+    strategy::trade::{trade_direction_from_f64, TradeDirection},
+};
 
 pub trait SeriesCastUtils {
-    fn to_f64(&self) -> Vec<Option<f64>>;
-    fn to_i32(&self) -> Vec<Option<i32>>;
-    fn to_usize(&self) -> Vec<Option<usize>>;
-    fn to_duration(&self) -> Vec<Option<Duration>>;
-    fn to_trade_dir(&self) -> Vec<Option<TradeDirection>>;
+    fn to_f64(&self) -> PaceResult<Vec<Option<f64>>>;
+    fn to_i32(&self) -> PaceResult<Vec<Option<i32>>>;
+    fn to_usize(&self) -> PaceResult<Vec<Option<usize>>>;
+    fn to_duration(&self) -> PaceResult<Vec<Option<Duration>>>;
+    fn to_trade_dir(&self) -> PaceResult<Vec<Option<TradeDirection>>>;
+}
+
+/// Casts `series` to `data_type`. Returns an error naming the first value that could not be converted.
+pub(crate) fn cast(series: &Series, data_type: &DataType) -> PaceResult<Series> {
+    let invalid_value = |index: Option<usize>, value: Option<String>| PaceError::InvalidValue {
+        column: series.name().to_string(),
+        index,
+        value,
+        expected: data_type.to_string(),
+    };
+
+    let casted = series
+        .cast(data_type)
+        .map_err(|_| invalid_value(None, None))?;
+
+    // Values that can't be converted become null.
+    if casted.null_count() > series.null_count() {
+        let was_null = series.is_null();
+        let index = was_null
+            .into_iter()
+            .zip(&casted.is_null())
+            .position(|(was_null, is_null)| was_null == Some(false) && is_null == Some(true));
+        let value = index
+            .and_then(|index| series.get(index).ok())
+            .map(|value| value.to_string());
+        return Err(invalid_value(index, value));
+    }
+
+    return Ok(casted);
 }
 
 impl SeriesCastUtils for Series {
-    fn to_f64(&self) -> Vec<Option<f64>> {
-        return self
-            .cast(&DataType::Float64)
-            .unwrap()
-            .f64()
-            .unwrap()
+    fn to_f64(&self) -> PaceResult<Vec<Option<f64>>> {
+        return Ok(cast(self, &DataType::Float64)?
+            .f64()?
             .into_iter()
             .map(|val| {
                 if val.is_none() || val.unwrap().is_nan() {
@@ -33,15 +59,12 @@ impl SeriesCastUtils for Series {
                     val
                 }
             })
-            .collect::<Vec<_>>();
+            .collect::<Vec<_>>());
     }
 
-    fn to_i32(&self) -> Vec<Option<i32>> {
-        return self
-            .cast(&DataType::Int32)
-            .unwrap()
-            .i32()
-            .unwrap()
+    fn to_i32(&self) -> PaceResult<Vec<Option<i32>>> {
+        return Ok(cast(self, &DataType::Int32)?
+            .i32()?
             .into_iter()
             .map(|val| {
                 if val.is_none() || val.unwrap().is_nan() {
@@ -50,15 +73,12 @@ impl SeriesCastUtils for Series {
                     val
                 }
             })
-            .collect::<Vec<_>>();
+            .collect::<Vec<_>>());
     }
 
-    fn to_usize(&self) -> Vec<Option<usize>> {
-        return self
-            .cast(&DataType::UInt64)
-            .unwrap()
-            .u64()
-            .unwrap()
+    fn to_usize(&self) -> PaceResult<Vec<Option<usize>>> {
+        return Ok(cast(self, &DataType::UInt64)?
+            .u64()?
             .into_iter()
             .map(|val| {
                 if val.is_none() || val.unwrap().is_nan() {
@@ -67,32 +87,33 @@ impl SeriesCastUtils for Series {
                     val.map(|x| x as usize)
                 }
             })
-            .collect::<Vec<_>>();
+            .collect::<Vec<_>>());
     }
 
-    fn to_duration(&self) -> Vec<Option<Duration>> {
-        return self
-            .cast(&DataType::Float64)
-            .unwrap()
-            .f64()
-            .unwrap()
+    /// Converts seconds since epoch to `Duration`. Negative values are not allowed.
+    fn to_duration(&self) -> PaceResult<Vec<Option<Duration>>> {
+        return cast(self, &DataType::Float64)?
+            .f64()?
             .into_iter()
-            .map(|val| {
-                if val.is_none() || val.unwrap().is_nan() {
-                    None
-                } else {
-                    Some(Duration::from_secs_f64(val.unwrap()))
-                }
+            .enumerate()
+            .map(|(index, val)| match val {
+                Some(val) if val.is_nan() => Ok(None),
+                Some(val) => Duration::try_from_secs_f64(val).map(Some).map_err(|_| {
+                    PaceError::InvalidValue {
+                        column: self.name().to_string(),
+                        index: Some(index),
+                        value: Some(val.to_string()),
+                        expected: "non-negative seconds".to_string(),
+                    }
+                }),
+                None => Ok(None),
             })
-            .collect::<Vec<_>>();
+            .collect();
     }
 
-    fn to_trade_dir(&self) -> Vec<Option<TradeDirection>> {
-        return self
-            .cast(&DataType::Float64)
-            .unwrap()
-            .f64()
-            .unwrap()
+    fn to_trade_dir(&self) -> PaceResult<Vec<Option<TradeDirection>>> {
+        return Ok(cast(self, &DataType::Float64)?
+            .f64()?
             .into_iter()
             .map(|val| {
                 if val.is_none() || val.unwrap().is_nan() {
@@ -101,6 +122,6 @@ impl SeriesCastUtils for Series {
                     trade_direction_from_f64(val)
                 }
             })
-            .collect::<Vec<_>>();
+            .collect::<Vec<_>>());
     }
 }
@@ -0,0 +1,213 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::File,
+        path::{Path, PathBuf},
+        time::Duration,
+    };
+
+    use polars::prelude::{IpcCompression, IpcWriter, NamedFrom, ParquetWriter, SerWriter, Series};
+
+    use crate::{
+        core::{
+            context::Context, data_provider::DataProvider, error::PaceError,
+            in_memory_data_provider::InMemoryDataProvider, incremental::Incremental,
+        },
+        polars::chunked_data_provider::{ChunkedDataProvider, ChunkedDataProviderConfig},
+        ta::highest_bars::HighestBars,
+        testing::{fixture::Fixture, temp_dir::TempDir},
+    };
+
+    const BARS: usize = 10;
+
+    /// Parquet file with row groups of about 3 rows.
+    fn write_parquet(dir: &TempDir, name: &str) -> PathBuf {
+        let path = dir.join(name);
+        ParquetWriter::new(File::create(&path).unwrap())
+            .with_row_group_size(Some(3))
+            .finish(&mut Fixture::ohlcv_df(BARS, 0, 60))
+            .unwrap();
+        return path;
+    }
+
+    /// Arrow IPC file with record batches of 4 rows.
+    fn write_ipc(dir: &TempDir, name: &str, compression: Option<IpcCompression>) -> PathBuf {
+        let path = dir.join(name);
+        let df = Fixture::ohlcv_df(BARS, 0, 60);
+        let mut writer = IpcWriter::new(File::create(&path).unwrap())
+            .with_compression(compression)
+            .batched(&df.schema())
+            .unwrap();
+        for offset in (0..BARS).step_by(4) {
+            writer
+                .write_batch(&df.slice(offset as i64, 4.min(BARS - offset)))
+                .unwrap();
+        }
+        writer.finish().unwrap();
+        return path;
+    }
+
+    fn assert_same_data(data: &dyn DataProvider) {
+        let expected = InMemoryDataProvider::from_df(&Fixture::ohlcv_df(BARS, 0, 60)).unwrap();
+
+        assert_eq!(data.get_start_tick(), 0);
+        assert_eq!(data.get_end_tick(), BARS - 1);
+        assert_eq!(data.get_series_names(), vec!["funding_rate"]);
+
+        for i in 0..BARS {
+            assert_eq!(data.get_open(i), expected.get_open(i));
+            assert_eq!(data.get_high(i), expected.get_high(i));
+            assert_eq!(data.get_low(i), expected.get_low(i));
+            assert_eq!(data.get_close(i), expected.get_close(i));
+            assert_eq!(data.get_volume(i), expected.get_volume(i));
+            assert_eq!(data.get_time(i), expected.get_time(i));
+            assert_eq!(
+                data.get_series("funding_rate", i),
+                expected.get_series("funding_rate", i)
+            );
+        }
+
+        // Across chunk boundaries.
+        assert_eq!(
+            data.get_close_for_range(1, 8),
+            expected.get_close_for_range(1, 8)
+        );
+        assert_eq!(
+            data.get_high_for_range(0, BARS - 1),
+            expected.get_high_for_range(0, BARS - 1)
+        );
+        assert_eq!(
+            data.get_series_for_range("funding_rate", 2, 5),
+            expected.get_series_for_range("funding_rate", 2, 5)
+        );
+        assert!(data.get_series_for_range("sentiment", 2, 5).is_empty());
+    }
+
+    #[test]
+    fn parquet_row_groups() {
+        let dir = TempDir::new("chunked_data_provider_test");
+        let path = write_parquet(&dir, "row_groups.parquet");
+        let data = ChunkedDataProvider::open(
+            &path,
+            ChunkedDataProviderConfig {
+                max_chunks: 2,
+                ..ChunkedDataProviderConfig::default()
+            },
+        )
+        .unwrap();
+
+        assert!(data.chunk_count() >= 3);
+        assert_same_data(&data);
+        assert_eq!(data.loaded_chunks().len(), 2);
+
+        data.get_close(4);
+        assert_eq!(*data.loaded_chunks().last().unwrap(), 1);
+    }
+
+    #[test]
+    fn ipc_record_batches() {
+        let dir = TempDir::new("chunked_data_provider_test");
+        for (name, compression) in [
+            ("batches.arrow", None),
+            ("batches_lz4.arrow", Some(IpcCompression::LZ4)),
+            ("batches_zstd.feather", Some(IpcCompression::ZSTD)),
+        ] {
+            let path = write_ipc(&dir, name, compression);
+            let data = ChunkedDataProvider::open(
+                &path,
+                ChunkedDataProviderConfig {
+                    max_chunks: 1,
+                    ..ChunkedDataProviderConfig::default()
+                },
+            )
+            .unwrap();
+
+            assert_eq!(data.chunk_count(), 3);
+            assert_same_data(&data);
+            assert_eq!(data.loaded_chunks(), vec![1]);
+        }
+    }
+
+    #[test]
+    fn context_with_chunks() {
+        let dir = TempDir::new("chunked_data_provider_test");
+        let path = write_parquet(&dir, "context.parquet");
+        let ctx = Context::new(
+            ChunkedDataProvider::open(
+                &path,
+                ChunkedDataProviderConfig {
+                    max_chunks: 1,
+                    ..ChunkedDataProviderConfig::default()
+                },
+            )
+            .unwrap()
+            .to_arc(),
+        );
+        let expected_ctx = Context::new(
+            InMemoryDataProvider::from_df(&Fixture::ohlcv_df(BARS, 0, 60))
+                .unwrap()
+                .to_arc(),
+        );
+
+        let mut highest_bars = HighestBars::new(ctx.clone(), 4);
+        let mut expected_highest_bars = HighestBars::new(expected_ctx.clone(), 4);
+
+        for _ in ctx.clone() {
+            expected_ctx.bar.index.set(ctx.bar.index.get());
+            assert_eq!(highest_bars.next(()), expected_highest_bars.next(()));
+            assert_eq!(ctx.bar.close(), expected_ctx.bar.close());
+            assert_eq!(
+                ctx.bar.time(),
+                Some(Duration::from_secs(ctx.bar.index.get() as u64 * 60))
+            );
+        }
+    }
+
+    #[test]
+    fn time_unit_of_first_chunk() {
+        let dir = TempDir::new("chunked_data_provider_test");
+        let path = dir.join("time_unit.parquet");
+        let mut df = Fixture::ohlcv_df(BARS, 0, 60).head(Some(6));
+        df.with_column(Series::new(
+            "time",
+            (0..6)
+                .map(|i| 99_999_999_997.0 + i as f64)
+                .collect::<Vec<f64>>(),
+        ))
+        .unwrap();
+        ParquetWriter::new(File::create(&path).unwrap())
+            .with_row_group_size(Some(3))
+            .finish(&mut df)
+            .unwrap();
+
+        // Second row group starts at 1e11, which alone would be detected as milliseconds.
+        let data =
+            ChunkedDataProvider::open_parquet(&path, ChunkedDataProviderConfig::default()).unwrap();
+        assert_eq!(data.chunk_count(), 2);
+        assert_eq!(data.get_time(3), Some(Duration::from_secs(100_000_000_000)));
+    }
+
+    #[test]
+    fn missing_column() {
+        let dir = TempDir::new("chunked_data_provider_test");
+        let path = dir.join("missing_close.parquet");
+        let mut df = Fixture::ohlcv_df(BARS, 0, 60).drop("close").unwrap();
+        ParquetWriter::new(File::create(&path).unwrap())
+            .finish(&mut df)
+            .unwrap();
+
+        let error = ChunkedDataProvider::open(&path, ChunkedDataProviderConfig::default())
+            .err()
+            .unwrap();
+        assert!(matches!(error, PaceError::MissingColumn(column) if column == "close"));
+    }
+
+    #[test]
+    fn unsupported_file_type() {
+        let error =
+            ChunkedDataProvider::open(Path::new("data.csv"), ChunkedDataProviderConfig::default())
+                .err()
+                .unwrap();
+        assert!(matches!(error, PaceError::UnsupportedFileType { .. }));
+    }
+}
@@ -0,0 +1,209 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use polars::prelude::{DataFrame, DataType, NamedFrom, Series, TimeUnit};
+
+    use crate::{
+        core::{
+            context::Context, data_provider::DataProvider, error::PaceError,
+            in_memory_data_provider::InMemoryDataProvider, incremental::Incremental,
+        },
+        polars::{dataframe_data_provider::DataFrameDataProvider, series::SeriesCastUtils},
+        ta::highest_bars::HighestBars,
+        testing::fixture::Fixture,
+    };
+
+    /// 2024-01-29 00:00:00 UTC.
+    const START_TIME: u64 = 1706486400;
+
+    fn with_time(mut df: DataFrame, time: Series) -> DataFrame {
+        df.with_column(time).unwrap();
+        return df;
+    }
+
+    /// Bars with missing and `NaN` prices, integer volume and a missing series value.
+    fn ohlcv_df() -> DataFrame {
+        let mut df = Fixture::ohlcv_df(4, START_TIME, 60);
+        for series in [
+            Series::new("open", &[Some(1.0), None, Some(3.0), Some(4.0)]),
+            Series::new("high", &[2.0, 3.0, f64::NAN, 5.0]),
+            Series::new("volume", &[10_i64, 20, 30, 40]),
+            Series::new("funding_rate", &[Some(0.01), None, Some(0.03), Some(0.04)]),
+        ] {
+            df.with_column(series).unwrap();
+        }
+        return df;
+    }
+
+    fn assert_same_data(data: &dyn DataProvider, expected: &dyn DataProvider) {
+        assert_eq!(data.get_start_tick(), expected.get_start_tick());
+        assert_eq!(data.get_end_tick(), expected.get_end_tick());
+        assert_eq!(data.get_series_names(), expected.get_series_names());
+
+        for i in data.get_start_tick()..=data.get_end_tick() {
+            assert_eq!(data.get_open(i), expected.get_open(i));
+            assert_eq!(data.get_high(i), expected.get_high(i));
+            assert_eq!(data.get_low(i), expected.get_low(i));
+            assert_eq!(data.get_close(i), expected.get_close(i));
+            assert_eq!(data.get_volume(i), expected.get_volume(i));
+            assert_eq!(data.get_time(i), expected.get_time(i));
+            for name in data.get_series_names() {
+                assert_eq!(data.get_series(name, i), expected.get_series(name, i));
+            }
+        }
+
+        let end = data.get_end_tick();
+        assert_eq!(
+            data.get_open_for_range(0, end),
+            expected.get_open_for_range(0, end)
+        );
+        assert_eq!(
+            data.get_high_for_range(1, end),
+            expected.get_high_for_range(1, end)
+        );
+        assert_eq!(
+            data.get_low_for_range(1, 2),
+            expected.get_low_for_range(1, 2)
+        );
+        assert_eq!(
+            data.get_close_for_range(0, 0),
+            expected.get_close_for_range(0, 0)
+        );
+        assert_eq!(
+            data.get_volume_for_range(0, end),
+            expected.get_volume_for_range(0, end)
+        );
+        for name in data.get_series_names() {
+            assert_eq!(
+                data.get_series_for_range(name, 1, end),
+                expected.get_series_for_range(name, 1, end)
+            );
+        }
+        assert!(data.get_series_for_range("sentiment", 0, end).is_empty());
+    }
+
+    #[test]
+    fn same_as_in_memory() {
+        let df = ohlcv_df();
+        let data = DataFrameDataProvider::from_df(&df).unwrap();
+        let expected = InMemoryDataProvider::from_df(&df).unwrap();
+
+        assert_same_data(&data, &expected);
+        assert_eq!(data.get_open(1), None);
+        assert_eq!(data.get_high(2), None);
+        assert_eq!(data.get_volume(3), Some(40.0));
+        assert_eq!(data.get_time(1), Some(Duration::from_secs(START_TIME + 60)));
+        assert_eq!(data.get_series_names(), vec!["funding_rate"]);
+    }
+
+    #[test]
+    fn time_columns() {
+        let prices = ohlcv_df().drop("time").unwrap();
+        let seconds = [
+            START_TIME,
+            START_TIME + 60,
+            START_TIME + 120,
+            START_TIME + 180,
+        ];
+
+        let millis = Series::new(
+            "time",
+            seconds
+                .iter()
+                .map(|s| (s * 1000) as i64)
+                .collect::<Vec<i64>>(),
+        );
+        let datetime = millis
+            .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+            .unwrap();
+        let strings = Series::new(
+            "time",
+            &[
+                "2024-01-29 00:00:00",
+                "2024-01-29 00:01:00",
+                "2024-01-29 00:02:00",
+                "2024-01-29 00:03:00",
+            ],
+        );
+
+        for time in [millis, datetime, strings] {
+            let df = with_time(prices.clone(), time);
+            let data = DataFrameDataProvider::from_df(&df).unwrap();
+            assert_same_data(&data, &InMemoryDataProvider::from_df(&df).unwrap());
+            for (i, seconds) in seconds.iter().enumerate() {
+                assert_eq!(data.get_time(i), Some(Duration::from_secs(*seconds)));
+            }
+        }
+
+        let data = DataFrameDataProvider::from_df(&prices).unwrap();
+        assert_eq!(data.get_time(0), None);
+    }
+
+    #[test]
+    fn nanosecond_datetime() {
+        let prices = ohlcv_df().drop("time").unwrap();
+        // Above 2^53, so not exactly representable as a float.
+        let nanos: Vec<i64> = (0..4)
+            .map(|i| (START_TIME as i64 + i * 60) * 1_000_000_000 + 123_456_789)
+            .collect();
+        let datetime = Series::new("time", nanos.clone())
+            .cast(&DataType::Datetime(TimeUnit::Nanoseconds, None))
+            .unwrap();
+
+        let data = DataFrameDataProvider::from_df(&with_time(prices, datetime)).unwrap();
+        for (i, nanos) in nanos.iter().enumerate() {
+            assert_eq!(data.get_time(i), Some(Duration::from_nanos(*nanos as u64)));
+        }
+    }
+
+    #[test]
+    fn multiple_chunks() {
+        let mut df = ohlcv_df().slice(0, 2);
+        df.vstack_mut(&ohlcv_df().slice(2, 2)).unwrap();
+        assert_eq!(df.column("close").unwrap().n_chunks(), 2);
+
+        let data = DataFrameDataProvider::from_df(&df).unwrap();
+        assert_same_data(&data, &InMemoryDataProvider::from_df(&ohlcv_df()).unwrap());
+        assert_eq!(
+            *data.get_close_for_range(0, 3),
+            df.column("close").unwrap().to_f64().unwrap()
+        );
+    }
+
+    #[test]
+    fn context_with_dataframe() {
+        let df = ohlcv_df();
+        let ctx = Context::new(DataFrameDataProvider::from_df(&df).unwrap().to_arc());
+        let expected_ctx = Context::new(InMemoryDataProvider::from_df(&df).unwrap().to_arc());
+
+        let mut highest_bars = HighestBars::new(ctx.clone(), 2);
+        let mut expected_highest_bars = HighestBars::new(expected_ctx.clone(), 2);
+
+        for _ in ctx.clone() {
+            expected_ctx.bar.index.set(ctx.bar.index.get());
+            assert_eq!(highest_bars.next(()), expected_highest_bars.next(()));
+            assert_eq!(ctx.bar.close(), expected_ctx.bar.close());
+        }
+    }
+
+    #[test]
+    fn invalid_data() {
+        let error = DataFrameDataProvider::from_df(&ohlcv_df().slice(0, 0))
+            .err()
+            .unwrap();
+        assert!(matches!(error, PaceError::InvalidData(_)));
+
+        let error = DataFrameDataProvider::from_df(&ohlcv_df().drop("close").unwrap())
+            .err()
+            .unwrap();
+        assert!(matches!(error, PaceError::MissingColumn(column) if column == "close"));
+
+        let df = with_time(ohlcv_df(), Series::new("time", &[0.0, -60.0, 120.0, 180.0]));
+        let error = DataFrameDataProvider::from_df(&df).err().unwrap();
+        assert!(matches!(
+            error,
+            PaceError::InvalidValue { index: Some(1), ref column, .. } if column == "time"
+        ));
+    }
+}
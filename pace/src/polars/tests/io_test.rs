@@ -0,0 +1,194 @@
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use polars::prelude::{df, DataType, NamedFrom};
+
+    use crate::{
+        core::{
+            data_provider::DataProvider, error::PaceError,
+            in_memory_data_provider::InMemoryDataProvider,
+        },
+        polars::{
+            dataframe::DataFrameUtils,
+            io::{is_csv_gzip, read_df, read_df_ndjson, save_df},
+            series::SeriesCastUtils,
+        },
+        testing::{fixture::Fixture, temp_dir::TempDir},
+    };
+
+    #[test]
+    fn unsupported_file_type() {
+        let error = read_df(Path::new("data.xlsx")).unwrap_err();
+        assert!(matches!(error, PaceError::UnsupportedFileType { .. }));
+        assert_eq!(
+            error.to_string(),
+            "Unsupported file type \".xlsx\" of \"data.xlsx\""
+        );
+
+        let error = save_df(&mut Fixture::ohlcv_df(2, 0, 60), Path::new("data")).unwrap_err();
+        assert_eq!(error.to_string(), "Missing file extension of \"data\"");
+    }
+
+    #[test]
+    fn missing_file() {
+        let error = read_df(Path::new("missing/data.csv")).unwrap_err();
+        assert!(matches!(error, PaceError::Io { .. }));
+        assert!(error.to_string().contains("missing/data.csv"));
+    }
+
+    #[test]
+    fn missing_column() {
+        let mut df = Fixture::ohlcv_df(2, 0, 60);
+        let _ = df.drop_in_place("close").unwrap();
+
+        let error = InMemoryDataProvider::from_df(&df).err().unwrap();
+        assert!(matches!(&error, PaceError::MissingColumn(column) if column == "close"));
+        assert_eq!(error.to_string(), "Missing column \"close\"");
+    }
+
+    #[test]
+    fn empty_df() {
+        let df = Fixture::ohlcv_df(2, 0, 60).head(Some(0));
+
+        let error = InMemoryDataProvider::from_df(&df).err().unwrap();
+        assert!(matches!(error, PaceError::InvalidData(_)));
+    }
+
+    #[test]
+    fn merge_columns() {
+        let df = Fixture::ohlcv_df(2, 0, 60);
+        assert_eq!(
+            df.merge_two_columns("open", "close").unwrap(),
+            vec![Some((Some(1.0), Some(1.2))), Some((Some(2.0), Some(2.2)))]
+        );
+
+        let error = df.merge_three_columns("open", "close", "vwap").unwrap_err();
+        assert!(matches!(&error, PaceError::MissingColumn(column) if column == "vwap"));
+    }
+
+    #[test]
+    fn invalid_value() {
+        let df = df!("close" => &["1.0", "2.0", "abc"]).unwrap();
+
+        let error = df.column("close").unwrap().to_f64().unwrap_err();
+        match &error {
+            PaceError::InvalidValue {
+                column,
+                index,
+                value,
+                ..
+            } => {
+                assert_eq!(column, "close");
+                assert_eq!(*index, Some(2));
+                assert!(value.as_ref().unwrap().contains("abc"));
+            }
+            _ => panic!("Unexpected error {:?}", error),
+        }
+        assert!(error.to_string().contains("at row 2"));
+    }
+
+    #[test]
+    fn negative_time() {
+        let df = df!("time" => &[0.0, -60.0]).unwrap();
+        let error = df.column("time").unwrap().to_duration().unwrap_err();
+        assert!(matches!(
+            error,
+            PaceError::InvalidValue { index: Some(1), .. }
+        ));
+    }
+
+    #[test]
+    fn save_and_read() {
+        let dir = TempDir::new("io_test");
+        let path = dir.path().join("nested").join("data.csv");
+
+        save_df(&mut Fixture::ohlcv_df(2, 0, 60), &path).unwrap();
+        let df = read_df(&path).unwrap();
+        let data = InMemoryDataProvider::from_df(&df).unwrap();
+
+        assert_eq!(data.get_close(1), Some(2.2));
+        assert_eq!(data.get_end_tick(), 1);
+    }
+
+    #[test]
+    fn csv_gzip_from_path() {
+        assert!(is_csv_gzip(Path::new("data.csv.gz")));
+        assert!(!is_csv_gzip(Path::new("data.parquet.gz")));
+        assert!(!is_csv_gzip(Path::new("data.csv")));
+    }
+
+    #[test]
+    fn save_and_read_formats() {
+        let dir = TempDir::new("io_formats_test");
+
+        for name in [
+            "data.parquet",
+            "data.csv.gz",
+            "data.arrow",
+            "data.feather",
+            "data.arrows",
+            "data.ndjson",
+            "data.jsonl",
+        ] {
+            let path = dir.join(name);
+            save_df(&mut Fixture::ohlcv_df(2, 0, 60), &path).unwrap();
+            let df = read_df(&path).unwrap();
+
+            assert_eq!(
+                df.get_column_names(),
+                Fixture::ohlcv_df(2, 0, 60).get_column_names(),
+                "{}",
+                name
+            );
+            assert_eq!(
+                df.column("close").unwrap().to_f64().unwrap(),
+                vec![Some(1.2), Some(2.2)],
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn ndjson_column_types() {
+        let dir = TempDir::new("io_ndjson_test");
+        let path = dir.join("indicator.ndjson");
+
+        let mut df = df!(
+            "time" => &[0i64, 60, 120],
+            "rsi" => &[None, Some(f64::NAN), Some(55.5)],
+            "signal" => &[Some("long"), None, Some("short")],
+            "cross" => &[true, false, true]
+        )
+        .unwrap();
+        save_df(&mut df, &path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content.lines().next().unwrap(),
+            r#"{"time":0,"rsi":null,"signal":"long","cross":true}"#
+        );
+
+        let df = read_df_ndjson(&path).unwrap();
+        assert_eq!(df.column("time").unwrap().dtype(), &DataType::Int64);
+        assert_eq!(df.column("rsi").unwrap().dtype(), &DataType::Float64);
+        assert_eq!(df.column("signal").unwrap().dtype(), &DataType::Utf8);
+        assert_eq!(df.column("cross").unwrap().dtype(), &DataType::Boolean);
+        assert_eq!(
+            df.column("rsi").unwrap().to_f64().unwrap(),
+            vec![None, None, Some(55.5)]
+        );
+    }
+
+    #[test]
+    fn ndjson_invalid_line() {
+        let dir = TempDir::new("io_ndjson_invalid_test");
+        let path = dir.join("data.jsonl");
+        std::fs::write(&path, "{\"close\": 1.0}\n\n{close}\n").unwrap();
+
+        let error = read_df(&path).unwrap_err();
+        assert!(matches!(error, PaceError::InvalidData(_)));
+        assert!(error.to_string().contains("line 3"));
+    }
+}
@@ -0,0 +1,5 @@
+mod chunked_data_provider_test;
+mod dataframe_data_provider_test;
+mod io_test;
+mod schema_test;
+mod vendor_test;
@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use polars::prelude::{df, DataFrame, DataType, NamedFrom, Series, TimeUnit};
+
+    use crate::{
+        core::{
+            data_provider::DataProvider, error::PaceError,
+            in_memory_data_provider::InMemoryDataProvider,
+        },
+        polars::schema::{parse_datetime, series_to_time, DataFrameSchema, TimestampUnit},
+    };
+
+    /// 2024-01-29 00:00:00 UTC.
+    const START_TIME: u64 = 1706486400;
+
+    fn with_time(mut df: DataFrame, time: Series) -> DataFrame {
+        df.with_column(time).unwrap();
+        return df;
+    }
+
+    fn prices() -> DataFrame {
+        return df!(
+            "Open" => &[1.0, 2.0],
+            "High" => &[1.5, 2.5],
+            "Low" => &[0.5, 1.5],
+            "Close" => &[1.2, 2.2]
+        )
+        .unwrap();
+    }
+
+    fn expected_time() -> Vec<Option<Duration>> {
+        return vec![
+            Some(Duration::from_secs(START_TIME)),
+            Some(Duration::from_secs(START_TIME + 3600)),
+        ];
+    }
+
+    #[test]
+    fn detect_columns() {
+        let mut df = with_time(
+            prices(),
+            Series::new(
+                "Timestamp",
+                &[START_TIME as i64 * 1000, (START_TIME as i64 + 3600) * 1000],
+            ),
+        );
+        df.with_column(Series::new("Vol", &[10.0, 20.0])).unwrap();
+
+        let schema = DataFrameSchema::detect(&df).unwrap();
+        assert_eq!(schema.open, "Open");
+        assert_eq!(schema.close, "Close");
+        assert_eq!(schema.volume, Some("Vol".to_string()));
+        assert_eq!(schema.time, Some("Timestamp".to_string()));
+
+        let data = InMemoryDataProvider::from_df(&df).unwrap();
+        assert_eq!(data.get_time(1), expected_time()[1]);
+        assert_eq!(data.get_volume(1), Some(20.0));
+    }
+
+    #[test]
+    fn missing_volume_and_time() {
+        let data = InMemoryDataProvider::from_df(&prices()).unwrap();
+        assert_eq!(data.get_volume(0), None);
+        assert_eq!(data.get_time(0), None);
+        assert_eq!(data.get_close(1), Some(2.2));
+    }
+
+    #[test]
+    fn missing_price_column() {
+        let mut df = prices();
+        let _ = df.drop_in_place("Close").unwrap();
+        let error = DataFrameSchema::detect(&df).unwrap_err();
+        assert!(matches!(&error, PaceError::MissingColumn(column) if column == "close"));
+    }
+
+    #[test]
+    fn custom_schema() {
+        let df = with_time(
+            df!(
+                "o" => &[1.0, 2.0],
+                "h" => &[1.5, 2.5],
+                "l" => &[0.5, 1.5],
+                "px" => &[1.2, 2.2]
+            )
+            .unwrap(),
+            Series::new(
+                "bar_time",
+                &[START_TIME * 1_000_000, (START_TIME + 3600) * 1_000_000],
+            ),
+        );
+        let schema = DataFrameSchema {
+            open: "o".to_string(),
+            high: "h".to_string(),
+            low: "l".to_string(),
+            close: "px".to_string(),
+            volume: None,
+            time: Some("bar_time".to_string()),
+            time_unit: Some(TimestampUnit::Microseconds),
+            ..DataFrameSchema::default()
+        };
+
+        let data = InMemoryDataProvider::from_df_with_schema(&df, &schema).unwrap();
+        assert_eq!(data.get_time(0), expected_time()[0]);
+        assert_eq!(data.get_close(0), Some(1.2));
+    }
+
+    #[test]
+    fn timestamp_units() {
+        assert_eq!(TimestampUnit::detect(1.7e9), TimestampUnit::Seconds);
+        assert_eq!(TimestampUnit::detect(1.7e12), TimestampUnit::Milliseconds);
+        assert_eq!(TimestampUnit::detect(1.7e15), TimestampUnit::Microseconds);
+        assert_eq!(TimestampUnit::detect(1.7e18), TimestampUnit::Nanoseconds);
+
+        let nanos = Series::new(
+            "time",
+            &[
+                START_TIME as i64 * 1_000_000_000,
+                (START_TIME as i64 + 3600) * 1_000_000_000,
+            ],
+        );
+        assert_eq!(series_to_time(&nanos, None, None).unwrap(), expected_time());
+    }
+
+    #[test]
+    fn datetime_strings() {
+        let time = Series::new("date", &["2024-01-29T00:00:00Z", "2024-01-29 01:00:00"]);
+        assert_eq!(series_to_time(&time, None, None).unwrap(), expected_time());
+
+        let time = Series::new("date", &["29/01/2024 00:00", "29/01/2024 01:00"]);
+        assert_eq!(
+            series_to_time(&time, None, Some("%d/%m/%Y %H:%M")).unwrap(),
+            expected_time()
+        );
+
+        assert_eq!(
+            parse_datetime("2024-01-29T02:00:00+02:00", None),
+            parse_datetime("2024-01-29", None)
+        );
+
+        let time = Series::new("date", &["2024-01-29", "yesterday"]);
+        let error = series_to_time(&time, None, None).unwrap_err();
+        assert!(matches!(
+            error,
+            PaceError::InvalidValue { index: Some(1), .. }
+        ));
+        assert!(error.to_string().contains("yesterday"));
+    }
+
+    #[test]
+    fn datetime_columns() {
+        let time = Series::new(
+            "time",
+            &[START_TIME as i64 * 1000, (START_TIME as i64 + 3600) * 1000],
+        )
+        .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+        .unwrap();
+        assert_eq!(series_to_time(&time, None, None).unwrap(), expected_time());
+
+        let days = (START_TIME / 86400) as i32;
+        let time = Series::new("date", &[days, days + 1])
+            .cast(&DataType::Date)
+            .unwrap();
+        assert_eq!(
+            series_to_time(&time, None, None).unwrap(),
+            vec![
+                Some(Duration::from_secs(START_TIME)),
+                Some(Duration::from_secs(START_TIME + 86400)),
+            ]
+        );
+    }
+
+    #[test]
+    fn auxiliary_series() {
+        let mut df = prices();
+        df.with_column(Series::new("funding_rate", &[0.01, -0.02]))
+            .unwrap();
+        df.with_column(Series::new("exchange", &["a", "b"]))
+            .unwrap();
+
+        let data = InMemoryDataProvider::from_df(&df).unwrap();
+        assert_eq!(data.get_series_names(), vec!["funding_rate"]);
+        assert_eq!(data.get_series("funding_rate", 1), Some(-0.02));
+
+        let schema = DataFrameSchema {
+            series: Some(vec![]),
+            ..DataFrameSchema::detect(&df).unwrap()
+        };
+        let data = InMemoryDataProvider::from_df_with_schema(&df, &schema).unwrap();
+        assert!(data.get_series_names().is_empty());
+    }
+}
@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::FixedOffset;
+
+    use crate::{
+        core::{data_provider::DataProvider, error::PaceError},
+        polars::vendor::{read_binance_klines, read_metatrader_csv, read_tradingview_csv},
+        testing::temp_dir::TempDir,
+    };
+
+    /// 2024-01-29 00:00:00 UTC.
+    const START_TIME: u64 = 1706486400;
+
+    #[test]
+    fn tradingview_unix_time() {
+        let dir = TempDir::new("vendor_test");
+        let path = dir.write(
+            "tradingview_unix.csv",
+            "time,open,high,low,close,Volume,Plot,Plot,MA\n\
+             1706486400,1,2,0.5,1.5,100,10,20,NaN\n\
+             1706572800,1.5,2.5,1,2,200,11,21,1.75\n",
+        );
+
+        let data = read_tradingview_csv(&path).unwrap();
+        assert_eq!(data.get_time(0), Some(Duration::from_secs(START_TIME)));
+        assert_eq!(data.get_close(1), Some(2.0));
+        assert_eq!(data.get_volume(1), Some(200.0));
+        assert_eq!(data.get_series_names(), vec!["MA", "Plot", "Plot_2"]);
+        assert_eq!(data.get_series("Plot_2", 1), Some(21.0));
+        assert_eq!(data.get_series("MA", 0), None);
+        assert_eq!(data.get_series("MA", 1), Some(1.75));
+    }
+
+    #[test]
+    fn tradingview_iso_time() {
+        let dir = TempDir::new("vendor_test");
+        let path = dir.write(
+            "tradingview_iso.csv",
+            "time,open,high,low,close\n\
+             2024-01-29T01:00:00+01:00,1,2,0.5,1.5\n\
+             2024-01-30T00:00:00Z,1.5,2.5,1,2\n",
+        );
+
+        let data = read_tradingview_csv(&path).unwrap();
+        assert_eq!(data.get_time(0), Some(Duration::from_secs(START_TIME)));
+        assert_eq!(
+            data.get_time(1),
+            Some(Duration::from_secs(START_TIME + 86400))
+        );
+        assert_eq!(data.get_volume(0), None);
+    }
+
+    const BINANCE_ROWS: &str = "\
+        1706486400000,42000.1,42500,41800,42300.5,12.5,1706489999999,528000,1200,6.1,258000,0\n\
+        1706490000000,42300.5,42400,42100,42200,8.25,1706493599999,348000,900,4,169000,0\n";
+
+    #[test]
+    fn binance_csv_without_header() {
+        let dir = TempDir::new("vendor_test");
+        let path = dir.write("binance.csv", BINANCE_ROWS);
+
+        let data = read_binance_klines(&path).unwrap();
+        assert_eq!(data.get_time(0), Some(Duration::from_secs(START_TIME)));
+        assert_eq!(
+            data.get_time(1),
+            Some(Duration::from_secs(START_TIME + 3600))
+        );
+        assert_eq!(data.get_open(0), Some(42000.1));
+        assert_eq!(data.get_volume(1), Some(8.25));
+        assert_eq!(data.get_series("trades", 0), Some(1200.0));
+        assert_eq!(data.get_series("taker_buy_quote_volume", 1), Some(169000.0));
+    }
+
+    #[test]
+    fn binance_csv_with_header_and_microseconds() {
+        let dir = TempDir::new("vendor_test");
+        let path = dir.write(
+            "binance_header.csv",
+            "open_time,open,high,low,close,volume,close_time,quote_volume,count,taker_buy_volume,taker_buy_quote_volume,ignore\n\
+             1706486400000000,1,2,0.5,1.5,10,1706489999999999,15,3,5,7.5,0\n",
+        );
+
+        let data = read_binance_klines(&path).unwrap();
+        assert_eq!(data.get_time(0), Some(Duration::from_secs(START_TIME)));
+        assert_eq!(data.get_series("trades", 0), Some(3.0));
+    }
+
+    #[test]
+    fn binance_json() {
+        let dir = TempDir::new("vendor_test");
+        let path = dir.write(
+            "binance.json",
+            r#"[
+                [1706486400000, "42000.1", "42500", "41800", "42300.5", "12.5", 1706489999999, "528000", 1200, "6.1", "258000", "0"],
+                [1706490000000, "42300.5", "42400", "42100", "42200", "8.25", 1706493599999, "348000", 900, "4", "169000", "0"]
+            ]"#,
+        );
+
+        let data = read_binance_klines(&path).unwrap();
+        assert_eq!(
+            data.get_time(1),
+            Some(Duration::from_secs(START_TIME + 3600))
+        );
+        assert_eq!(data.get_close(0), Some(42300.5));
+        assert_eq!(data.get_series("trades", 1), Some(900.0));
+    }
+
+    #[test]
+    fn binance_json_invalid_value() {
+        let dir = TempDir::new("vendor_test");
+        let path = dir.write(
+            "binance_invalid.json",
+            r#"[[1706486400000, "x", "1", "1", "1", "1", 0, "1", 1, "1", "1", "0"]]"#,
+        );
+
+        let error = read_binance_klines(&path).err().unwrap();
+        assert!(matches!(
+            error,
+            PaceError::InvalidValue { index: Some(0), ref column, .. } if column == "open"
+        ));
+    }
+
+    #[test]
+    fn metatrader5_export() {
+        let dir = TempDir::new("vendor_test");
+        let path = dir.write(
+            "metatrader5.csv",
+            "<DATE>\t<TIME>\t<OPEN>\t<HIGH>\t<LOW>\t<CLOSE>\t<TICKVOL>\t<VOL>\t<SPREAD>\n\
+             2024.01.29\t02:00:00\t1.0850\t1.0870\t1.0840\t1.0860\t1500\t0\t8\n\
+             2024.01.29\t03:00:00\t1.0860\t1.0880\t1.0855\t1.0875\t1200\t0\t9\n",
+        );
+
+        let data = read_metatrader_csv(&path, FixedOffset::east_opt(2 * 3600).unwrap()).unwrap();
+        assert_eq!(data.get_time(0), Some(Duration::from_secs(START_TIME)));
+        assert_eq!(
+            data.get_time(1),
+            Some(Duration::from_secs(START_TIME + 3600))
+        );
+        assert_eq!(data.get_close(1), Some(1.0875));
+        assert_eq!(data.get_volume(0), Some(1500.0));
+        assert_eq!(data.get_series("spread", 1), Some(9.0));
+    }
+
+    #[test]
+    fn metatrader4_history() {
+        let dir = TempDir::new("vendor_test");
+        let path = dir.write(
+            "metatrader4.csv",
+            "2024.01.29,00:00,1.0850,1.0870,1.0840,1.0860,1500\n\
+             2024.01.29,00:01,1.0860,1.0880,1.0855,1.0875,1200\n",
+        );
+
+        let data = read_metatrader_csv(&path, FixedOffset::east_opt(0).unwrap()).unwrap();
+        assert_eq!(data.get_time(1), Some(Duration::from_secs(START_TIME + 60)));
+        assert_eq!(data.get_open(0), Some(1.085));
+        assert_eq!(data.get_series_names(), Vec::<&str>::new());
+    }
+}
@@ -0,0 +1,282 @@
+use std::{
+    io::{BufRead, BufReader},
+    path::Path,
+    time::Duration,
+};
+
+use chrono::FixedOffset;
+use polars::{
+    prelude::{CsvReader, DataFrame, DataType, NamedFrom, SerReader},
+    series::Series,
+};
+use serde_json::Value;
+
+use crate::{
+    core::{
+        error::{PaceError, PaceResult},
+        in_memory_data_provider::InMemoryDataProvider,
+    },
+    utils::fs::get_filename_extension,
+};
+
+use super::{
+    in_memory_data_provider::df_column,
+    io::{open_file, unsupported_file_type},
+    schema::{parse_datetime, DataFrameSchema},
+    series::SeriesCastUtils,
+};
+
+/// First line of a text file, without the line ending.
+fn read_first_line(path: &Path) -> PaceResult<String> {
+    let mut line = String::new();
+    BufReader::new(open_file(path)?)
+        .read_line(&mut line)
+        .map_err(|source| PaceError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    return Ok(line.trim_end_matches(['\r', '\n']).to_string());
+}
+
+/// Reads a CSV file and names its first columns `names`. If `has_header` is `true`, the header line is skipped.
+fn read_csv_with_names(
+    path: &Path,
+    delimiter: u8,
+    has_header: bool,
+    names: &[&str],
+) -> PaceResult<DataFrame> {
+    let mut file = open_file(path)?;
+    let mut df = CsvReader::new(&mut file)
+        .has_header(false)
+        .with_skip_rows(usize::from(has_header))
+        .with_delimiter(delimiter)
+        .finish()?;
+
+    if df.width() < names.len() {
+        return Err(PaceError::InvalidData(format!(
+            "\"{}\" has {} columns, expected {}",
+            path.display(),
+            df.width(),
+            names.len()
+        )));
+    }
+    let mut columns: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+    columns.extend(
+        df.get_column_names()
+            .iter()
+            .skip(names.len())
+            .map(|name| name.to_string()),
+    );
+    df.set_column_names(&columns)?;
+
+    return Ok(df);
+}
+
+/// Suffixes repeated names with `_2`, `_3`...
+fn dedup_names(names: Vec<String>) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::with_capacity(names.len());
+    for name in names {
+        let mut candidate = name.clone();
+        let mut n = 1;
+        while unique.contains(&candidate) {
+            n += 1;
+            candidate = format!("{}_{}", name, n);
+        }
+        unique.push(candidate);
+    }
+    return unique;
+}
+
+/// Reads a CSV exported from a TradingView chart with "Export chart data".
+///
+/// Time is either a unix timestamp or an ISO 8601 datetime with timezone, depending on the export setting.
+/// Plotted indicator columns are loaded as auxiliary series. Repeated names, like several `Plot` columns, are suffixed with `_2`, `_3`...
+pub fn read_tradingview_csv(path: &Path) -> PaceResult<InMemoryDataProvider> {
+    let header = read_first_line(path)?;
+    let names = dedup_names(
+        header
+            .split(',')
+            .map(|name| name.trim().trim_matches('"').to_string())
+            .collect(),
+    );
+    let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+    let df = read_csv_with_names(path, b',', true, &names)?;
+    return InMemoryDataProvider::from_df(&df);
+}
+
+/// Columns of Binance klines, in order.
+pub const BINANCE_KLINE_COLUMNS: [&str; 12] = [
+    "open_time",
+    "open",
+    "high",
+    "low",
+    "close",
+    "volume",
+    "close_time",
+    "quote_volume",
+    "trades",
+    "taker_buy_base_volume",
+    "taker_buy_quote_volume",
+    "ignore",
+];
+
+/// Binance kline columns loaded as auxiliary series.
+pub const BINANCE_KLINE_SERIES: [&str; 4] = [
+    "quote_volume",
+    "trades",
+    "taker_buy_base_volume",
+    "taker_buy_quote_volume",
+];
+
+/// Reads Binance klines from a CSV or JSON file, depending on the extension.
+pub fn read_binance_klines(path: &Path) -> PaceResult<InMemoryDataProvider> {
+    return match get_filename_extension(path) {
+        Some("csv") => read_binance_klines_csv(path),
+        Some("json") => read_binance_klines_json(path),
+        _ => Err(unsupported_file_type(path)),
+    };
+}
+
+/// Reads a kline CSV from Binance public data archives, with or without a header.
+pub fn read_binance_klines_csv(path: &Path) -> PaceResult<InMemoryDataProvider> {
+    let has_header = !read_first_line(path)?
+        .trim_start()
+        .starts_with(|c: char| c.is_ascii_digit());
+    let df = read_csv_with_names(path, b',', has_header, &BINANCE_KLINE_COLUMNS)?;
+    return binance_klines_from_df(&df);
+}
+
+/// Reads a JSON array of klines, as returned by `GET /api/v3/klines`.
+pub fn read_binance_klines_json(path: &Path) -> PaceResult<InMemoryDataProvider> {
+    let rows: Vec<Vec<Value>> = serde_json::from_reader(BufReader::new(open_file(path)?))
+        .map_err(|error| PaceError::InvalidData(format!("{}: {}", path.display(), error)))?;
+
+    let mut columns: Vec<Vec<f64>> = (0..11).map(|_| Vec::with_capacity(rows.len())).collect();
+    for (index, row) in rows.iter().enumerate() {
+        for (i, column) in columns.iter_mut().enumerate() {
+            let value = row.get(i);
+            let parsed = value.and_then(|value| match value {
+                Value::Number(number) => number.as_f64(),
+                Value::String(value) => value.parse::<f64>().ok(),
+                _ => None,
+            });
+            column.push(parsed.ok_or_else(|| PaceError::InvalidValue {
+                column: BINANCE_KLINE_COLUMNS[i].to_string(),
+                index: Some(index),
+                value: value.map(|value| value.to_string()),
+                expected: "number".to_string(),
+            })?);
+        }
+    }
+
+    let df = DataFrame::new(
+        columns
+            .into_iter()
+            .enumerate()
+            .map(|(i, values)| Series::new(BINANCE_KLINE_COLUMNS[i], values))
+            .collect(),
+    )?;
+    return binance_klines_from_df(&df);
+}
+
+/// Creates a data provider from a data frame with `BINANCE_KLINE_COLUMNS`.
+///
+/// Open time is used as bar time, in milliseconds or, for spot data since 2025, microseconds.
+pub fn binance_klines_from_df(df: &DataFrame) -> PaceResult<InMemoryDataProvider> {
+    return InMemoryDataProvider::from_df_with_schema(
+        df,
+        &DataFrameSchema {
+            time: Some("open_time".to_string()),
+            time_unit: None,
+            series: Some(
+                BINANCE_KLINE_SERIES
+                    .iter()
+                    .map(|name| name.to_string())
+                    .collect(),
+            ),
+            ..DataFrameSchema::default()
+        },
+    );
+}
+
+/// Columns of MetaTrader 4 history CSV without a header.
+const METATRADER_COLUMNS: [&str; 7] = ["date", "time", "open", "high", "low", "close", "tickvol"];
+
+/// Reads MetaTrader history, either exported by MetaTrader 5 (tab separated, `<DATE> <TIME> <OPEN>...` header)
+/// or by MetaTrader 4 History Center (comma separated `date,time,open,high,low,close,volume`, no header).
+///
+/// MetaTrader stores time of the broker server, which is converted to UTC with `server_utc_offset`.
+/// Tick volume is used as volume. Real volume and spread are loaded as auxiliary series, if present.
+pub fn read_metatrader_csv(
+    path: &Path,
+    server_utc_offset: FixedOffset,
+) -> PaceResult<InMemoryDataProvider> {
+    let header = read_first_line(path)?;
+    let delimiter = if header.contains('\t') { b'\t' } else { b',' };
+
+    let df = if header.trim_start().starts_with('<') {
+        let names: Vec<String> = header
+            .split(delimiter as char)
+            .map(|name| {
+                name.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_lowercase()
+            })
+            .collect();
+        let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+        read_csv_with_names(path, delimiter, true, &names)?
+    } else {
+        read_csv_with_names(path, delimiter, false, &METATRADER_COLUMNS)?
+    };
+
+    let dates = df_column(&df, "date")?.cast(&DataType::Utf8)?;
+    let dates: Vec<Option<&str>> = dates.utf8()?.into_iter().collect();
+    let times = match df.column("time") {
+        Ok(times) => Some(times.cast(&DataType::Utf8)?),
+        Err(_) => None,
+    };
+    let times: Vec<Option<&str>> = match &times {
+        Some(times) => times.utf8()?.into_iter().collect(),
+        None => vec![None; dates.len()],
+    };
+    let offset = chrono::Duration::seconds(server_utc_offset.local_minus_utc() as i64);
+
+    let time = dates
+        .into_iter()
+        .zip(times)
+        .enumerate()
+        .map(|(index, (date, time))| {
+            let value = match (date, time) {
+                (Some(date), Some(time)) => format!("{} {}", date, time),
+                (Some(date), None) => date.to_string(),
+                (None, _) => return Ok(None),
+            };
+            return parse_datetime(&value, None)
+                .and_then(|datetime| u64::try_from((datetime - offset).timestamp_millis()).ok())
+                .map(|millis| Some(Duration::from_millis(millis)))
+                .ok_or_else(|| PaceError::InvalidValue {
+                    column: "date".to_string(),
+                    index: Some(index),
+                    value: Some(value),
+                    expected: "MetaTrader datetime".to_string(),
+                });
+        })
+        .collect::<PaceResult<Vec<Option<Duration>>>>()?;
+
+    let mut data = InMemoryDataProvider::new(
+        df_column(&df, "open")?.to_f64()?,
+        df_column(&df, "high")?.to_f64()?,
+        df_column(&df, "low")?.to_f64()?,
+        df_column(&df, "close")?.to_f64()?,
+        df_column(&df, "tickvol")?.to_f64()?,
+        time,
+    );
+    for name in ["vol", "spread"] {
+        if let Ok(series) = df.column(name) {
+            data.add_series(name, series.to_f64()?)?;
+        }
+    }
+
+    return Ok(data);
+}
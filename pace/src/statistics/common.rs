@@ -20,3 +20,111 @@ pub fn stdev(values: &[f64]) -> f64 {
 pub fn stdev_from_var(var: f64) -> f64 {
     return var.sqrt();
 }
+
+/// Sample skewness (third standardized moment).
+pub fn skewness(values: &[f64]) -> f64 {
+    let mean = mean(values);
+    let stdev = stdev_from_var(var_from_mean(values, mean));
+    if stdev == 0.0 {
+        return 0.0;
+    }
+    let n = values.len() as f64;
+    return values
+        .iter()
+        .map(|x| ((x - mean) / stdev).powi(3))
+        .sum::<f64>()
+        / n;
+}
+
+/// Sample kurtosis (fourth standardized moment). Normal distribution has kurtosis of `3.0`.
+pub fn kurtosis(values: &[f64]) -> f64 {
+    let mean = mean(values);
+    let stdev = stdev_from_var(var_from_mean(values, mean));
+    if stdev == 0.0 {
+        return 3.0;
+    }
+    let n = values.len() as f64;
+    return values
+        .iter()
+        .map(|x| ((x - mean) / stdev).powi(4))
+        .sum::<f64>()
+        / n;
+}
+
+/// Cumulative distribution function of the standard normal distribution.
+pub fn normal_cdf(x: f64) -> f64 {
+    // Abramowitz & Stegun 7.1.26 approximation of `erf`.
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * z);
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+
+    if x >= 0.0 {
+        return 0.5 * (1.0 + erf);
+    }
+    return 0.5 * (1.0 - erf);
+}
+
+/// Inverse of the standard normal cumulative distribution function (quantile function).
+///
+/// Uses Acklam's rational approximation. Returns infinity for `p` outside of `(0, 1)`.
+pub fn normal_inv_cdf(p: f64) -> f64 {
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    let a = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.38357751867269e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    let b = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    let c = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    let d = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+
+    let p_low = 0.02425;
+    let p_high = 1.0 - p_low;
+
+    if p < p_low {
+        let q = (-2.0 * p.ln()).sqrt();
+        return (((((c[0] * q + c[1]) * q + c[2]) * q + c[3]) * q + c[4]) * q + c[5])
+            / ((((d[0] * q + d[1]) * q + d[2]) * q + d[3]) * q + 1.0);
+    }
+
+    if p > p_high {
+        let q = (-2.0 * (1.0 - p).ln()).sqrt();
+        return -(((((c[0] * q + c[1]) * q + c[2]) * q + c[3]) * q + c[4]) * q + c[5])
+            / ((((d[0] * q + d[1]) * q + d[2]) * q + d[3]) * q + 1.0);
+    }
+
+    let q = p - 0.5;
+    let r = q * q;
+    return (((((a[0] * r + a[1]) * r + a[2]) * r + a[3]) * r + a[4]) * r + a[5]) * q
+        / (((((b[0] * r + b[1]) * r + b[2]) * r + b[3]) * r + b[4]) * r + 1.0);
+}
@@ -0,0 +1,614 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use polars::prelude::DataFrame;
+use rusqlite::{params, Connection, OptionalExtension, Row, Transaction};
+
+use crate::{
+    core::{
+        data_provider::DataProvider,
+        error::{PaceError, PaceResult},
+        in_memory_data_provider::InMemoryDataProvider,
+    },
+    polars::io::read_df,
+    strategy::trade::{Trade, TradeDirection, TradeFill},
+};
+
+/// Version of `SCHEMA`, kept in `PRAGMA user_version`. Increased on every incompatible change.
+pub const SCHEMA_VERSION: i64 = 1;
+
+/// How long a statement waits for another connection to release the database.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+const SCHEMA: &str = "
+PRAGMA foreign_keys = ON;
+
+CREATE TABLE IF NOT EXISTS bars (
+    symbol TEXT NOT NULL,
+    timeframe TEXT NOT NULL,
+    time INTEGER NOT NULL,
+    open REAL,
+    high REAL,
+    low REAL,
+    close REAL,
+    volume REAL,
+    PRIMARY KEY (symbol, timeframe, time)
+) WITHOUT ROWID;
+
+CREATE TABLE IF NOT EXISTS bar_series (
+    symbol TEXT NOT NULL,
+    timeframe TEXT NOT NULL,
+    name TEXT NOT NULL,
+    time INTEGER NOT NULL,
+    value REAL,
+    PRIMARY KEY (symbol, timeframe, name, time)
+) WITHOUT ROWID;
+
+CREATE TABLE IF NOT EXISTS backtests (
+    id INTEGER PRIMARY KEY,
+    symbol TEXT NOT NULL,
+    timeframe TEXT NOT NULL,
+    strategy TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS backtest_params (
+    backtest_id INTEGER NOT NULL REFERENCES backtests (id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (backtest_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS backtest_metrics (
+    backtest_id INTEGER NOT NULL REFERENCES backtests (id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    value REAL,
+    PRIMARY KEY (backtest_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS backtest_trades (
+    backtest_id INTEGER NOT NULL REFERENCES backtests (id) ON DELETE CASCADE,
+    trade_index INTEGER NOT NULL,
+    direction TEXT NOT NULL,
+    is_closed INTEGER NOT NULL,
+    entry_tick INTEGER,
+    entry_price REAL,
+    exit_tick INTEGER,
+    exit_price REAL,
+    fill_size REAL,
+    pnl REAL,
+    is_liquidated INTEGER NOT NULL,
+    funding REAL,
+    commission REAL,
+    point_value REAL,
+    max_drawdown REAL,
+    max_runup REAL,
+    entry_id TEXT NOT NULL,
+    entry_comment TEXT,
+    exit_id TEXT,
+    exit_comment TEXT,
+    unfilled_size REAL,
+    PRIMARY KEY (backtest_id, trade_index)
+);
+
+CREATE TABLE IF NOT EXISTS backtest_fills (
+    backtest_id INTEGER NOT NULL REFERENCES backtests (id) ON DELETE CASCADE,
+    trade_index INTEGER NOT NULL,
+    fill_index INTEGER NOT NULL,
+    tick INTEGER NOT NULL,
+    price REAL,
+    size REAL,
+    PRIMARY KEY (backtest_id, trade_index, fill_index)
+);
+";
+
+/// Bars of a single symbol and timeframe in `DataStore`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataStoreEntry {
+    pub symbol: String,
+    pub timeframe: String,
+    pub bars: usize,
+    pub start_time: Duration,
+    pub end_time: Duration,
+}
+
+/// Backtest result persisted in `DataStore`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestRecord {
+    /// Set by `DataStore.save_backtest`.
+    pub id: Option<i64>,
+    pub symbol: String,
+    pub timeframe: String,
+    /// Name of the strategy.
+    pub strategy: String,
+    /// Time since epoch the record was saved. Set by `DataStore.save_backtest`.
+    pub created_at: Option<Duration>,
+    /// Strategy parameters, e.g. `"length" => "14"`.
+    pub params: BTreeMap<String, String>,
+    /// Metrics by name, e.g. from `TradingViewMetricsData.to_map`. `NaN` is stored as `NULL` and loaded back as `NaN`.
+    pub metrics: BTreeMap<String, f64>,
+    pub trades: Vec<Trade>,
+}
+
+impl Default for BacktestRecord {
+    fn default() -> Self {
+        return Self {
+            id: None,
+            symbol: String::new(),
+            timeframe: String::new(),
+            strategy: String::new(),
+            created_at: None,
+            params: BTreeMap::new(),
+            metrics: BTreeMap::new(),
+            trades: Vec::new(),
+        };
+    }
+}
+
+/// Time is stored as nanoseconds since epoch.
+fn time_to_sql(time: Duration, index: usize) -> PaceResult<i64> {
+    return i64::try_from(time.as_nanos()).map_err(|_| PaceError::InvalidValue {
+        column: "time".to_string(),
+        index: Some(index),
+        value: Some(format!("{:?}", time)),
+        expected: "time before year 2262".to_string(),
+    });
+}
+
+fn time_from_sql(time: i64) -> Duration {
+    return Duration::from_nanos(time.max(0) as u64);
+}
+
+/// Inclusive lower and exclusive upper bound of a time range.
+fn time_bounds(start_time: Option<Duration>, end_time: Option<Duration>) -> (i64, i64) {
+    let bound = |time: Duration| i64::try_from(time.as_nanos()).unwrap_or(i64::MAX);
+    return (
+        start_time.map_or(i64::MIN, bound),
+        end_time.map_or(i64::MAX, bound),
+    );
+}
+
+/// `NULL` is loaded as `NaN`, same as it was stored by SQLite.
+fn get_f64(row: &Row, index: usize) -> rusqlite::Result<f64> {
+    return Ok(row.get::<_, Option<f64>>(index)?.unwrap_or(f64::NAN));
+}
+
+fn direction_from_sql(direction: &str) -> PaceResult<TradeDirection> {
+    return match direction {
+        "Long" => Ok(TradeDirection::Long),
+        "Short" => Ok(TradeDirection::Short),
+        _ => Err(PaceError::InvalidValue {
+            column: "direction".to_string(),
+            index: None,
+            value: Some(direction.to_string()),
+            expected: "Long or Short".to_string(),
+        }),
+    };
+}
+
+/// Local database of bars keyed by symbol and timeframe, and of backtest results, in a single SQLite file.
+///
+/// Timeframe is any label, e.g. `"1h"` or `"1D"`. Bars are keyed by time, so ingesting the same bars again replaces them.
+pub struct DataStore {
+    connection: Connection,
+}
+
+impl DataStore {
+    /// Opens or creates a database file.
+    pub fn open(path: &Path) -> PaceResult<Self> {
+        return Self::from_connection(Connection::open(path)?);
+    }
+
+    /// Creates a temporary database, dropped with the store.
+    pub fn open_in_memory() -> PaceResult<Self> {
+        return Self::from_connection(Connection::open_in_memory()?);
+    }
+
+    /// Returns an error if the database was created with a different `SCHEMA_VERSION`.
+    fn from_connection(connection: Connection) -> PaceResult<Self> {
+        connection.busy_timeout(BUSY_TIMEOUT)?;
+
+        let version: i64 = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version != 0 && version != SCHEMA_VERSION {
+            return Err(PaceError::InvalidData(format!(
+                "Data store schema version {} is not supported, expected {}",
+                version, SCHEMA_VERSION
+            )));
+        }
+
+        connection.execute_batch(SCHEMA)?;
+        connection.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        return Ok(Self { connection });
+    }
+
+    /// Writes all bars and auxiliary series of `data`. Every bar must have time. Returns the number of written bars.
+    pub fn ingest_data(
+        &mut self,
+        symbol: &str,
+        timeframe: &str,
+        data: &dyn DataProvider,
+    ) -> PaceResult<usize> {
+        let mut times: Vec<i64> = Vec::new();
+        for index in data.get_start_tick()..=data.get_end_tick() {
+            let time = data
+                .get_time(index)
+                .ok_or_else(|| PaceError::InvalidValue {
+                    column: "time".to_string(),
+                    index: Some(index),
+                    value: None,
+                    expected: "time of every bar".to_string(),
+                })?;
+            times.push(time_to_sql(time, index)?);
+        }
+
+        let transaction = self.connection.transaction()?;
+        {
+            let mut insert_bar = transaction.prepare(
+                "INSERT OR REPLACE INTO bars (symbol, timeframe, time, open, high, low, close, volume)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+            let mut insert_series = transaction.prepare(
+                "INSERT OR REPLACE INTO bar_series (symbol, timeframe, name, time, value)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            let series_names = data.get_series_names();
+
+            for (time, index) in times.iter().zip(data.get_start_tick()..) {
+                insert_bar.execute(params![
+                    symbol,
+                    timeframe,
+                    time,
+                    data.get_open(index),
+                    data.get_high(index),
+                    data.get_low(index),
+                    data.get_close(index),
+                    data.get_volume(index),
+                ])?;
+                for name in &series_names {
+                    insert_series.execute(params![
+                        symbol,
+                        timeframe,
+                        name,
+                        time,
+                        data.get_series(name, index),
+                    ])?;
+                }
+            }
+        }
+        transaction.commit()?;
+
+        return Ok(times.len());
+    }
+
+    /// Writes bars of a data frame with columns detected by `DataFrameSchema::detect`.
+    pub fn ingest_df(
+        &mut self,
+        symbol: &str,
+        timeframe: &str,
+        df: &DataFrame,
+    ) -> PaceResult<usize> {
+        if df.height() == 0 {
+            return Ok(0);
+        }
+        return self.ingest_data(symbol, timeframe, &InMemoryDataProvider::from_df(df)?);
+    }
+
+    /// Writes bars of a file read with `read_df`.
+    pub fn ingest_file(&mut self, symbol: &str, timeframe: &str, path: &Path) -> PaceResult<usize> {
+        return self.ingest_df(symbol, timeframe, &read_df(path)?);
+    }
+
+    /// Loads bars with time in `[start_time, end_time)`, sorted by time. `None` is an open bound.
+    ///
+    /// Returns an error if there are no bars in the range.
+    pub fn load(
+        &self,
+        symbol: &str,
+        timeframe: &str,
+        start_time: Option<Duration>,
+        end_time: Option<Duration>,
+    ) -> PaceResult<InMemoryDataProvider> {
+        let (start, end) = time_bounds(start_time, end_time);
+
+        let mut open: Vec<Option<f64>> = Vec::new();
+        let mut high: Vec<Option<f64>> = Vec::new();
+        let mut low: Vec<Option<f64>> = Vec::new();
+        let mut close: Vec<Option<f64>> = Vec::new();
+        let mut volume: Vec<Option<f64>> = Vec::new();
+        let mut time: Vec<Option<Duration>> = Vec::new();
+        let mut indices: HashMap<i64, usize> = HashMap::new();
+
+        let mut statement = self.connection.prepare(
+            "SELECT time, open, high, low, close, volume FROM bars
+             WHERE symbol = ?1 AND timeframe = ?2 AND time >= ?3 AND time < ?4
+             ORDER BY time",
+        )?;
+        let mut rows = statement.query(params![symbol, timeframe, start, end])?;
+        while let Some(row) = rows.next()? {
+            let bar_time: i64 = row.get(0)?;
+            indices.insert(bar_time, time.len());
+            time.push(Some(time_from_sql(bar_time)));
+            open.push(row.get(1)?);
+            high.push(row.get(2)?);
+            low.push(row.get(3)?);
+            close.push(row.get(4)?);
+            volume.push(row.get(5)?);
+        }
+
+        if time.is_empty() {
+            return Err(PaceError::InvalidData(format!(
+                "No bars of \"{}\" {} in the time range",
+                symbol, timeframe
+            )));
+        }
+
+        let bars = time.len();
+        let mut series: BTreeMap<String, Vec<Option<f64>>> = BTreeMap::new();
+        let mut statement = self.connection.prepare(
+            "SELECT name, time, value FROM bar_series
+             WHERE symbol = ?1 AND timeframe = ?2 AND time >= ?3 AND time < ?4",
+        )?;
+        let mut rows = statement.query(params![symbol, timeframe, start, end])?;
+        while let Some(row) = rows.next()? {
+            let name: String = row.get(0)?;
+            if let Some(index) = indices.get(&row.get::<_, i64>(1)?) {
+                series.entry(name).or_insert_with(|| vec![None; bars])[*index] = row.get(2)?;
+            }
+        }
+
+        let mut data = InMemoryDataProvider::new(open, high, low, close, volume, time);
+        for (name, values) in series {
+            data.add_series(&name, values)?;
+        }
+        return Ok(data);
+    }
+
+    /// Stored symbols and timeframes, sorted by symbol and timeframe.
+    pub fn entries(&self) -> PaceResult<Vec<DataStoreEntry>> {
+        let mut statement = self.connection.prepare(
+            "SELECT symbol, timeframe, COUNT(*), MIN(time), MAX(time) FROM bars
+             GROUP BY symbol, timeframe ORDER BY symbol, timeframe",
+        )?;
+        let entries = statement
+            .query_map([], |row| {
+                return Ok(DataStoreEntry {
+                    symbol: row.get(0)?,
+                    timeframe: row.get(1)?,
+                    bars: row.get::<_, i64>(2)? as usize,
+                    start_time: time_from_sql(row.get(3)?),
+                    end_time: time_from_sql(row.get(4)?),
+                });
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        return Ok(entries);
+    }
+
+    /// Deletes bars and auxiliary series of a symbol and timeframe. Returns the number of deleted bars.
+    pub fn delete(&mut self, symbol: &str, timeframe: &str) -> PaceResult<usize> {
+        let transaction = self.connection.transaction()?;
+        transaction.execute(
+            "DELETE FROM bar_series WHERE symbol = ?1 AND timeframe = ?2",
+            params![symbol, timeframe],
+        )?;
+        let bars = transaction.execute(
+            "DELETE FROM bars WHERE symbol = ?1 AND timeframe = ?2",
+            params![symbol, timeframe],
+        )?;
+        transaction.commit()?;
+        return Ok(bars);
+    }
+
+    /// Saves a new backtest record, ignoring `record.id` and `record.created_at`. Returns its id.
+    pub fn save_backtest(&mut self, record: &BacktestRecord) -> PaceResult<i64> {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let transaction = self.connection.transaction()?;
+        transaction.execute(
+            "INSERT INTO backtests (symbol, timeframe, strategy, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                record.symbol,
+                record.timeframe,
+                record.strategy,
+                time_to_sql(created_at, 0)?
+            ],
+        )?;
+        let id = transaction.last_insert_rowid();
+        Self::save_backtest_details(&transaction, id, record)?;
+        transaction.commit()?;
+
+        return Ok(id);
+    }
+
+    fn save_backtest_details(
+        transaction: &Transaction,
+        id: i64,
+        record: &BacktestRecord,
+    ) -> PaceResult<()> {
+        let mut insert_param = transaction.prepare(
+            "INSERT INTO backtest_params (backtest_id, name, value) VALUES (?1, ?2, ?3)",
+        )?;
+        for (name, value) in &record.params {
+            insert_param.execute(params![id, name, value])?;
+        }
+
+        let mut insert_metric = transaction.prepare(
+            "INSERT INTO backtest_metrics (backtest_id, name, value) VALUES (?1, ?2, ?3)",
+        )?;
+        for (name, value) in &record.metrics {
+            insert_metric.execute(params![id, name, value])?;
+        }
+
+        let mut insert_trade = transaction.prepare(
+            "INSERT INTO backtest_trades (
+                backtest_id, trade_index, direction, is_closed, entry_tick, entry_price, exit_tick,
+                exit_price, fill_size, pnl, is_liquidated, funding, commission, point_value,
+                max_drawdown, max_runup, entry_id, entry_comment, exit_id, exit_comment, unfilled_size
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21)",
+        )?;
+        let mut insert_fill = transaction.prepare(
+            "INSERT INTO backtest_fills (backtest_id, trade_index, fill_index, tick, price, size)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        for (trade_index, trade) in record.trades.iter().enumerate() {
+            insert_trade.execute(params![
+                id,
+                trade_index as i64,
+                trade.direction.to_string(),
+                trade.is_closed,
+                trade.entry_tick.map(|tick| tick as i64),
+                trade.entry_price,
+                trade.exit_tick.map(|tick| tick as i64),
+                trade.exit_price,
+                trade.fill_size,
+                trade.pnl,
+                trade.is_liquidated,
+                trade.funding,
+                trade.commission,
+                trade.point_value,
+                trade.max_drawdown,
+                trade.max_runup,
+                trade.entry_id,
+                trade.entry_comment,
+                trade.exit_id,
+                trade.exit_comment,
+                trade.unfilled_size,
+            ])?;
+            for (fill_index, fill) in trade.fills.iter().enumerate() {
+                insert_fill.execute(params![
+                    id,
+                    trade_index as i64,
+                    fill_index as i64,
+                    fill.tick as i64,
+                    fill.price,
+                    fill.size,
+                ])?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Loads a backtest record, or `None` if it doesn't exist.
+    pub fn backtest(&self, id: i64) -> PaceResult<Option<BacktestRecord>> {
+        let record = self
+            .connection
+            .query_row(
+                "SELECT id, symbol, timeframe, strategy, created_at FROM backtests WHERE id = ?1",
+                params![id],
+                |row| {
+                    return Ok(BacktestRecord {
+                        id: Some(row.get(0)?),
+                        symbol: row.get(1)?,
+                        timeframe: row.get(2)?,
+                        strategy: row.get(3)?,
+                        created_at: Some(time_from_sql(row.get(4)?)),
+                        ..BacktestRecord::default()
+                    });
+                },
+            )
+            .optional()?;
+
+        return match record {
+            Some(record) => Ok(Some(self.load_backtest_details(record)?)),
+            None => Ok(None),
+        };
+    }
+
+    /// Loads backtest records of a symbol and timeframe, from the oldest one.
+    pub fn backtests(&self, symbol: &str, timeframe: &str) -> PaceResult<Vec<BacktestRecord>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT id FROM backtests WHERE symbol = ?1 AND timeframe = ?2 ORDER BY id")?;
+        let ids = statement
+            .query_map(params![symbol, timeframe], |row| row.get::<_, i64>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut records: Vec<BacktestRecord> = Vec::with_capacity(ids.len());
+        for id in ids {
+            records.extend(self.backtest(id)?);
+        }
+        return Ok(records);
+    }
+
+    /// Deletes a backtest record. Returns `false` if it doesn't exist.
+    pub fn delete_backtest(&mut self, id: i64) -> PaceResult<bool> {
+        return Ok(self
+            .connection
+            .execute("DELETE FROM backtests WHERE id = ?1", params![id])?
+            > 0);
+    }
+
+    fn load_backtest_details(&self, mut record: BacktestRecord) -> PaceResult<BacktestRecord> {
+        let id = record.id;
+
+        let mut statement = self
+            .connection
+            .prepare("SELECT name, value FROM backtest_params WHERE backtest_id = ?1")?;
+        record.params = statement
+            .query_map(params![id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let mut statement = self
+            .connection
+            .prepare("SELECT name, value FROM backtest_metrics WHERE backtest_id = ?1")?;
+        record.metrics = statement
+            .query_map(params![id], |row| Ok((row.get(0)?, get_f64(row, 1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let mut statement = self.connection.prepare(
+            "SELECT direction, is_closed, entry_tick, entry_price, exit_tick, exit_price, fill_size,
+                pnl, is_liquidated, funding, commission, point_value, max_drawdown, max_runup,
+                entry_id, entry_comment, exit_id, exit_comment, unfilled_size
+             FROM backtest_trades WHERE backtest_id = ?1 ORDER BY trade_index",
+        )?;
+        let mut rows = statement.query(params![id])?;
+        while let Some(row) = rows.next()? {
+            let direction: String = row.get(0)?;
+            record.trades.push(Trade {
+                direction: direction_from_sql(&direction)?,
+                is_closed: row.get(1)?,
+                entry_tick: row.get::<_, Option<i64>>(2)?.map(|tick| tick as usize),
+                entry_price: row.get(3)?,
+                exit_tick: row.get::<_, Option<i64>>(4)?.map(|tick| tick as usize),
+                exit_price: row.get(5)?,
+                fill_size: row.get(6)?,
+                pnl: get_f64(row, 7)?,
+                is_liquidated: row.get(8)?,
+                funding: get_f64(row, 9)?,
+                commission: get_f64(row, 10)?,
+                point_value: get_f64(row, 11)?,
+                max_drawdown: get_f64(row, 12)?,
+                max_runup: get_f64(row, 13)?,
+                entry_id: row.get(14)?,
+                entry_comment: row.get(15)?,
+                exit_id: row.get(16)?,
+                exit_comment: row.get(17)?,
+                fills: Vec::new(),
+                unfilled_size: get_f64(row, 18)?,
+            });
+        }
+
+        let mut statement = self.connection.prepare(
+            "SELECT trade_index, tick, price, size FROM backtest_fills
+             WHERE backtest_id = ?1 ORDER BY trade_index, fill_index",
+        )?;
+        let mut rows = statement.query(params![id])?;
+        while let Some(row) = rows.next()? {
+            let trade_index = row.get::<_, i64>(0)? as usize;
+            if let Some(trade) = record.trades.get_mut(trade_index) {
+                trade.fills.push(TradeFill {
+                    tick: row.get::<_, i64>(1)? as usize,
+                    price: get_f64(row, 2)?,
+                    size: get_f64(row, 3)?,
+                });
+            }
+        }
+
+        return Ok(record);
+    }
+}
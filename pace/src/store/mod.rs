@@ -0,0 +1,3 @@
+pub mod data_store;
+
+mod tests;
@@ -0,0 +1,300 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, time::Duration};
+
+    use polars::prelude::{df, DataFrame, NamedFrom, Series};
+
+    use crate::{
+        core::{
+            data_provider::DataProvider, error::PaceError,
+            in_memory_data_provider::InMemoryDataProvider,
+        },
+        polars::io::save_df,
+        store::data_store::{BacktestRecord, DataStore, DataStoreEntry, SCHEMA_VERSION},
+        strategy::trade::{Trade, TradeDirection, TradeFill},
+        testing::{fixture::Fixture, temp_dir::TempDir},
+    };
+
+    /// 2024-01-29 00:00:00 UTC.
+    const START_TIME: u64 = 1706486400;
+
+    /// Hourly bars with a missing volume and a missing series value.
+    fn ohlcv_df() -> DataFrame {
+        let mut df = Fixture::ohlcv_df(5, START_TIME, 3600);
+        for series in [
+            Series::new(
+                "volume",
+                &[Some(10.0), None, Some(30.0), Some(40.0), Some(50.0)],
+            ),
+            Series::new(
+                "funding_rate",
+                &[Some(0.01), Some(0.02), None, Some(0.04), Some(0.05)],
+            ),
+        ] {
+            df.with_column(series).unwrap();
+        }
+        return df;
+    }
+
+    fn hour(index: u64) -> Duration {
+        return Duration::from_secs(START_TIME + index * 3600);
+    }
+
+    #[test]
+    fn ingest_and_load() {
+        let mut store = DataStore::open_in_memory().unwrap();
+        assert_eq!(store.ingest_df("BTCUSD", "1h", &ohlcv_df()).unwrap(), 5);
+
+        let expected = InMemoryDataProvider::from_df(&ohlcv_df()).unwrap();
+        let data = store.load("BTCUSD", "1h", None, None).unwrap();
+
+        assert_eq!(data.get_end_tick(), 4);
+        assert_eq!(data.get_series_names(), vec!["funding_rate"]);
+        for i in 0..5 {
+            assert_eq!(data.get_open(i), expected.get_open(i));
+            assert_eq!(data.get_high(i), expected.get_high(i));
+            assert_eq!(data.get_low(i), expected.get_low(i));
+            assert_eq!(data.get_close(i), expected.get_close(i));
+            assert_eq!(data.get_volume(i), expected.get_volume(i));
+            assert_eq!(data.get_time(i), expected.get_time(i));
+            assert_eq!(
+                data.get_series("funding_rate", i),
+                expected.get_series("funding_rate", i)
+            );
+        }
+    }
+
+    #[test]
+    fn load_time_range() {
+        let mut store = DataStore::open_in_memory().unwrap();
+        store.ingest_df("BTCUSD", "1h", &ohlcv_df()).unwrap();
+
+        let data = store
+            .load("BTCUSD", "1h", Some(hour(1)), Some(hour(3)))
+            .unwrap();
+        assert_eq!(data.get_end_tick(), 1);
+        assert_eq!(data.get_time(0), Some(hour(1)));
+        assert_eq!(data.get_close(1), Some(3.2));
+        assert_eq!(data.get_series("funding_rate", 0), Some(0.02));
+        assert_eq!(data.get_series("funding_rate", 1), None);
+
+        let data = store.load("BTCUSD", "1h", Some(hour(3)), None).unwrap();
+        assert_eq!(data.get_time(0), Some(hour(3)));
+        assert_eq!(data.get_end_tick(), 1);
+
+        let error = store
+            .load("BTCUSD", "1h", Some(hour(10)), None)
+            .err()
+            .unwrap();
+        assert!(matches!(error, PaceError::InvalidData(_)));
+        let error = store.load("ETHUSD", "1h", None, None).err().unwrap();
+        assert!(matches!(error, PaceError::InvalidData(_)));
+    }
+
+    #[test]
+    fn ingest_replaces_bars() {
+        let mut store = DataStore::open_in_memory().unwrap();
+        store.ingest_df("BTCUSD", "1h", &ohlcv_df()).unwrap();
+        store
+            .ingest_df("BTCUSD", "1d", &ohlcv_df().head(Some(1)))
+            .unwrap();
+
+        // Replaces the last 2 bars and appends 1.
+        let update = df!(
+            "time" => (3..6).map(|i| START_TIME + i * 3600).collect::<Vec<u64>>(),
+            "open" => &[4.0, 5.0, 6.0],
+            "high" => &[4.5, 5.5, 6.5],
+            "low" => &[3.5, 4.5, 5.5],
+            "close" => &[40.0, 50.0, 6.2],
+            "volume" => &[40.0, 50.0, 60.0],
+            "funding_rate" => &[0.04, 0.05, 0.06]
+        )
+        .unwrap();
+        assert_eq!(store.ingest_df("BTCUSD", "1h", &update).unwrap(), 3);
+
+        let data = store.load("BTCUSD", "1h", None, None).unwrap();
+        assert_eq!(data.get_end_tick(), 5);
+        assert_eq!(data.get_close(2), Some(3.2));
+        assert_eq!(data.get_close(3), Some(40.0));
+        assert_eq!(data.get_close(5), Some(6.2));
+
+        assert_eq!(
+            store.entries().unwrap(),
+            vec![
+                DataStoreEntry {
+                    symbol: "BTCUSD".to_string(),
+                    timeframe: "1d".to_string(),
+                    bars: 1,
+                    start_time: hour(0),
+                    end_time: hour(0),
+                },
+                DataStoreEntry {
+                    symbol: "BTCUSD".to_string(),
+                    timeframe: "1h".to_string(),
+                    bars: 6,
+                    start_time: hour(0),
+                    end_time: hour(5),
+                },
+            ]
+        );
+
+        assert_eq!(store.delete("BTCUSD", "1h").unwrap(), 6);
+        assert_eq!(store.entries().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn ingest_file_and_reopen() {
+        let dir = TempDir::new("data_store_test");
+        let csv_path = dir.join("bars.csv");
+        save_df(&mut ohlcv_df(), &csv_path).unwrap();
+        let db_path = dir.join("store.db");
+
+        {
+            let mut store = DataStore::open(&db_path).unwrap();
+            assert_eq!(store.ingest_file("BTCUSD", "1h", &csv_path).unwrap(), 5);
+        }
+
+        let store = DataStore::open(&db_path).unwrap();
+        let data = store.load("BTCUSD", "1h", None, None).unwrap();
+        assert_eq!(data.get_end_tick(), 4);
+        assert_eq!(data.get_time(4), Some(hour(4)));
+        assert_eq!(data.get_series("funding_rate", 4), Some(0.05));
+    }
+
+    #[test]
+    fn schema_version() {
+        let dir = TempDir::new("data_store_test");
+        let path = dir.join("version.db");
+        DataStore::open(&path).unwrap();
+
+        let connection = rusqlite::Connection::open(&path).unwrap();
+        let version: i64 = connection
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+        connection
+            .pragma_update(None, "user_version", SCHEMA_VERSION + 1)
+            .unwrap();
+        drop(connection);
+
+        let error = DataStore::open(&path).err().unwrap();
+        assert!(matches!(error, PaceError::InvalidData(_)));
+    }
+
+    #[test]
+    fn ingest_missing_time() {
+        let mut store = DataStore::open_in_memory().unwrap();
+        let df = ohlcv_df().drop("time").unwrap();
+
+        let error = store.ingest_df("BTCUSD", "1h", &df).err().unwrap();
+        assert!(matches!(
+            error,
+            PaceError::InvalidValue { index: Some(0), ref column, .. } if column == "time"
+        ));
+        assert!(store.entries().unwrap().is_empty());
+    }
+
+    fn backtest_record() -> BacktestRecord {
+        let mut long = Trade::new(TradeDirection::Long);
+        long.is_closed = true;
+        long.entry_tick = Some(1);
+        long.entry_price = Some(2.0);
+        long.exit_tick = Some(3);
+        long.exit_price = Some(4.0);
+        long.fill_size = Some(1.5);
+        long.pnl = 3.0;
+        long.commission = 0.1;
+        long.exit_id = Some("exit".to_string());
+        long.fills = vec![
+            TradeFill {
+                tick: 1,
+                price: 2.0,
+                size: 1.0,
+            },
+            TradeFill {
+                tick: 2,
+                price: 2.5,
+                size: 0.5,
+            },
+        ];
+        let mut short = Trade::new(TradeDirection::Short);
+        short.entry_tick = Some(4);
+        short.entry_price = Some(5.0);
+        short.entry_comment = Some("reversal".to_string());
+
+        return BacktestRecord {
+            symbol: "BTCUSD".to_string(),
+            timeframe: "1h".to_string(),
+            strategy: "ma_cross".to_string(),
+            params: BTreeMap::from([
+                ("fast".to_string(), "9".to_string()),
+                ("slow".to_string(), "21".to_string()),
+            ]),
+            metrics: BTreeMap::from([
+                ("net_profit".to_string(), 3.0),
+                ("sharpe_ratio".to_string(), 1.25),
+            ]),
+            trades: vec![long, short],
+            ..BacktestRecord::default()
+        };
+    }
+
+    #[test]
+    fn save_and_load_backtests() {
+        let mut store = DataStore::open_in_memory().unwrap();
+        let record = backtest_record();
+
+        let first = store.save_backtest(&record).unwrap();
+        let second = store
+            .save_backtest(&BacktestRecord {
+                strategy: "rsi".to_string(),
+                ..BacktestRecord::default()
+            })
+            .unwrap();
+        store
+            .save_backtest(&BacktestRecord {
+                symbol: "BTCUSD".to_string(),
+                timeframe: "1h".to_string(),
+                strategy: "rsi".to_string(),
+                ..BacktestRecord::default()
+            })
+            .unwrap();
+
+        let loaded = store.backtest(first).unwrap().unwrap();
+        assert_eq!(loaded.id, Some(first));
+        assert!(loaded.created_at.unwrap() > hour(0));
+        assert_eq!(
+            loaded,
+            BacktestRecord {
+                id: loaded.id,
+                created_at: loaded.created_at,
+                ..record
+            }
+        );
+
+        let records = store.backtests("BTCUSD", "1h").unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, Some(first));
+        assert_eq!(records[1].strategy, "rsi");
+
+        assert!(store.delete_backtest(first).unwrap());
+        assert!(!store.delete_backtest(first).unwrap());
+        assert!(store.backtest(first).unwrap().is_none());
+        assert_eq!(store.backtests("BTCUSD", "1h").unwrap().len(), 1);
+        assert!(store.backtest(second).unwrap().is_some());
+    }
+
+    #[test]
+    fn nan_metrics() {
+        let mut store = DataStore::open_in_memory().unwrap();
+        let id = store
+            .save_backtest(&BacktestRecord {
+                metrics: BTreeMap::from([("profit_factor".to_string(), f64::NAN)]),
+                ..BacktestRecord::default()
+            })
+            .unwrap();
+
+        let record = store.backtest(id).unwrap().unwrap();
+        assert!(record.metrics["profit_factor"].is_nan());
+    }
+}
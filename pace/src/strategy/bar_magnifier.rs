@@ -0,0 +1,83 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::core::data_provider::DataProvider;
+
+/// Assumed order of price moves within a single bar, used when no lower-timeframe data is available.
+///
+/// Same as TradingView broker emulator: if high is closer to open than low, price moves `open -> high -> low -> close`, otherwise `open -> low -> high -> close`.
+pub fn intrabar_path(open: f64, high: f64, low: f64, close: f64) -> [f64; 4] {
+    if high - open <= open - low {
+        return [open, high, low, close];
+    }
+    return [open, low, high, close];
+}
+
+/// Resolves the order of price moves within a chart bar, using lower-timeframe bars of the same period.
+///
+/// Same as TradingView "Bar Magnifier". Both data providers must have bar time.
+pub struct BarMagnifier {
+    pub data: Arc<dyn DataProvider + Send + Sync>,
+    cursor: usize,
+}
+
+impl BarMagnifier {
+    pub fn new(data: Arc<dyn DataProvider + Send + Sync>) -> Self {
+        let cursor = data.get_start_tick();
+        return Self { data, cursor };
+    }
+
+    /// Intrabar price path of a chart bar, that starts at `start_time` and ends before `end_time`.
+    ///
+    /// Chart bars must be requested in chronological order. Returns `None` if there are no lower-timeframe bars in range.
+    pub fn path(&mut self, start_time: Duration, end_time: Option<Duration>) -> Option<Vec<f64>> {
+        let end_tick = self.data.get_end_tick();
+        let mut path: Vec<f64> = Vec::new();
+
+        while self.cursor <= end_tick {
+            let time = self.data.get_time(self.cursor);
+
+            if let Some(time) = time {
+                if end_time.is_some_and(|end_time| time >= end_time) {
+                    break;
+                }
+                if time >= start_time {
+                    if let (Some(open), Some(high), Some(low), Some(close)) = (
+                        self.data.get_open(self.cursor),
+                        self.data.get_high(self.cursor),
+                        self.data.get_low(self.cursor),
+                        self.data.get_close(self.cursor),
+                    ) {
+                        path.extend(intrabar_path(open, high, low, close));
+                    }
+                }
+            }
+
+            self.cursor += 1;
+        }
+
+        if path.is_empty() {
+            return None;
+        }
+        return Some(path);
+    }
+
+    /// Open price of the first lower-timeframe bar that starts at or after `time`.
+    pub fn open_at(&self, time: Duration) -> Option<f64> {
+        let mut start = self.data.get_start_tick();
+        let mut end = self.data.get_end_tick() + 1;
+
+        while start < end {
+            let middle = (start + end) / 2;
+            if self.data.get_time(middle).is_some_and(|x| x < time) {
+                start = middle + 1;
+            } else {
+                end = middle;
+            }
+        }
+
+        if start > self.data.get_end_tick() {
+            return None;
+        }
+        return self.data.get_open(start);
+    }
+}
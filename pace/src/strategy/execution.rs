@@ -0,0 +1,68 @@
+/// Commission charged on every fill. Same as PineScript `commission_type` and `commission_value`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StrategyCommission {
+    /// Fraction of the order value, e.g. `0.001` = `0.1%`. Same as PineScript `strategy.commission.percent`.
+    Percent(f64),
+    /// Amount per contract. Same as PineScript `strategy.commission.cash_per_contract`.
+    CashPerContract(f64),
+    /// Amount per order. Same as PineScript `strategy.commission.cash_per_order`.
+    CashPerOrder(f64),
+}
+
+impl StrategyCommission {
+    /// Commission of a fill of `quantity` at `price`.
+    pub fn amount(&self, quantity: f64, price: f64, point_value: f64) -> f64 {
+        if quantity <= 0.0 {
+            return 0.0;
+        }
+        return match self {
+            StrategyCommission::Percent(value) => quantity * price * point_value * value,
+            StrategyCommission::CashPerContract(value) => quantity * value,
+            StrategyCommission::CashPerOrder(value) => *value,
+        };
+    }
+}
+
+/// Liquidity constraints and costs of orders.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StrategyExecutionConfig {
+    /// Maximum quantity filled on a single bar, as a fraction of bar volume, e.g. `0.1` = `10%`.
+    ///
+    /// Unfilled remainder is carried to subsequent bars. If `None` or bar volume is missing, orders are filled at once.
+    pub max_volume_participation: Option<f64>,
+    /// Square-root market impact coefficient. Fill price is moved against the order by `market_impact * sqrt(quantity / volume)` fraction of price.
+    pub market_impact: Option<f64>,
+    /// Commission of every fill. If `None`, orders are free.
+    pub commission: Option<StrategyCommission>,
+}
+
+impl StrategyExecutionConfig {
+    /// Commission of a fill of `quantity` at `price`.
+    pub fn commission(&self, quantity: f64, price: f64, point_value: f64) -> f64 {
+        return self.commission.map_or(0.0, |commission| {
+            commission.amount(quantity, price, point_value)
+        });
+    }
+
+    /// Maximum quantity that can be filled on a bar with given `volume`.
+    pub fn fill_limit(&self, volume: Option<f64>) -> f64 {
+        return match (self.max_volume_participation, volume) {
+            (Some(participation), Some(volume)) => f64::max(participation * volume, 0.0),
+            _ => f64::INFINITY,
+        };
+    }
+
+    /// Price of a market order of `quantity` on a bar with given `volume`, including market impact.
+    pub fn fill_price(&self, price: f64, quantity: f64, volume: Option<f64>, is_buy: bool) -> f64 {
+        let impact = match (self.market_impact, volume) {
+            (Some(market_impact), Some(volume)) if volume > 0.0 => {
+                market_impact * (quantity / volume).sqrt()
+            }
+            _ => return price,
+        };
+        if is_buy {
+            return price * (1.0 + impact);
+        }
+        return price * (1.0 - impact);
+    }
+}
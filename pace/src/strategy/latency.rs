@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use crate::core::data_provider::DataProvider;
+
+/// Delay between a signal and submission of the resulting order.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StrategyLatency {
+    /// Order is submitted this many bars after the signal.
+    Bars(usize),
+    /// Order is submitted this long after the bar that generated the signal has closed.
+    ///
+    /// With intrabar data, the order is filled at the first lower-timeframe bar after the delay.
+    /// Otherwise, it is filled at the first chart bar after the delay. If bar time is missing, there is no delay.
+    Time(Duration),
+}
+
+impl StrategyLatency {
+    /// Returns the tick on which an order created on `tick` is passed to the strategy, and time of an intrabar fill, if any.
+    ///
+    /// Order passed on tick `t` is filled on the close of `t` if `on_bar_close` is `true`, otherwise on the open of `t + 1`.
+    pub fn schedule(
+        &self,
+        data: &dyn DataProvider,
+        tick: usize,
+        on_bar_close: bool,
+        is_intrabar: bool,
+    ) -> (usize, Option<Duration>) {
+        let delay = match self {
+            StrategyLatency::Bars(bars) => return (tick + bars, None),
+            StrategyLatency::Time(delay) => *delay,
+        };
+
+        let end_tick = data.get_end_tick();
+        // Bar close time is the open time of the next bar.
+        let close_time = |tick: usize| -> Option<Duration> {
+            if tick >= end_tick {
+                return None;
+            }
+            return data.get_time(tick + 1);
+        };
+
+        let Some(ready_time) = close_time(tick).map(|time| time + delay) else {
+            return (tick, None);
+        };
+
+        if on_bar_close {
+            for fill_tick in tick..=end_tick {
+                if close_time(fill_tick).is_none_or(|time| time >= ready_time) {
+                    return (fill_tick, None);
+                }
+            }
+            return (end_tick, None);
+        }
+
+        for fill_tick in tick + 1..=end_tick {
+            let Some(open_time) = data.get_time(fill_tick) else {
+                return (fill_tick - 1, None);
+            };
+            if open_time >= ready_time {
+                return (fill_tick - 1, None);
+            }
+            if is_intrabar && close_time(fill_tick).is_none_or(|time| time > ready_time) {
+                return (fill_tick - 1, Some(ready_time));
+            }
+        }
+
+        // Order is never filled.
+        return (end_tick + 1, None);
+    }
+}
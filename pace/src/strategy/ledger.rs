@@ -0,0 +1,204 @@
+use rust_decimal::{prelude::FromPrimitive, prelude::ToPrimitive, Decimal};
+
+/// Converts a float to decimal. Values out of range and `NaN` become `0`.
+pub fn to_decimal(value: f64) -> Decimal {
+    return Decimal::from_f64(value).unwrap_or_default();
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LedgerEntryKind {
+    /// Position change at a price.
+    Fill,
+    /// Commission paid.
+    Fee,
+    /// Carrying cost paid or received.
+    Funding,
+}
+
+/// Single cash movement.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LedgerEntry {
+    pub tick: usize,
+    pub kind: LedgerEntryKind,
+    /// Signed position change. Positive for buys, negative for sells. `0` for fees and funding.
+    pub quantity: Decimal,
+    /// Fill price. `0` for fees and funding.
+    pub price: Decimal,
+    /// Signed cash change.
+    pub cash: Decimal,
+    /// Profit realized by reducing the position.
+    pub realized_pnl: Decimal,
+}
+
+/// State of the ledger at the end of a bar.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LedgerSnapshot {
+    pub tick: usize,
+    pub cash: Decimal,
+    pub position: Decimal,
+    pub avg_cost: Decimal,
+    pub realized_pnl: Decimal,
+    pub fees: Decimal,
+    pub funding: Decimal,
+    pub equity: Decimal,
+}
+
+/// Records every cash movement of a strategy with decimal precision.
+///
+/// Buying moves `quantity * price * point_value` from cash to the position and selling moves it back, so equity is always `cash + position * price * point_value`.
+pub struct Ledger {
+    pub initial_capital: Decimal,
+    /// Point value of the last fill. Same as PineScript `syminfo.pointvalue`.
+    pub point_value: Decimal,
+    pub cash: Decimal,
+    /// Signed position quantity. Positive for long, negative for short.
+    pub position: Decimal,
+    /// Average fill price of the open position.
+    pub avg_cost: Decimal,
+    /// Total profit realized by reducing positions, excluding fees and funding.
+    pub realized_pnl: Decimal,
+    /// Total commission paid.
+    pub fees: Decimal,
+    /// Total funding. Negative value means funding was paid.
+    pub funding: Decimal,
+    pub entries: Vec<LedgerEntry>,
+    pub snapshots: Vec<LedgerSnapshot>,
+}
+
+impl Ledger {
+    pub fn new(initial_capital: f64) -> Self {
+        let initial_capital = to_decimal(initial_capital);
+        return Self {
+            initial_capital,
+            point_value: Decimal::ONE,
+            cash: initial_capital,
+            position: Decimal::ZERO,
+            avg_cost: Decimal::ZERO,
+            realized_pnl: Decimal::ZERO,
+            fees: Decimal::ZERO,
+            funding: Decimal::ZERO,
+            entries: Vec::new(),
+            snapshots: Vec::new(),
+        };
+    }
+
+    /// Records a fill of signed `quantity` at `price`.
+    pub fn fill(&mut self, tick: usize, quantity: f64, price: f64, point_value: f64) {
+        let quantity = to_decimal(quantity);
+        let price = to_decimal(price);
+
+        if quantity.is_zero() {
+            return;
+        }
+
+        self.point_value = to_decimal(point_value);
+
+        let mut realized_pnl = Decimal::ZERO;
+        let is_reducing = !self.position.is_zero()
+            && self.position.is_sign_positive() != quantity.is_sign_positive();
+
+        if is_reducing {
+            let closed_quantity = quantity.abs().min(self.position.abs());
+            let direction = if self.position.is_sign_positive() {
+                Decimal::ONE
+            } else {
+                Decimal::NEGATIVE_ONE
+            };
+            realized_pnl = (price - self.avg_cost) * closed_quantity * direction * self.point_value;
+
+            // Position is reversed, so the rest opens at the fill price.
+            if quantity.abs() > self.position.abs() {
+                self.avg_cost = price;
+            }
+        } else {
+            let position = self.position.abs();
+            self.avg_cost =
+                (self.avg_cost * position + price * quantity.abs()) / (position + quantity.abs());
+        }
+
+        self.position += quantity;
+        if self.position.is_zero() {
+            self.avg_cost = Decimal::ZERO;
+        }
+
+        let cash = -quantity * price * self.point_value;
+        self.cash += cash;
+        self.realized_pnl += realized_pnl;
+
+        self.entries.push(LedgerEntry {
+            tick,
+            kind: LedgerEntryKind::Fill,
+            quantity,
+            price,
+            cash,
+            realized_pnl,
+        });
+    }
+
+    /// Records paid commission.
+    pub fn fee(&mut self, tick: usize, amount: f64) {
+        let amount = to_decimal(amount);
+        if amount.is_zero() {
+            return;
+        }
+        self.cash -= amount;
+        self.fees += amount;
+        self.entries.push(LedgerEntry {
+            tick,
+            kind: LedgerEntryKind::Fee,
+            quantity: Decimal::ZERO,
+            price: Decimal::ZERO,
+            cash: -amount,
+            realized_pnl: Decimal::ZERO,
+        });
+    }
+
+    /// Records funding. Negative `amount` means funding was paid.
+    pub fn funding(&mut self, tick: usize, amount: f64) {
+        let amount = to_decimal(amount);
+        if amount.is_zero() {
+            return;
+        }
+        self.cash += amount;
+        self.funding += amount;
+        self.entries.push(LedgerEntry {
+            tick,
+            kind: LedgerEntryKind::Funding,
+            quantity: Decimal::ZERO,
+            price: Decimal::ZERO,
+            cash: amount,
+            realized_pnl: Decimal::ZERO,
+        });
+    }
+
+    /// Profit of the open position at `price`.
+    pub fn unrealized_pnl(&self, price: f64) -> Decimal {
+        if self.position.is_zero() {
+            return Decimal::ZERO;
+        }
+        return (to_decimal(price) - self.avg_cost) * self.position * self.point_value;
+    }
+
+    pub fn equity(&self, price: f64) -> Decimal {
+        return self.cash + self.position * to_decimal(price) * self.point_value;
+    }
+
+    /// Same as `equity`, as a float.
+    pub fn equity_f64(&self, price: f64) -> f64 {
+        return self.equity(price).to_f64().unwrap_or(f64::NAN);
+    }
+
+    /// Records state at the end of a bar.
+    pub fn snapshot(&mut self, tick: usize, price: f64) {
+        self.snapshots.push(LedgerSnapshot {
+            tick,
+            cash: self.cash,
+            position: self.position,
+            avg_cost: self.avg_cost,
+            realized_pnl: self.realized_pnl,
+            fees: self.fees,
+            funding: self.funding,
+            equity: self.equity(price),
+        });
+    }
+}
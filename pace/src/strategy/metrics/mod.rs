@@ -1,6 +1,7 @@
 pub mod cobra_metrics;
 pub mod common;
 pub mod equity_metrics;
+pub mod overfitting;
 pub mod returns;
 pub mod tradingview_metrics;
 
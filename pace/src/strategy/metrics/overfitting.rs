@@ -0,0 +1,177 @@
+use itertools::Itertools;
+
+use crate::statistics::common::{mean, normal_cdf, normal_inv_cdf, stdev};
+
+/// Euler-Mascheroni constant.
+static EULER_GAMMA: f64 = 0.5772156649015329;
+
+/// Non-annualized Sharpe ratio of per-period returns, assuming risk free rate of `0`.
+pub fn returns_sharpe_ratio(returns: &[f64]) -> f64 {
+    if returns.is_empty() {
+        return 0.0;
+    }
+    let stdev = stdev(returns);
+    if stdev == 0.0 {
+        return 0.0;
+    }
+    return mean(returns) / stdev;
+}
+
+/// Expected maximum Sharpe ratio among `trials` independent strategies with true Sharpe ratio of `0`.
+///
+/// `sharpe_variance` is the variance of Sharpe ratios across all trials.
+///
+/// Bailey, López de Prado - "The Deflated Sharpe Ratio" (2014).
+pub fn expected_max_sharpe_ratio(trials: usize, sharpe_variance: f64) -> f64 {
+    if trials <= 1 {
+        return 0.0;
+    }
+    let n = trials as f64;
+    return sharpe_variance.sqrt()
+        * ((1.0 - EULER_GAMMA) * normal_inv_cdf(1.0 - 1.0 / n)
+            + EULER_GAMMA * normal_inv_cdf(1.0 - 1.0 / (n * std::f64::consts::E)));
+}
+
+/// Probability that the true Sharpe ratio is higher than `benchmark_sharpe`, correcting for non-normal returns.
+///
+/// `sharpe` must be non-annualized and computed from `observations` returns.
+/// `kurtosis` is **not** excess kurtosis, meaning normal distribution has kurtosis of `3.0`.
+pub fn probabilistic_sharpe_ratio(
+    sharpe: f64,
+    benchmark_sharpe: f64,
+    observations: usize,
+    skewness: f64,
+    kurtosis: f64,
+) -> f64 {
+    if observations <= 1 {
+        return 0.0;
+    }
+    let denominator = (1.0 - skewness * sharpe + (kurtosis - 1.0) / 4.0 * sharpe.powi(2)).sqrt();
+    if denominator == 0.0 || denominator.is_nan() {
+        return 0.0;
+    }
+    return normal_cdf(
+        (sharpe - benchmark_sharpe) * ((observations - 1) as f64).sqrt() / denominator,
+    );
+}
+
+/// Deflated Sharpe ratio. Probability that the selected strategy has a true Sharpe ratio higher than `0`, after correcting for selection bias from running `trials` backtests.
+///
+/// `sharpe` must be non-annualized and computed from `observations` returns.
+/// `sharpe_variance` is the variance of Sharpe ratios across all trials.
+/// `kurtosis` is **not** excess kurtosis, meaning normal distribution has kurtosis of `3.0`.
+pub fn deflated_sharpe_ratio(
+    sharpe: f64,
+    trials: usize,
+    sharpe_variance: f64,
+    observations: usize,
+    skewness: f64,
+    kurtosis: f64,
+) -> f64 {
+    return probabilistic_sharpe_ratio(
+        sharpe,
+        expected_max_sharpe_ratio(trials, sharpe_variance),
+        observations,
+        skewness,
+        kurtosis,
+    );
+}
+
+#[derive(Clone, Debug)]
+pub struct BacktestOverfittingData {
+    /// Probability of backtest overfitting. Fraction of combinations where the best in-sample run ranked below median out-of-sample.
+    pub pbo: f64,
+    /// Logits of relative out-of-sample rank of the best in-sample run, for each combination.
+    pub logits: Vec<f64>,
+    /// Index of the best in-sample run, for each combination.
+    pub best_in_sample: Vec<usize>,
+}
+
+/// Probability of backtest overfitting, computed with combinatorially symmetric cross-validation (CSCV).
+///
+/// `returns` is a matrix of per-period returns, where each row is a single run (parameter combination) and all rows have the same length.
+/// Periods are split into `partitions` (even) contiguous blocks and every combination of half of them is used as in-sample set.
+///
+/// Bailey, Borwein, López de Prado, Zhu - "The Probability of Backtest Overfitting" (2015).
+pub fn probability_of_backtest_overfitting(
+    returns: &[Vec<f64>],
+    partitions: usize,
+) -> BacktestOverfittingData {
+    assert!(
+        partitions >= 2 && partitions.is_multiple_of(2),
+        "Number of partitions must be even and at least 2"
+    );
+    assert!(returns.len() >= 2, "At least 2 runs are required");
+
+    let periods = returns[0].len();
+    assert!(
+        returns.iter().all(|run| run.len() == periods),
+        "All runs must have the same number of periods"
+    );
+    assert!(
+        periods >= partitions,
+        "Number of periods must be at least equal to number of partitions"
+    );
+
+    let block_size = periods / partitions;
+    let blocks: Vec<(usize, usize)> = (0..partitions)
+        .map(|i| {
+            let start = i * block_size;
+            let end = if i == partitions - 1 {
+                periods
+            } else {
+                start + block_size
+            };
+            (start, end)
+        })
+        .collect();
+
+    let collect_returns = |run: &Vec<f64>, block_indices: &[usize]| -> Vec<f64> {
+        return block_indices
+            .iter()
+            .flat_map(|&i| run[blocks[i].0..blocks[i].1].iter().copied())
+            .collect();
+    };
+
+    let runs = returns.len();
+    let mut logits: Vec<f64> = Vec::new();
+    let mut best_in_sample: Vec<usize> = Vec::new();
+
+    for in_sample_blocks in (0..partitions).combinations(partitions / 2) {
+        let out_of_sample_blocks: Vec<usize> = (0..partitions)
+            .filter(|i| !in_sample_blocks.contains(i))
+            .collect();
+
+        let in_sample: Vec<f64> = returns
+            .iter()
+            .map(|run| returns_sharpe_ratio(&collect_returns(run, &in_sample_blocks)))
+            .collect();
+        let out_of_sample: Vec<f64> = returns
+            .iter()
+            .map(|run| returns_sharpe_ratio(&collect_returns(run, &out_of_sample_blocks)))
+            .collect();
+
+        let best = (0..runs)
+            .max_by(|&a, &b| in_sample[a].total_cmp(&in_sample[b]))
+            .unwrap();
+
+        // Rank is 1-based, so relative rank is always in `(0, 1)`.
+        let rank = out_of_sample
+            .iter()
+            .filter(|&&x| x < out_of_sample[best])
+            .count()
+            + 1;
+        let relative_rank = rank as f64 / (runs + 1) as f64;
+
+        logits.push((relative_rank / (1.0 - relative_rank)).ln());
+        best_in_sample.push(best);
+    }
+
+    let pbo = logits.iter().filter(|&&x| x <= 0.0).count() as f64 / logits.len() as f64;
+
+    return BacktestOverfittingData {
+        pbo,
+        logits,
+        best_in_sample,
+    };
+}
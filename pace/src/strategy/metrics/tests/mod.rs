@@ -1 +1,2 @@
+mod overfitting_test;
 mod strategy_metrics_test;
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        statistics::common::{normal_cdf, normal_inv_cdf},
+        strategy::metrics::overfitting::{
+            deflated_sharpe_ratio, expected_max_sharpe_ratio, probabilistic_sharpe_ratio,
+            probability_of_backtest_overfitting,
+        },
+        testing::comparison::FloatComparison,
+    };
+
+    #[test]
+    fn normal_distribution() {
+        assert!(normal_cdf(0.0).compare(0.5));
+        assert!(normal_cdf(1.959964).compare_with_precision(0.975, 0.000001));
+        assert!(normal_cdf(-1.959964).compare_with_precision(0.025, 0.000001));
+        assert!(normal_inv_cdf(0.5).compare(0.0));
+        assert!(normal_inv_cdf(0.975).compare(1.959964));
+        assert!(normal_inv_cdf(0.001).compare(-3.090232));
+    }
+
+    #[test]
+    fn expected_max_sharpe() {
+        assert_eq!(expected_max_sharpe_ratio(1, 0.5), 0.0);
+        assert!(expected_max_sharpe_ratio(100, 1.0).compare_with_precision(2.5306, 0.001));
+        assert!(expected_max_sharpe_ratio(1000, 1.0) > expected_max_sharpe_ratio(100, 1.0));
+    }
+
+    #[test]
+    fn deflated_sharpe() {
+        assert!(probabilistic_sharpe_ratio(0.0, 0.0, 100, 0.0, 3.0).compare(0.5));
+        assert!(deflated_sharpe_ratio(0.1, 1, 0.01, 250, 0.0, 3.0)
+            .compare(probabilistic_sharpe_ratio(0.1, 0.0, 250, 0.0, 3.0)));
+
+        let single_trial = deflated_sharpe_ratio(0.1, 1, 0.01, 250, 0.0, 3.0);
+        let many_trials = deflated_sharpe_ratio(0.1, 1000, 0.01, 250, 0.0, 3.0);
+        assert!(single_trial > 0.9);
+        assert!(many_trials < single_trial);
+        assert!(many_trials < 0.5);
+    }
+
+    #[test]
+    fn pbo_consistent_winner() {
+        let noise = |i: usize| ((i * 7919) % 13) as f64 / 1000.0 - 0.006;
+        let returns: Vec<Vec<f64>> = (0..4)
+            .map(|run| {
+                (0..80)
+                    .map(|i| noise(i + run) + run as f64 * 0.01)
+                    .collect()
+            })
+            .collect();
+
+        let result = probability_of_backtest_overfitting(&returns, 4);
+
+        assert_eq!(result.logits.len(), 6);
+        assert!(result.best_in_sample.iter().all(|&x| x == 3));
+        assert!(result.pbo.compare(0.0));
+    }
+
+    #[test]
+    fn pbo_reversed_performance() {
+        let noise = |i: usize| ((i * 7919) % 13) as f64 / 1000.0 - 0.006;
+        let first: Vec<f64> = (0..40)
+            .map(|i| noise(i) + if i < 20 { 0.01 } else { -0.01 })
+            .collect();
+        let second: Vec<f64> = (0..40)
+            .map(|i| noise(i + 1) + if i < 20 { -0.01 } else { 0.01 })
+            .collect();
+
+        let result = probability_of_backtest_overfitting(&[first, second], 2);
+
+        assert_eq!(result.best_in_sample, vec![0, 1]);
+        assert!(result.pbo.compare(1.0));
+    }
+}
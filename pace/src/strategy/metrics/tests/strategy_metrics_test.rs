@@ -379,62 +379,138 @@ mod tests {
 
     fn _load_metrics(df: &DataFrame) -> Vec<Option<TestMetricsPayload>> {
         // BASE METRICS
-        let equity = df.column("_target_equity_").unwrap().to_f64();
-        let net_profit = df.column("_target_net_profit_").unwrap().to_f64();
-        let open_profit = df.column("_target_open_profit_").unwrap().to_f64();
-        let gross_profit = df.column("_target_gross_profit_").unwrap().to_f64();
-        let gross_loss = df.column("_target_gross_loss_").unwrap().to_f64();
-        let closed_trades = df.column("_target_closed_trades_").unwrap().to_usize();
-        let losing_trades = df.column("_target_losing_trades_").unwrap().to_usize();
-        let winning_trades = df.column("_target_winning_trades_").unwrap().to_usize();
+        let equity = df.column("_target_equity_").unwrap().to_f64().unwrap();
+        let net_profit = df.column("_target_net_profit_").unwrap().to_f64().unwrap();
+        let open_profit = df.column("_target_open_profit_").unwrap().to_f64().unwrap();
+        let gross_profit = df
+            .column("_target_gross_profit_")
+            .unwrap()
+            .to_f64()
+            .unwrap();
+        let gross_loss = df.column("_target_gross_loss_").unwrap().to_f64().unwrap();
+        let closed_trades = df
+            .column("_target_closed_trades_")
+            .unwrap()
+            .to_usize()
+            .unwrap();
+        let losing_trades = df
+            .column("_target_losing_trades_")
+            .unwrap()
+            .to_usize()
+            .unwrap();
+        let winning_trades = df
+            .column("_target_winning_trades_")
+            .unwrap()
+            .to_usize()
+            .unwrap();
 
         // ADDITIONAL BASE METRICS
-        let net_equity = df.column("_target_net_equity_").unwrap().to_f64();
-        let long_net_profit = df.column("_target_long_net_profit_").unwrap().to_f64();
-        let short_net_profit = df.column("_target_short_net_profit_").unwrap().to_f64();
-        let max_drawdown = df.column("_target_max_drawdown_").unwrap().to_f64();
-        let max_run_up = df.column("_target_max_run_up_").unwrap().to_f64();
-        let avg_trade = df.column("_target_avg_trade_").unwrap().to_f64();
-        let avg_winning_trade = df.column("_target_avg_winning_trade_").unwrap().to_f64();
-        let avg_losing_trade = df.column("_target_avg_losing_trade_").unwrap().to_f64();
-        let avg_win_loss_trade_ratio = df.column("_target_avg_win_loss_ratio_").unwrap().to_f64();
+        let net_equity = df.column("_target_net_equity_").unwrap().to_f64().unwrap();
+        let long_net_profit = df
+            .column("_target_long_net_profit_")
+            .unwrap()
+            .to_f64()
+            .unwrap();
+        let short_net_profit = df
+            .column("_target_short_net_profit_")
+            .unwrap()
+            .to_f64()
+            .unwrap();
+        let max_drawdown = df
+            .column("_target_max_drawdown_")
+            .unwrap()
+            .to_f64()
+            .unwrap();
+        let max_run_up = df.column("_target_max_run_up_").unwrap().to_f64().unwrap();
+        let avg_trade = df.column("_target_avg_trade_").unwrap().to_f64().unwrap();
+        let avg_winning_trade = df
+            .column("_target_avg_winning_trade_")
+            .unwrap()
+            .to_f64()
+            .unwrap();
+        let avg_losing_trade = df
+            .column("_target_avg_losing_trade_")
+            .unwrap()
+            .to_f64()
+            .unwrap();
+        let avg_win_loss_trade_ratio = df
+            .column("_target_avg_win_loss_ratio_")
+            .unwrap()
+            .to_f64()
+            .unwrap();
 
         // COBRA METRICS
         let equity_max_drawdown_percent = df
             .column("_target_equity_max_drawdown_percent_")
             .unwrap()
-            .to_f64();
-        let profit_factor = df.column("_target_profit_factor_").unwrap().to_f64();
-        let percent_profitable = df.column("_target_percent_profitable_").unwrap().to_f64();
+            .to_f64()
+            .unwrap();
+        let profit_factor = df
+            .column("_target_profit_factor_")
+            .unwrap()
+            .to_f64()
+            .unwrap();
+        let percent_profitable = df
+            .column("_target_percent_profitable_")
+            .unwrap()
+            .to_f64()
+            .unwrap();
         let long_short_net_profit_ratio = df
             .column("_target_long_short_net_profit_ratio_")
             .unwrap()
-            .to_f64();
+            .to_f64()
+            .unwrap();
         let intra_trade_max_drawdown_percent = df
             .column("_target_intra_trade_max_drawdown_percent_")
             .unwrap()
-            .to_f64();
+            .to_f64()
+            .unwrap();
 
         // UTILITY METRICS
-        let net_profit_percent = df.column("_target_net_profit_percent_").unwrap().to_f64();
-        let gross_profit_percent = df.column("_target_gross_profit_percent_").unwrap().to_f64();
-        let gross_loss_percent = df.column("_target_gross_loss_percent_").unwrap().to_f64();
+        let net_profit_percent = df
+            .column("_target_net_profit_percent_")
+            .unwrap()
+            .to_f64()
+            .unwrap();
+        let gross_profit_percent = df
+            .column("_target_gross_profit_percent_")
+            .unwrap()
+            .to_f64()
+            .unwrap();
+        let gross_loss_percent = df
+            .column("_target_gross_loss_percent_")
+            .unwrap()
+            .to_f64()
+            .unwrap();
         let long_net_profit_percent = df
             .column("_target_long_net_profit_percent_")
             .unwrap()
-            .to_f64();
+            .to_f64()
+            .unwrap();
         let short_net_profit_percent = df
             .column("_target_short_net_profit_percent_")
             .unwrap()
-            .to_f64();
+            .to_f64()
+            .unwrap();
         // let net_equity_max_drawdown_percent = df
         //     .column("_target_net_equity_max_drawdown_percent_")
         //     .unwrap()
-        //     .to_f64();
-        let net_equity_max_drawdown_percent =
-            df.column("_target_max_drawdown_percent_").unwrap().to_f64();
-        let net_equity_min = df.column("_target_net_equity_min_").unwrap().to_f64();
-        let net_equity_max = df.column("_target_net_equity_max_").unwrap().to_f64();
+        //     .to_f64().unwrap();
+        let net_equity_max_drawdown_percent = df
+            .column("_target_max_drawdown_percent_")
+            .unwrap()
+            .to_f64()
+            .unwrap();
+        let net_equity_min = df
+            .column("_target_net_equity_min_")
+            .unwrap()
+            .to_f64()
+            .unwrap();
+        let net_equity_max = df
+            .column("_target_net_equity_max_")
+            .unwrap()
+            .to_f64()
+            .unwrap();
 
         let mut metrics: Vec<Option<TestMetricsPayload>> = Vec::new();
 
@@ -502,6 +578,7 @@ mod tests {
                     on_bar_close: false,
                     initial_capital: 1000.0,
                     buy_with_equity: false,
+                    ..StrategyConfig::default()
                 },
                 // Long entries
                 vec![2, 18, 44, 60, 120, 180, 400, 700, 1000, 1600],
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use prettytable::{color, row, Attr, Cell, Row, Table};
 use textplots::{Chart, Plot, Shape};
 
@@ -34,6 +36,8 @@ pub struct TradingViewMetricsData {
     pub sortino_ratio: f64,
     pub profit_factor: f64,
     pub open_pl: f64,
+    /// Total funding paid or received. Negative value means funding was paid.
+    pub net_funding: f64,
     pub total_closed_trades: usize,
     pub number_winning_trades: usize,
     pub number_losing_trades: usize,
@@ -64,6 +68,7 @@ impl TradingViewMetricsData {
             sortino_ratio: 0.0,
             profit_factor: 0.0,
             open_pl: 0.0,
+            net_funding: 0.0,
             total_closed_trades: 0,
             number_winning_trades: 0,
             number_losing_trades: 0,
@@ -78,6 +83,38 @@ impl TradingViewMetricsData {
         };
     }
 
+    /// Metrics by field name, without histories. Used to persist metrics, e.g. in `DataStore`.
+    pub fn to_map(&self) -> BTreeMap<String, f64> {
+        return [
+            ("net_profit", self.net_profit),
+            ("net_profit_percent", self.net_profit_percent),
+            ("gross_profit", self.gross_profit),
+            ("gross_profit_percent", self.gross_profit_percent),
+            ("gross_loss", self.gross_loss),
+            ("gross_loss_percent", self.gross_loss_percent),
+            ("max_run_up", self.max_run_up),
+            ("max_run_up_percent", self.max_run_up_percent),
+            ("max_drawdown", self.max_drawdown),
+            ("max_drawdown_percent", self.max_drawdown_percent),
+            ("sharpe_ratio", self.sharpe_ratio),
+            ("sortino_ratio", self.sortino_ratio),
+            ("profit_factor", self.profit_factor),
+            ("open_pl", self.open_pl),
+            ("net_funding", self.net_funding),
+            ("total_closed_trades", self.total_closed_trades as f64),
+            ("number_winning_trades", self.number_winning_trades as f64),
+            ("number_losing_trades", self.number_losing_trades as f64),
+            ("percent_profitable", self.percent_profitable),
+            ("avg_trade", self.avg_trade),
+            ("avg_winning_trade", self.avg_winning_trade),
+            ("avg_losing_trade", self.avg_losing_trade),
+            ("ratio_avg_win_avg_loss", self.ratio_avg_win_avg_loss),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    }
+
     pub fn print_overview(&self, currency: &str) {
         let f_price = with_suffix(&format!(" {}", currency));
         let f_percent = with_suffix("%");
@@ -182,6 +219,7 @@ impl TradingViewMetricsData {
         table.add_row(row!["Sortino Ratio", format!("{:0.3}", self.sortino_ratio)]);
         table.add_row(row!["Profit Factor", format!("{:0.3}", self.profit_factor)]);
         table.add_row(row!["Open P/L", f_price(self.open_pl)]);
+        table.add_row(row!["Net Funding", f_price(self.net_funding)]);
         table.add_row(row!["Total Closed Trades", self.total_closed_trades]);
         table.add_row(row!["Number Winning Trades", self.number_winning_trades]);
         table.add_row(row!["Number Losing Trades", self.number_losing_trades]);
@@ -305,6 +343,7 @@ impl Incremental<&Strategy, ()> for TradingViewMetrics {
                 .unwrap_or(0.0);
 
         self.data.open_pl = strategy.metrics.open_profit;
+        self.data.net_funding = strategy.metrics.net_funding;
         self.data.total_closed_trades = strategy.metrics.closed_trades;
         self.data.number_winning_trades = strategy.metrics.winning_trades;
         self.data.number_losing_trades = strategy.metrics.losing_trades;
@@ -1,5 +1,16 @@
+pub mod bar_magnifier;
+pub mod execution;
+pub mod latency;
+pub mod ledger;
 pub mod metrics;
+pub mod multi_strategy;
+pub mod order;
+pub mod position_sizer;
+pub mod rebalance;
+pub mod risk;
+pub mod signal;
 pub mod strategy;
 pub mod trade;
+pub mod trade_list;
 
 mod tests;
@@ -0,0 +1,290 @@
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+
+use crate::core::{
+    context::Context,
+    error::{PaceError, PaceResult},
+    incremental::Incremental,
+};
+
+use super::{
+    execution::StrategyExecutionConfig,
+    ledger::{Ledger, LedgerEntryKind},
+    signal::StrategySignal,
+    strategy::{Strategy, StrategyConfig},
+};
+
+/// Allows allocations like `0.1` repeated 10 times to sum slightly above `1.0`.
+const ALLOCATION_TOLERANCE: f64 = 1e-9;
+
+/// Sub-strategy with its own share of capital.
+#[derive(Clone, Debug)]
+pub struct StrategySleeveConfig {
+    pub name: String,
+    /// Fraction of `MultiStrategyConfig::initial_capital`, e.g. `0.5` = `50%`. Overrides `strategy.initial_capital`.
+    pub allocation: f64,
+    pub strategy: StrategyConfig,
+}
+
+#[derive(Clone, Debug)]
+pub struct MultiStrategyConfig {
+    pub initial_capital: f64,
+    pub sleeves: Vec<StrategySleeveConfig>,
+    /// Commission of netted orders. Commission of sleeve strategies is ignored.
+    pub execution: StrategyExecutionConfig,
+}
+
+impl Default for MultiStrategyConfig {
+    fn default() -> Self {
+        return Self {
+            initial_capital: 1000.0,
+            sleeves: Vec::new(),
+            execution: StrategyExecutionConfig::default(),
+        };
+    }
+}
+
+pub struct StrategySleeve {
+    pub name: String,
+    pub allocation: f64,
+    /// Orders of the sleeve. Its own commission is disabled, so its metrics exclude `commission`.
+    pub strategy: Strategy,
+    /// Share of commission of netted orders, in proportion to quantity traded by the sleeve.
+    pub commission: f64,
+    /// Index of the first entry of `strategy.ledger` that was not executed yet.
+    executed_entries: usize,
+}
+
+impl StrategySleeve {
+    /// Equity of the sleeve, after its share of commission.
+    pub fn equity(&self) -> f64 {
+        return self.strategy.metrics.equity - self.commission;
+    }
+}
+
+/// Metrics of all sleeves combined.
+pub struct MultiStrategyMetrics {
+    /// Equity of the shared position. Unallocated capital is included.
+    pub equity: f64,
+    pub net_profit: f64,
+    pub open_profit: f64,
+    pub closed_trades: usize,
+    pub winning_trades: usize,
+    pub losing_trades: usize,
+    /// Largest drop of combined equity from its highest value.
+    pub max_drawdown: f64,
+    pub equity_max: f64,
+    /// Signed quantity of the shared position. Opposite positions of sleeves cancel out.
+    pub net_position: f64,
+    /// Sum of absolute quantities of all sleeve positions.
+    pub gross_position: f64,
+    /// Commission paid for netted orders.
+    pub commission: f64,
+}
+
+impl MultiStrategyMetrics {
+    pub fn default(initial_capital: f64) -> Self {
+        return Self {
+            equity: initial_capital,
+            net_profit: 0.0,
+            open_profit: 0.0,
+            closed_trades: 0,
+            winning_trades: 0,
+            losing_trades: 0,
+            max_drawdown: 0.0,
+            equity_max: initial_capital,
+            net_position: 0.0,
+            gross_position: 0.0,
+            commission: 0.0,
+        };
+    }
+}
+
+/// Runs several independent strategies on one symbol, each with its own capital allocation.
+///
+/// Every sleeve is a regular `Strategy` sharing the same `Context`, so per-sleeve metrics are available through `sleeves[i].strategy.metrics`.
+///
+/// Sleeves only decide their positions. Their position changes on a bar are netted and executed once on the shared `ledger`, where orders at the same price cancel out and commission is charged on the net quantity.
+pub struct MultiStrategy {
+    pub ctx: Context,
+    pub initial_capital: f64,
+    pub sleeves: Vec<StrategySleeve>,
+    pub metrics: MultiStrategyMetrics,
+    /// Commission of netted orders.
+    pub execution: StrategyExecutionConfig,
+    /// Shared position of all sleeves.
+    pub ledger: Ledger,
+}
+
+impl MultiStrategy {
+    /// Returns an error if an allocation is negative or not finite, if allocations sum to more than `1.0` or if a sleeve margin is invalid.
+    pub fn new(ctx: Context, config: MultiStrategyConfig) -> PaceResult<Self> {
+        for sleeve in &config.sleeves {
+            if !sleeve.allocation.is_finite() || sleeve.allocation < 0.0 {
+                return Err(PaceError::InvalidData(format!(
+                    "Invalid allocation {} of sleeve \"{}\", expected a non-negative fraction",
+                    sleeve.allocation, sleeve.name
+                )));
+            }
+            sleeve
+                .strategy
+                .margin
+                .map(|margin| margin.validate())
+                .transpose()?;
+        }
+        let allocation: f64 = config.sleeves.iter().map(|sleeve| sleeve.allocation).sum();
+        if allocation > 1.0 + ALLOCATION_TOLERANCE {
+            return Err(PaceError::InvalidData(format!(
+                "Sleeve allocations sum to {}, expected at most 1.0",
+                allocation
+            )));
+        }
+
+        let sleeves = config
+            .sleeves
+            .into_iter()
+            .map(|sleeve| {
+                return Ok(StrategySleeve {
+                    strategy: Strategy::try_new(
+                        ctx.clone(),
+                        StrategyConfig {
+                            initial_capital: config.initial_capital * sleeve.allocation,
+                            execution: StrategyExecutionConfig {
+                                commission: None,
+                                ..sleeve.strategy.execution
+                            },
+                            ..sleeve.strategy
+                        },
+                    )?,
+                    name: sleeve.name,
+                    allocation: sleeve.allocation,
+                    commission: 0.0,
+                    executed_entries: 0,
+                });
+            })
+            .collect::<PaceResult<Vec<_>>>()?;
+
+        return Ok(Self {
+            ctx: ctx.clone(),
+            initial_capital: config.initial_capital,
+            sleeves,
+            metrics: MultiStrategyMetrics::default(config.initial_capital),
+            execution: config.execution,
+            ledger: Ledger::new(config.initial_capital),
+        });
+    }
+
+    pub fn sleeve(&self, name: &str) -> Option<&StrategySleeve> {
+        return self.sleeves.iter().find(|sleeve| sleeve.name == name);
+    }
+
+    pub fn sleeve_mut(&mut self, name: &str) -> Option<&mut StrategySleeve> {
+        return self.sleeves.iter_mut().find(|sleeve| sleeve.name == name);
+    }
+
+    /// Capital not allocated to any sleeve.
+    pub fn unallocated_capital(&self) -> f64 {
+        let allocation: f64 = self.sleeves.iter().map(|sleeve| sleeve.allocation).sum();
+        return self.initial_capital * (1.0 - allocation);
+    }
+
+    /// Executes new fills of all sleeves on the shared position. Fills at the same price are netted into a single order.
+    fn execute_orders(&mut self) {
+        let tick = self.ctx.bar.index();
+        let point_value = self.ctx.syminfo.point_value;
+        // Orders by price, with quantity of each sleeve.
+        let mut orders: Vec<(Decimal, Vec<(usize, Decimal)>)> = Vec::new();
+        let mut funding = Decimal::ZERO;
+
+        for (index, sleeve) in self.sleeves.iter_mut().enumerate() {
+            let entries = &sleeve.strategy.ledger.entries;
+
+            for entry in &entries[sleeve.executed_entries..] {
+                match entry.kind {
+                    LedgerEntryKind::Fill => {
+                        match orders.iter_mut().find(|(price, _)| *price == entry.price) {
+                            Some((_, fills)) => fills.push((index, entry.quantity)),
+                            None => orders.push((entry.price, vec![(index, entry.quantity)])),
+                        }
+                    }
+                    LedgerEntryKind::Funding => funding += entry.cash,
+                    LedgerEntryKind::Fee => {}
+                }
+            }
+            sleeve.executed_entries = entries.len();
+        }
+
+        for (price, fills) in orders {
+            let price = price.to_f64().unwrap();
+            let quantity = fills.iter().map(|(_, quantity)| *quantity).sum::<Decimal>();
+            let quantity = quantity.to_f64().unwrap();
+            let traded = fills
+                .iter()
+                .map(|(_, quantity)| quantity.abs())
+                .sum::<Decimal>()
+                .to_f64()
+                .unwrap();
+
+            self.ledger.fill(tick, quantity, price, point_value);
+
+            let commission = self
+                .execution
+                .commission(quantity.abs(), price, point_value);
+            if commission > 0.0 {
+                self.ledger.fee(tick, commission);
+                for (index, sleeve_quantity) in fills {
+                    self.sleeves[index].commission +=
+                        commission * sleeve_quantity.abs().to_f64().unwrap() / traded;
+                }
+            }
+        }
+
+        if !funding.is_zero() {
+            self.ledger.funding(tick, funding.to_f64().unwrap());
+        }
+        if let Some(close) = self.ctx.bar.close() {
+            self.ledger.snapshot(tick, close);
+        }
+    }
+
+    fn update_metrics(&mut self) {
+        let mut metrics = MultiStrategyMetrics::default(self.unallocated_capital());
+        metrics.max_drawdown = self.metrics.max_drawdown;
+        metrics.equity_max = self.metrics.equity_max;
+
+        for sleeve in &self.sleeves {
+            let strategy = &sleeve.strategy;
+            metrics.equity += sleeve.equity();
+            metrics.net_profit += strategy.metrics.net_profit - sleeve.commission;
+            metrics.open_profit += strategy.metrics.open_profit;
+            metrics.closed_trades += strategy.metrics.closed_trades;
+            metrics.winning_trades += strategy.metrics.winning_trades;
+            metrics.losing_trades += strategy.metrics.losing_trades;
+
+            if let Some(trade) = strategy.trades.last().filter(|trade| trade.is_active()) {
+                metrics.gross_position += trade.fill_size.unwrap();
+            }
+        }
+
+        if let Some(close) = self.ctx.bar.close() {
+            metrics.equity = self.ledger.equity_f64(close);
+        }
+        metrics.net_position = self.ledger.position.to_f64().unwrap();
+        metrics.commission = self.ledger.fees.to_f64().unwrap();
+        metrics.equity_max = f64::max(metrics.equity_max, metrics.equity);
+        metrics.max_drawdown = f64::max(metrics.max_drawdown, metrics.equity_max - metrics.equity);
+
+        self.metrics = metrics;
+    }
+}
+
+/// Accepts one signal per sleeve, in order of `sleeves`. Missing signals are treated as `StrategySignal::Hold`.
+impl Incremental<Vec<StrategySignal>, ()> for MultiStrategy {
+    fn next(&mut self, signals: Vec<StrategySignal>) {
+        for (i, sleeve) in self.sleeves.iter_mut().enumerate() {
+            let signal = signals.get(i).copied().unwrap_or(StrategySignal::Hold);
+            sleeve.strategy.next(signal);
+        }
+        self.execute_orders();
+        self.update_metrics();
+    }
+}
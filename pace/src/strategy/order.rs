@@ -0,0 +1,88 @@
+use super::trade::TradeDirection;
+
+/// Exit of the open position at `stop` or `limit` price, whichever is reached first within a bar.
+///
+/// Order is bound to the position that is open when it is first evaluated, and is cancelled once that position is closed.
+///
+/// Same as PineScript `strategy.exit(id, stop=..., limit=...)`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StrategyExitOrder {
+    /// Exit id of the closed trade.
+    pub id: String,
+    /// Stop loss price. Below entry price for long positions, above for short.
+    pub stop: Option<f64>,
+    /// Take profit price. Above entry price for long positions, below for short.
+    pub limit: Option<f64>,
+}
+
+/// Entry at `stop` or `limit` price. If both are `None`, entry is filled at the next bar open.
+///
+/// If both are set, it's a stop-limit order: once price reaches `stop`, a limit order at `limit` becomes active.
+///
+/// Entry in the opposite direction of the open position reverses it.
+///
+/// Same as PineScript `strategy.entry(id, direction, limit=..., stop=..., oca_name=...)`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StrategyEntryOrder {
+    /// Entry id of the opened trade. Order with the same id replaces the pending one.
+    pub id: String,
+    pub direction: TradeDirection,
+    /// Entry is filled once price reaches it in the trade direction, e.g. a breakout above it for long. With `limit`, it activates the limit instead.
+    pub stop: Option<f64>,
+    /// Entry is filled once price reaches it against the trade direction, e.g. a pullback below it for long.
+    pub limit: Option<f64>,
+    /// Pending orders of the same group are cancelled once any of them is filled.
+    ///
+    /// Same as PineScript `strategy.oca.cancel`.
+    pub oca_group: Option<String>,
+    /// Exit order activated once this entry is filled, making it a bracket order. Stop and limit of the exit cancel each other.
+    pub bracket: Option<StrategyExitOrder>,
+}
+
+impl StrategyEntryOrder {
+    pub fn new(id: &str, direction: TradeDirection) -> Self {
+        return Self {
+            id: id.to_string(),
+            direction,
+            stop: None,
+            limit: None,
+            oca_group: None,
+            bracket: None,
+        };
+    }
+
+    /// Bracket order, that enters at `entry` and exits at `stop` loss or `target` profit.
+    ///
+    /// Entry is a stop or limit order, depending on the current price. If `entry` is `None`, it is filled at the next bar open.
+    pub fn bracket(
+        id: &str,
+        direction: TradeDirection,
+        entry: Option<f64>,
+        current_price: f64,
+        stop: f64,
+        target: f64,
+    ) -> Self {
+        let mut order = Self::new(id, direction);
+
+        if let Some(entry) = entry {
+            let is_above = entry > current_price;
+            let is_stop = match direction {
+                TradeDirection::Long => is_above,
+                TradeDirection::Short => !is_above,
+            };
+            if is_stop {
+                order.stop = Some(entry);
+            } else {
+                order.limit = Some(entry);
+            }
+        }
+
+        order.bracket = Some(StrategyExitOrder {
+            id: format!("{} exit", id),
+            stop: Some(stop),
+            limit: Some(target),
+        });
+
+        return order;
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    core::{context::Context, incremental::Incremental},
+    ta::{average_true_range::Atr, stdev::Stdev},
+};
+
+use super::trade::Trade;
+
+/// Method of computing quantity of a new trade. Replaces `buy_with_equity` when set.
+#[derive(Clone, Copy, Debug)]
+pub enum StrategyPositionSizer {
+    /// Risks `risk` fraction of equity, assuming the trade is stopped out `stop_distance` price units away from the entry.
+    FixedFractional { risk: f64, stop_distance: f64 },
+    /// Same as `FixedFractional`, but the stop distance is `multiplier` times `ta.atr(length)`.
+    Atr {
+        risk: f64,
+        length: usize,
+        multiplier: f64,
+    },
+    /// Scales the position, so that its per-bar volatility equals `target` fraction of equity.
+    ///
+    /// Volatility is the standard deviation of close-to-close returns over `length` bars.
+    VolatilityTarget { target: f64, length: usize },
+    /// Invests Kelly fraction of equity, computed from win rate and payoff ratio of closed trades.
+    ///
+    /// Kelly fraction is multiplied by `fraction` (e.g. `0.5` for half-Kelly) and capped at `max_fraction`.
+    /// Until `min_trades` trades are closed, `max_fraction` is used.
+    Kelly {
+        fraction: f64,
+        max_fraction: f64,
+        min_trades: usize,
+    },
+}
+
+/// Kelly criterion. Optimal fraction of equity to risk, given probability of winning and ratio of average win to average loss.
+pub fn kelly_fraction(win_rate: f64, payoff_ratio: f64) -> f64 {
+    if payoff_ratio <= 0.0 {
+        return 0.0;
+    }
+    return win_rate - (1.0 - win_rate) / payoff_ratio;
+}
+
+/// Computes quantity of new trades. Indicators used by the sizer are updated on every bar.
+pub struct PositionSizer {
+    pub config: StrategyPositionSizer,
+    pub ctx: Context,
+    atr: Option<Atr>,
+    stdev: Option<Stdev>,
+    prev_close: Option<f64>,
+    /// Last value of the sizer indicator: ATR or stdev of returns.
+    value: Option<f64>,
+}
+
+impl PositionSizer {
+    pub fn new(ctx: Context, config: StrategyPositionSizer) -> Self {
+        let (atr, stdev) = match config {
+            StrategyPositionSizer::Atr { length, .. } => {
+                (Some(Atr::new(ctx.clone(), length)), None)
+            }
+            StrategyPositionSizer::VolatilityTarget { length, .. } => {
+                (None, Some(Stdev::new(ctx.clone(), length, false)))
+            }
+            _ => (None, None),
+        };
+        return Self {
+            ctx: ctx.clone(),
+            config,
+            atr,
+            stdev,
+            prev_close: None,
+            value: None,
+        };
+    }
+
+    /// Quantity of a new trade, not limited by buying power. `price` must include the point value.
+    ///
+    /// Returns `None` if the sizer indicator is not available yet.
+    pub fn size(&self, equity: f64, price: f64, point_value: f64, trades: &[Trade]) -> Option<f64> {
+        if equity <= 0.0 || price <= 0.0 {
+            return Some(0.0);
+        }
+        return match self.config {
+            StrategyPositionSizer::FixedFractional {
+                risk,
+                stop_distance,
+            } => Some(Self::risk_size(equity, risk, stop_distance * point_value)),
+            StrategyPositionSizer::Atr {
+                risk, multiplier, ..
+            } => self
+                .value
+                .map(|atr| Self::risk_size(equity, risk, atr * multiplier * point_value)),
+            StrategyPositionSizer::VolatilityTarget { target, .. } => {
+                self.value.map(|volatility| {
+                    if volatility <= 0.0 {
+                        return 0.0;
+                    }
+                    return equity * target / volatility / price;
+                })
+            }
+            StrategyPositionSizer::Kelly {
+                fraction,
+                max_fraction,
+                min_trades,
+            } => {
+                let closed_trades: Vec<&Trade> =
+                    trades.iter().filter(|trade| trade.is_closed).collect();
+
+                let kelly = if closed_trades.len() < min_trades {
+                    max_fraction
+                } else {
+                    let wins: Vec<f64> = closed_trades
+                        .iter()
+                        .filter(|trade| trade.pnl > 0.0)
+                        .map(|trade| trade.pnl)
+                        .collect();
+                    let losses: Vec<f64> = closed_trades
+                        .iter()
+                        .filter(|trade| trade.pnl < 0.0)
+                        .map(|trade| -trade.pnl)
+                        .collect();
+
+                    if losses.is_empty() {
+                        max_fraction
+                    } else if wins.is_empty() {
+                        0.0
+                    } else {
+                        let win_rate = wins.len() as f64 / closed_trades.len() as f64;
+                        let avg_win = wins.iter().sum::<f64>() / wins.len() as f64;
+                        let avg_loss = losses.iter().sum::<f64>() / losses.len() as f64;
+                        f64::min(
+                            kelly_fraction(win_rate, avg_win / avg_loss) * fraction,
+                            max_fraction,
+                        )
+                    }
+                };
+
+                Some(f64::max(kelly, 0.0) * equity / price)
+            }
+        };
+    }
+
+    fn risk_size(equity: f64, risk: f64, stop_distance: f64) -> f64 {
+        if stop_distance <= 0.0 {
+            return 0.0;
+        }
+        return equity * risk / stop_distance;
+    }
+}
+
+impl Incremental<(), ()> for PositionSizer {
+    fn next(&mut self, _: ()) {
+        if let Some(atr) = &mut self.atr {
+            self.value = atr.next(());
+        }
+
+        if let Some(stdev) = &mut self.stdev {
+            let close = self.ctx.bar.close();
+            let returns = match (self.prev_close, close) {
+                (Some(prev_close), Some(close)) if prev_close != 0.0 => {
+                    Some(close / prev_close - 1.0)
+                }
+                _ => None,
+            };
+            self.prev_close = close;
+            self.value = stdev.next(returns);
+        }
+    }
+}
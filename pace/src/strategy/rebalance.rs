@@ -0,0 +1,286 @@
+use std::sync::Arc;
+
+use chrono::{Datelike, NaiveDateTime};
+
+use crate::core::{context::Context, data_provider::DataProvider, incremental::Incremental};
+
+use super::execution::StrategyCommission;
+
+/// Calendar or bar-count rebalancing period.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RebalanceSchedule {
+    /// Every `n` bars.
+    Bars(usize),
+    /// On the first bar of every day. Requires bar time.
+    Daily,
+    /// On the first bar of every ISO week. Requires bar time.
+    Weekly,
+    /// On the first bar of every month. Requires bar time.
+    Monthly,
+}
+
+impl RebalanceSchedule {
+    /// Returns `true` if a new period started since the last rebalance.
+    pub fn is_due(
+        &self,
+        last: Option<(usize, Option<NaiveDateTime>)>,
+        tick: usize,
+        datetime: Option<NaiveDateTime>,
+    ) -> bool {
+        let (last_tick, last_datetime) = match last {
+            Some(last) => last,
+            None => return true,
+        };
+        if let RebalanceSchedule::Bars(bars) = self {
+            return tick >= last_tick + bars;
+        }
+        let (last_datetime, datetime) = match (last_datetime, datetime) {
+            (Some(last_datetime), Some(datetime)) => (last_datetime, datetime),
+            _ => return false,
+        };
+        return match self {
+            RebalanceSchedule::Daily => datetime.date() != last_datetime.date(),
+            RebalanceSchedule::Weekly => datetime.iso_week() != last_datetime.iso_week(),
+            RebalanceSchedule::Monthly => {
+                (datetime.year(), datetime.month()) != (last_datetime.year(), last_datetime.month())
+            }
+            RebalanceSchedule::Bars(_) => unreachable!(),
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RebalancerConfig {
+    pub initial_capital: f64,
+    /// Target weight of every instrument, e.g. `0.6` = `60%` of equity. Negative weight means a short position.
+    ///
+    /// Replaced by weights passed to `Rebalancer::next`.
+    pub weights: Vec<f64>,
+    /// Rebalances on the first bar of every period. If both `schedule` and `drift_threshold` are `None`, rebalances on every bar.
+    pub schedule: Option<RebalanceSchedule>,
+    /// Rebalances once weight of any instrument differs from its target by more than this fraction, e.g. `0.05`.
+    pub drift_threshold: Option<f64>,
+    /// Commission of every trade. If `None`, rebalancing is free.
+    pub commission: Option<StrategyCommission>,
+}
+
+impl Default for RebalancerConfig {
+    fn default() -> Self {
+        return Self {
+            initial_capital: 1000.0,
+            weights: Vec::new(),
+            schedule: None,
+            drift_threshold: None,
+            commission: None,
+        };
+    }
+}
+
+/// Single rebalance.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RebalanceEvent {
+    pub tick: usize,
+    /// Equity before the rebalance.
+    pub equity: f64,
+    /// Value of all trades.
+    pub traded_value: f64,
+    /// `traded_value` as a fraction of `equity`.
+    pub turnover: f64,
+    pub cost: f64,
+}
+
+pub struct RebalanceMetrics {
+    /// Cash plus value of all positions.
+    pub equity: f64,
+    pub net_profit: f64,
+    /// Total value of all trades.
+    pub traded_value: f64,
+    /// Sum of turnover of all rebalances.
+    pub turnover: f64,
+    /// Total commission paid.
+    pub costs: f64,
+    pub rebalances: usize,
+}
+
+impl RebalanceMetrics {
+    pub fn default(initial_capital: f64) -> Self {
+        return Self {
+            equity: initial_capital,
+            net_profit: 0.0,
+            traded_value: 0.0,
+            turnover: 0.0,
+            costs: 0.0,
+            rebalances: 0,
+        };
+    }
+}
+
+/// Holds several instruments at target weights, rebalancing on a schedule or when weights drift.
+///
+/// Instruments must be aligned with `ctx`, i.e. bar `i` of every instrument has the same time as bar `i` of `ctx`.
+/// Trades are filled at the close of the bar on which the rebalance happens.
+pub struct Rebalancer {
+    pub ctx: Context,
+    pub config: RebalancerConfig,
+    pub instruments: Vec<Arc<dyn DataProvider + Send + Sync>>,
+    pub cash: f64,
+    /// Quantity held of every instrument. Negative quantity means a short position.
+    pub positions: Vec<f64>,
+    /// Last known close of every instrument.
+    pub prices: Vec<Option<f64>>,
+    pub target_weights: Vec<f64>,
+    pub events: Vec<RebalanceEvent>,
+    pub metrics: RebalanceMetrics,
+    last_rebalance: Option<(usize, Option<NaiveDateTime>)>,
+}
+
+impl Rebalancer {
+    pub fn new(
+        ctx: Context,
+        instruments: Vec<Arc<dyn DataProvider + Send + Sync>>,
+        config: RebalancerConfig,
+    ) -> Self {
+        let count = instruments.len();
+        let mut target_weights = config.weights.clone();
+        target_weights.resize(count, 0.0);
+        return Self {
+            ctx: ctx.clone(),
+            cash: config.initial_capital,
+            positions: vec![0.0; count],
+            prices: vec![None; count],
+            target_weights,
+            events: Vec::new(),
+            metrics: RebalanceMetrics::default(config.initial_capital),
+            last_rebalance: None,
+            instruments,
+            config,
+        };
+    }
+
+    pub fn equity(&self) -> f64 {
+        return self.cash
+            + self
+                .positions
+                .iter()
+                .zip(self.prices.iter())
+                .map(|(position, price)| position * price.unwrap_or(0.0))
+                .sum::<f64>();
+    }
+
+    /// Current weight of every instrument.
+    pub fn weights(&self) -> Vec<f64> {
+        let equity = self.equity();
+        return self
+            .positions
+            .iter()
+            .zip(self.prices.iter())
+            .map(|(position, price)| {
+                if equity == 0.0 {
+                    return 0.0;
+                }
+                return position * price.unwrap_or(0.0) / equity;
+            })
+            .collect();
+    }
+
+    fn is_drifted(&self) -> bool {
+        let threshold = match self.config.drift_threshold {
+            Some(threshold) => threshold,
+            None => return false,
+        };
+        return self
+            .weights()
+            .iter()
+            .zip(self.target_weights.iter())
+            .any(|(weight, target)| (weight - target).abs() > threshold);
+    }
+
+    fn is_due(&self, tick: usize, datetime: Option<NaiveDateTime>) -> bool {
+        if self.last_rebalance.is_none() {
+            return true;
+        }
+        if self.config.schedule.is_none() && self.config.drift_threshold.is_none() {
+            return true;
+        }
+        if let Some(schedule) = &self.config.schedule {
+            if schedule.is_due(self.last_rebalance, tick, datetime) {
+                return true;
+            }
+        }
+        return self.is_drifted();
+    }
+
+    /// Trades every instrument with known price to its target weight.
+    fn rebalance(&mut self, tick: usize, datetime: Option<NaiveDateTime>) {
+        let equity = self.equity();
+        let mut traded_value = 0.0;
+        let mut cost = 0.0;
+
+        for i in 0..self.positions.len() {
+            let price = match self.prices[i] {
+                Some(price) if price > 0.0 => price,
+                _ => continue,
+            };
+            let target = self.target_weights[i] * equity / price;
+            let quantity = target - self.positions[i];
+
+            if quantity == 0.0 {
+                continue;
+            }
+
+            let commission = self.config.commission.map_or(0.0, |commission| {
+                commission.amount(quantity.abs(), price, 1.0)
+            });
+
+            self.positions[i] = target;
+            self.cash -= quantity * price + commission;
+            traded_value += quantity.abs() * price;
+            cost += commission;
+        }
+
+        let turnover = if equity != 0.0 {
+            traded_value / equity
+        } else {
+            0.0
+        };
+
+        self.events.push(RebalanceEvent {
+            tick,
+            equity,
+            traded_value,
+            turnover,
+            cost,
+        });
+        self.metrics.traded_value += traded_value;
+        self.metrics.turnover += turnover;
+        self.metrics.costs += cost;
+        self.metrics.rebalances += 1;
+        self.last_rebalance = Some((tick, datetime));
+    }
+}
+
+/// Accepts new target weights, one per instrument. If `None`, previous targets are kept.
+impl Incremental<Option<Vec<f64>>, ()> for Rebalancer {
+    fn next(&mut self, weights: Option<Vec<f64>>) {
+        let tick = self.ctx.bar.index();
+        let datetime = self.ctx.bar.datetime();
+
+        for (i, instrument) in self.instruments.iter().enumerate() {
+            if let Some(price) = instrument.get_close(tick) {
+                self.prices[i] = Some(price);
+            }
+        }
+
+        if let Some(mut weights) = weights {
+            weights.resize(self.instruments.len(), 0.0);
+            self.target_weights = weights;
+        }
+
+        if self.is_due(tick, datetime) {
+            self.rebalance(tick, datetime);
+        }
+
+        self.metrics.equity = self.equity();
+        self.metrics.net_profit = self.metrics.equity - self.config.initial_capital;
+    }
+}
@@ -0,0 +1,203 @@
+use chrono::NaiveDate;
+
+/// Risk limit expressed either in cash or as a fraction of equity.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StrategyRiskValue {
+    /// Amount in account currency. Same as PineScript `strategy.cash`.
+    Cash(f64),
+    /// Fraction of equity, e.g. `0.1` = `10%`. Same as PineScript `strategy.percent_of_equity`.
+    Percent(f64),
+}
+
+impl StrategyRiskValue {
+    /// Returns `true` if `loss` relative to `reference_equity` reached the limit.
+    pub fn is_reached(&self, loss: f64, reference_equity: f64) -> bool {
+        return match self {
+            StrategyRiskValue::Cash(value) => loss >= *value,
+            StrategyRiskValue::Percent(value) => {
+                reference_equity > 0.0 && loss / reference_equity >= *value
+            }
+        };
+    }
+}
+
+/// Rules evaluated on every `Strategy` step. All rules are disabled by default.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StrategyRiskConfig {
+    /// Closes the position and stops trading permanently, once equity falls this far below its highest value.
+    ///
+    /// Same as PineScript `strategy.risk.max_drawdown`.
+    pub max_drawdown: Option<StrategyRiskValue>,
+    /// Closes the position and stops trading until the next day, once equity falls this far below its value at the start of the day.
+    ///
+    /// Same as PineScript `strategy.risk.max_intraday_loss`.
+    pub max_intraday_loss: Option<StrategyRiskValue>,
+    /// Closes the position and stops trading permanently after this many losing days in a row.
+    ///
+    /// Same as PineScript `strategy.risk.max_cons_loss_days`.
+    pub max_cons_loss_days: Option<usize>,
+    /// Maximum quantity of a single position. Larger entries are reduced to this size.
+    ///
+    /// Same as PineScript `strategy.risk.max_position_size`.
+    pub max_position_size: Option<f64>,
+    /// Closes the position and stops trading until the next day, once this many orders were filled during the day.
+    ///
+    /// Same as PineScript `strategy.risk.max_intraday_filled_orders`.
+    pub max_intraday_filled_orders: Option<usize>,
+    /// Number of bars during which entries are blocked after a losing trade.
+    pub loss_cooldown: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StrategyRiskRule {
+    MaxDrawdown,
+    MaxIntradayLoss,
+    MaxConsLossDays,
+    MaxPositionSize,
+    MaxIntradayFilledOrders,
+    LossCooldown,
+}
+
+/// Tracks state of risk rules. Intraday rules require bar time, otherwise they are ignored.
+pub struct StrategyRisk {
+    pub config: StrategyRiskConfig,
+    /// Rule that stopped trading permanently.
+    pub halted_by: Option<StrategyRiskRule>,
+    /// Rule that stopped trading until the next day.
+    pub halted_today_by: Option<StrategyRiskRule>,
+    pub equity_max: f64,
+    pub day: Option<NaiveDate>,
+    pub day_start_equity: f64,
+    pub intraday_filled_orders: usize,
+    pub cons_loss_days: usize,
+    /// Entries are blocked until this bar index (exclusive).
+    pub cooldown_until: Option<usize>,
+}
+
+impl StrategyRisk {
+    pub fn new(config: StrategyRiskConfig, initial_capital: f64) -> Self {
+        return Self {
+            config,
+            halted_by: None,
+            halted_today_by: None,
+            equity_max: initial_capital,
+            day: None,
+            day_start_equity: initial_capital,
+            intraday_filled_orders: 0,
+            cons_loss_days: 0,
+            cooldown_until: None,
+        };
+    }
+
+    /// Must be called at the beginning of every bar, with equity at the end of the previous bar.
+    ///
+    /// Returns a rule if consecutive losing days limit was reached.
+    pub fn next_bar(&mut self, date: Option<NaiveDate>, equity: f64) -> Option<StrategyRiskRule> {
+        let date = date?;
+
+        if self.day == Some(date) {
+            return None;
+        }
+
+        let is_first_day = self.day.is_none();
+
+        self.day = Some(date);
+        self.halted_today_by = None;
+        self.intraday_filled_orders = 0;
+
+        if !is_first_day {
+            if equity < self.day_start_equity {
+                self.cons_loss_days += 1;
+            } else {
+                self.cons_loss_days = 0;
+            }
+        }
+
+        self.day_start_equity = equity;
+
+        if let Some(max_cons_loss_days) = self.config.max_cons_loss_days {
+            if self.cons_loss_days >= max_cons_loss_days && self.halted_by.is_none() {
+                self.halted_by = Some(StrategyRiskRule::MaxConsLossDays);
+                return self.halted_by;
+            }
+        }
+
+        return None;
+    }
+
+    /// Returns a rule that currently blocks new entries.
+    pub fn entry_blocked_by(&self, tick: usize) -> Option<StrategyRiskRule> {
+        if self.halted_by.is_some() {
+            return self.halted_by;
+        }
+        if self.halted_today_by.is_some() {
+            return self.halted_today_by;
+        }
+        if let Some(cooldown_until) = self.cooldown_until {
+            if tick < cooldown_until {
+                return Some(StrategyRiskRule::LossCooldown);
+            }
+        }
+        return None;
+    }
+
+    /// Reduces position size to `max_position_size`. Returns the rule if the size was reduced.
+    pub fn limit_size(&self, size: f64) -> (f64, Option<StrategyRiskRule>) {
+        if let Some(max_position_size) = self.config.max_position_size {
+            if size > max_position_size {
+                return (max_position_size, Some(StrategyRiskRule::MaxPositionSize));
+            }
+        }
+        return (size, None);
+    }
+
+    /// Must be called after every filled order.
+    pub fn on_fill(&mut self) {
+        self.intraday_filled_orders += 1;
+    }
+
+    /// Must be called after a trade is closed.
+    pub fn on_trade_exit(&mut self, tick: usize, pnl: f64) {
+        if pnl < 0.0 {
+            if let Some(loss_cooldown) = self.config.loss_cooldown {
+                self.cooldown_until = Some(tick + loss_cooldown + 1);
+            }
+        }
+    }
+
+    /// Must be called at the end of every bar. Returns a rule that requires closing the position.
+    pub fn check(&mut self, equity: f64) -> Option<StrategyRiskRule> {
+        self.equity_max = f64::max(self.equity_max, equity);
+
+        if self.halted_by.is_some() || self.halted_today_by.is_some() {
+            return None;
+        }
+
+        if let Some(max_drawdown) = self.config.max_drawdown {
+            if max_drawdown.is_reached(self.equity_max - equity, self.equity_max) {
+                self.halted_by = Some(StrategyRiskRule::MaxDrawdown);
+                return self.halted_by;
+            }
+        }
+
+        if self.day.is_some() {
+            if let Some(max_intraday_loss) = self.config.max_intraday_loss {
+                if max_intraday_loss
+                    .is_reached(self.day_start_equity - equity, self.day_start_equity)
+                {
+                    self.halted_today_by = Some(StrategyRiskRule::MaxIntradayLoss);
+                    return self.halted_today_by;
+                }
+            }
+
+            if let Some(max_intraday_filled_orders) = self.config.max_intraday_filled_orders {
+                if self.intraday_filled_orders >= max_intraday_filled_orders {
+                    self.halted_today_by = Some(StrategyRiskRule::MaxIntradayFilledOrders);
+                    return self.halted_today_by;
+                }
+            }
+        }
+
+        return None;
+    }
+}
@@ -0,0 +1,88 @@
+use super::trade::TradeDirection;
+
+/// Order signal accepted by `Strategy`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StrategySignal {
+    /// No action.
+    Hold,
+    /// Enters long position. Reverses short position, if `StrategyConfig::continous` is enabled.
+    Long,
+    /// Enters short position. Reverses long position, if `StrategyConfig::continous` is enabled.
+    Short,
+    /// Closes any open position.
+    Exit,
+    /// Closes long position. Same as PineScript `strategy.close("Long")`.
+    ExitLong,
+    /// Closes short position. Same as PineScript `strategy.close("Short")`.
+    ExitShort,
+}
+
+impl StrategySignal {
+    /// Returns direction of an entry signal.
+    pub fn direction(&self) -> Option<TradeDirection> {
+        return match self {
+            StrategySignal::Long => Some(TradeDirection::Long),
+            StrategySignal::Short => Some(TradeDirection::Short),
+            _ => None,
+        };
+    }
+
+    /// Converts an entry signal into an exit of the opposite position.
+    pub fn without_entry(&self) -> Self {
+        return match self {
+            StrategySignal::Long => StrategySignal::ExitShort,
+            StrategySignal::Short => StrategySignal::ExitLong,
+            _ => *self,
+        };
+    }
+
+    /// Returns `true` if the signal closes a position in given direction.
+    pub fn closes(&self, direction: TradeDirection) -> bool {
+        return match self {
+            StrategySignal::Exit => true,
+            StrategySignal::ExitLong => direction == TradeDirection::Long,
+            StrategySignal::ExitShort => direction == TradeDirection::Short,
+            _ => false,
+        };
+    }
+}
+
+impl From<Option<TradeDirection>> for StrategySignal {
+    fn from(direction: Option<TradeDirection>) -> Self {
+        return match direction {
+            Some(TradeDirection::Long) => StrategySignal::Long,
+            Some(TradeDirection::Short) => StrategySignal::Short,
+            None => StrategySignal::Hold,
+        };
+    }
+}
+
+/// Directions in which entries are allowed.
+///
+/// Same as PineScript `strategy.direction`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StrategyDirection {
+    All,
+    Long,
+    Short,
+}
+
+impl StrategyDirection {
+    pub fn allows(&self, direction: TradeDirection) -> bool {
+        return match self {
+            StrategyDirection::All => true,
+            StrategyDirection::Long => direction == TradeDirection::Long,
+            StrategyDirection::Short => direction == TradeDirection::Short,
+        };
+    }
+
+    /// Entries in a disallowed direction close the opposite position instead.
+    ///
+    /// Same as PineScript `strategy.risk.allow_entry_in`.
+    pub fn restrict(&self, signal: StrategySignal) -> StrategySignal {
+        return match signal.direction() {
+            Some(direction) if !self.allows(direction) => signal.without_entry(),
+            _ => signal,
+        };
+    }
+}
@@ -1,11 +1,29 @@
 use std::{
     cell::{Ref, RefCell, RefMut},
     rc::Rc,
+    time::Duration,
 };
 
-use crate::core::{context::Context, incremental::Incremental};
+use rust_decimal::prelude::ToPrimitive;
 
-use super::trade::{fill_size, Trade, TradeDirection};
+use crate::core::{
+    context::Context,
+    error::{PaceError, PaceResult},
+    incremental::Incremental,
+};
+
+use super::{
+    bar_magnifier::{intrabar_path, BarMagnifier},
+    execution::StrategyExecutionConfig,
+    latency::StrategyLatency,
+    ledger::{to_decimal, Ledger},
+    order::{StrategyEntryOrder, StrategyExitOrder},
+    position_sizer::{PositionSizer, StrategyPositionSizer},
+    risk::{StrategyRisk, StrategyRiskConfig, StrategyRiskRule},
+    signal::{StrategyDirection, StrategySignal},
+    trade::{fill_size, Trade, TradeDirection},
+    trade_list::TradeList,
+};
 
 pub struct StrategyOnTradeEntryEvent {
     pub trade: Trade,
@@ -15,12 +33,145 @@ pub struct StrategyOnTradeExitEvent {
     pub trade: Trade,
 }
 
+pub struct StrategyOnMarginCallEvent {
+    /// Liquidated part of the trade.
+    pub trade: Trade,
+    /// Price below (long) or above (short) which equity no longer covers the margin.
+    pub liquidation_price: f64,
+}
+
+pub struct StrategyOnRiskRuleEvent {
+    pub rule: StrategyRiskRule,
+    /// Trade closed by the rule, if any.
+    pub trade: Option<Trade>,
+}
+
 pub struct StrategyEvents {
     pub on_trade_entry: Option<StrategyOnTradeEntryEvent>,
     pub on_trade_exit: Option<StrategyOnTradeExitEvent>,
+    pub on_margin_call: Option<StrategyOnMarginCallEvent>,
+    /// Emitted when a risk rule closes a position, blocks an entry or reduces its size.
+    pub on_risk_rule: Option<StrategyOnRiskRuleEvent>,
 }
 
+/// Margin requirements. Values are fractions of position value, e.g. `0.1` = `10%`.
+///
+/// Same as PineScript: if equity falls below the margin of the open position, there is a margin call that liquidates 4 times the contracts needed to cover the shortfall.
 #[derive(Clone, Copy, Debug)]
+pub struct StrategyMarginConfig {
+    /// Margin required for a long position. Leverage is `1 / margin_long`.
+    ///
+    /// Similar to PineScript `margin_long`, but as a fraction instead of percent.
+    pub margin_long: f64,
+    /// Margin required for a short position. Leverage is `1 / margin_short`.
+    ///
+    /// Similar to PineScript `margin_short`, but as a fraction instead of percent.
+    pub margin_short: f64,
+}
+
+impl Default for StrategyMarginConfig {
+    fn default() -> Self {
+        return Self {
+            margin_long: 1.0,
+            margin_short: 1.0,
+        };
+    }
+}
+
+impl StrategyMarginConfig {
+    /// Returns an error if a margin is not positive.
+    pub fn validate(&self) -> PaceResult<()> {
+        for (name, margin) in [
+            ("margin_long", self.margin_long),
+            ("margin_short", self.margin_short),
+        ] {
+            if !margin.is_finite() || margin <= 0.0 {
+                return Err(PaceError::InvalidData(format!(
+                    "Invalid {} {}, expected a positive margin",
+                    name, margin
+                )));
+            }
+        }
+        return Ok(());
+    }
+
+    pub fn margin(&self, direction: TradeDirection) -> f64 {
+        return match direction {
+            TradeDirection::Long => self.margin_long,
+            TradeDirection::Short => self.margin_short,
+        };
+    }
+
+    pub fn leverage(&self, direction: TradeDirection) -> f64 {
+        return 1.0 / self.margin(direction);
+    }
+}
+
+/// When funding of an open position is charged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StrategyFundingInterval {
+    /// Every this many bars. `Bars(1)` charges funding on every bar.
+    Bars(usize),
+    /// At every multiple of this duration since epoch, e.g. 8 hours for perpetual swaps funded at 00:00, 08:00 and 16:00 UTC.
+    ///
+    /// Funding is charged on the first bar opened at or after the funding time, once per funding time it crossed. Bars without time are not charged.
+    Time(Duration),
+}
+
+/// Carrying costs of an open position, like perpetual swap funding, short borrow fees or overnight swaps.
+///
+/// Rates are fractions of position value, e.g. `0.0001` = `0.01%`. Positive rate is a cost, negative rate is an income.
+#[derive(Clone, Debug)]
+pub struct StrategyFundingConfig {
+    /// Rate charged on a long position every funding period.
+    pub long_rate: f64,
+    /// Rate charged on a short position every funding period.
+    pub short_rate: f64,
+    pub interval: StrategyFundingInterval,
+    /// Auxiliary series with the perpetual funding rate of each bar, e.g. `"funding_rate"`. If set, it overrides `long_rate` and `short_rate`, so longs pay the rate and shorts receive it. Bars without a value are not charged.
+    pub rate_series: Option<String>,
+}
+
+impl Default for StrategyFundingConfig {
+    fn default() -> Self {
+        return Self {
+            long_rate: 0.0,
+            short_rate: 0.0,
+            interval: StrategyFundingInterval::Bars(1),
+            rate_series: None,
+        };
+    }
+}
+
+impl StrategyFundingConfig {
+    /// Perpetual swap funding, where longs pay `rate` to shorts. Negative `rate` means shorts pay longs.
+    pub fn perpetual(rate: f64, interval: StrategyFundingInterval) -> Self {
+        return Self {
+            long_rate: rate,
+            short_rate: -rate,
+            interval,
+            ..Self::default()
+        };
+    }
+
+    /// Perpetual swap funding with rates from auxiliary series `name`.
+    pub fn perpetual_series(name: &str, interval: StrategyFundingInterval) -> Self {
+        return Self {
+            interval,
+            rate_series: Some(name.to_string()),
+            ..Self::default()
+        };
+    }
+
+    pub fn rate(&self, direction: TradeDirection) -> f64 {
+        return match direction {
+            TradeDirection::Long => self.long_rate,
+            TradeDirection::Short => self.short_rate,
+        };
+    }
+}
+
+#[derive(Clone, Debug)]
 pub struct StrategyConfig {
     /**
     Enables an additional calculation on bar close, allowing market orders to enter on the same tick the order is placed
@@ -29,6 +180,20 @@ pub struct StrategyConfig {
     pub continous: bool,
     pub initial_capital: f64,
     pub buy_with_equity: bool,
+    /// Enables leverage and forced liquidation. If `None`, equity is allowed to go negative.
+    pub margin: Option<StrategyMarginConfig>,
+    /// Accrues carrying costs into open profit and trade pnl. If `None`, holding a position is free.
+    pub funding: Option<StrategyFundingConfig>,
+    /// Same as PineScript `strategy.risk.allow_entry_in`.
+    pub allow_entry_in: StrategyDirection,
+    /// Same as PineScript `strategy.risk.*` rules.
+    pub risk: StrategyRiskConfig,
+    /// Computes quantity of new trades. If set, `buy_with_equity` is ignored and quantity is limited by buying power.
+    pub position_sizer: Option<StrategyPositionSizer>,
+    /// Volume participation limit, market impact and commission of orders.
+    pub execution: StrategyExecutionConfig,
+    /// Delays signals, exit orders and their cancellations. If `None`, orders are submitted on the bar they were created.
+    pub latency: Option<StrategyLatency>,
 }
 
 impl Default for StrategyConfig {
@@ -38,6 +203,13 @@ impl Default for StrategyConfig {
             continous: true,
             on_bar_close: false,
             initial_capital: 1000.0,
+            margin: None,
+            funding: None,
+            allow_entry_in: StrategyDirection::All,
+            risk: StrategyRiskConfig::default(),
+            position_sizer: None,
+            execution: StrategyExecutionConfig::default(),
+            latency: None,
         };
     }
 }
@@ -63,6 +235,10 @@ pub struct StrategyMetrics {
     pub losing_trades: usize,
     pub long_net_profit: f64,
     pub short_net_profit: f64,
+    /// Total number of trades closed by forced liquidation.
+    pub liquidations: usize,
+    /// Total funding of all trades, including the open one. Negative value means funding was paid.
+    pub net_funding: f64,
 }
 
 impl StrategyMetrics {
@@ -78,140 +254,1150 @@ impl StrategyMetrics {
             winning_trades: 0,
             long_net_profit: 0.0,
             short_net_profit: 0.0,
+            liquidations: 0,
+            net_funding: 0.0,
         };
     }
 }
 
+enum StrategyOrderAction {
+    Signal(StrategySignal),
+    SetExit(StrategyExitOrder),
+    CancelExit,
+    PlaceEntry(StrategyEntryOrder),
+    CancelEntry(String),
+}
+
+/// Order action waiting for submission.
+struct StrategyDelayedOrder {
+    /// Tick on which the action is submitted.
+    tick: usize,
+    /// Time of an intrabar fill.
+    time: Option<Duration>,
+    action: StrategyOrderAction,
+}
+
 /// Manages trades and provides data for all strategy components.
 pub struct Strategy {
     pub ctx: Context,
     pub config: StrategyConfig,
     pub trades: Vec<Trade>,
-    pub unfilled_trade_direction: Option<TradeDirection>,
+    pub unfilled_signal: StrategySignal,
     pub events: StrategyEvents,
     pub metrics: StrategyMetrics,
+    pub risk: StrategyRisk,
+    pub position_sizer: Option<PositionSizer>,
+    /// Pending exit order. Same as PineScript `strategy.exit`.
+    pub exit_order: Option<StrategyExitOrder>,
+    /// Pending entry orders. Same as PineScript `strategy.entry` with `stop` or `limit`.
+    pub entry_orders: Vec<StrategyEntryOrder>,
+    /// If set, intrabar fills are resolved with lower-timeframe data instead of assumed price path.
+    pub bar_magnifier: Option<BarMagnifier>,
+    /// Every cash movement of the strategy in decimal precision, with a snapshot at the end of every bar.
+    pub ledger: Ledger,
+    /// Index of the trade protected by `exit_order`.
+    exit_order_trade: Option<usize>,
+    /// `true` if `unfilled_signal` was not fully filled due to volume limit and is retried on the next bar.
+    is_signal_carried: bool,
+    /// Time of an intrabar fill of `unfilled_signal`, if it was delayed by `StrategyLatency::Time`.
+    unfilled_signal_time: Option<Duration>,
+    /// Start of the current bar price path, if the position was opened within the bar.
+    intrabar_start_time: Option<Duration>,
+    delayed_orders: Vec<StrategyDelayedOrder>,
+    /// Ids of stop-limit entry orders, whose stop was reached, so their limit is active.
+    triggered_entries: Vec<String>,
+    pending_entry_id: Option<(String, Option<String>)>,
+    pending_exit_id: Option<(String, Option<String>)>,
 }
 
 impl Strategy {
+    /// Same as `new`, but returns an error if `config.margin` is invalid. See `StrategyMarginConfig::validate`.
+    pub fn try_new(ctx: Context, config: StrategyConfig) -> PaceResult<Self> {
+        if let Some(margin) = &config.margin {
+            margin.validate()?;
+        }
+        return Ok(Self::new(ctx, config));
+    }
+
+    /// Doesn't validate `config`. Use `try_new` for configuration provided by the user.
     pub fn new(ctx: Context, config: StrategyConfig) -> Self {
         return Self {
             ctx: ctx.clone(),
             trades: Vec::new(),
-            unfilled_trade_direction: None,
+            unfilled_signal: StrategySignal::Hold,
             events: StrategyEvents {
                 on_trade_entry: None,
                 on_trade_exit: None,
+                on_margin_call: None,
+                on_risk_rule: None,
             },
             metrics: StrategyMetrics::default(config.initial_capital),
+            risk: StrategyRisk::new(config.risk, config.initial_capital),
+            position_sizer: config
+                .position_sizer
+                .map(|sizer| PositionSizer::new(ctx.clone(), sizer)),
+            pending_entry_id: None,
+            exit_order: None,
+            entry_orders: Vec::new(),
+            bar_magnifier: None,
+            ledger: Ledger::new(config.initial_capital),
+            exit_order_trade: None,
+            is_signal_carried: false,
+            unfilled_signal_time: None,
+            intrabar_start_time: None,
+            delayed_orders: Vec::new(),
+            triggered_entries: Vec::new(),
+            pending_exit_id: None,
             config,
         };
     }
 }
 
+impl Strategy {
+    /// Same as PineScript `strategy.opentrades`.
+    pub fn opentrades(&self) -> TradeList<'_> {
+        return TradeList::new(
+            self.trades
+                .iter()
+                .filter(|trade| !trade.is_closed)
+                .collect(),
+            self.current_price(),
+        );
+    }
+
+    /// Same as PineScript `strategy.closedtrades`.
+    pub fn closedtrades(&self) -> TradeList<'_> {
+        return TradeList::new(
+            self.trades.iter().filter(|trade| trade.is_closed).collect(),
+            self.current_price(),
+        );
+    }
+
+    /// Close of the current bar, or of the last bar once the context was fully iterated.
+    fn current_price(&self) -> Option<f64> {
+        let tick = usize::min(self.ctx.bar.index(), self.ctx.last_bar_index());
+        return self.ctx.data.get_close(tick);
+    }
+
+    /// Signed quantity of the open position. Positive for long, negative for short and `0.0` if flat.
+    ///
+    /// Same as PineScript `strategy.position_size`.
+    pub fn position_size(&self) -> f64 {
+        return self
+            .opentrades()
+            .iter()
+            .map(|trade| trade.signed_size())
+            .sum();
+    }
+
+    /// Average entry price of the open position.
+    ///
+    /// Same as PineScript `strategy.position_avg_price`.
+    pub fn position_avg_price(&self) -> Option<f64> {
+        let opentrades = self.opentrades();
+        let size: f64 = opentrades
+            .iter()
+            .map(|trade| trade.fill_size.unwrap())
+            .sum();
+        if size == 0.0 {
+            return None;
+        }
+        let cost: f64 = opentrades
+            .iter()
+            .map(|trade| trade.fill_size.unwrap() * trade.entry_price.unwrap())
+            .sum();
+        return Some(cost / size);
+    }
+
+    /// Same as PineScript `strategy.position_entry_name`.
+    pub fn position_entry_name(&self) -> Option<&str> {
+        return self
+            .opentrades()
+            .last()
+            .map(|trade| trade.entry_id.as_str());
+    }
+
+    /// Sets id and comment of the next filled entry. By default, id is the trade direction.
+    ///
+    /// Same as `id` and `comment` arguments of PineScript `strategy.entry`.
+    pub fn set_entry_id(&mut self, id: &str, comment: Option<&str>) {
+        self.pending_entry_id = Some((id.to_string(), comment.map(|x| x.to_string())));
+    }
+
+    /// Sets id and comment of the next filled exit.
+    ///
+    /// Same as `id` and `comment` arguments of PineScript `strategy.close`.
+    pub fn set_exit_id(&mut self, id: &str, comment: Option<&str>) {
+        self.pending_exit_id = Some((id.to_string(), comment.map(|x| x.to_string())));
+    }
+
+    /// Places an exit order, replacing the previous one. Order is active from the next bar, or later with `StrategyConfig::latency`.
+    ///
+    /// Same as PineScript `strategy.exit(id, stop=..., limit=...)`.
+    pub fn set_exit(&mut self, id: &str, stop: Option<f64>, limit: Option<f64>) {
+        self.delay_order(StrategyOrderAction::SetExit(StrategyExitOrder {
+            id: id.to_string(),
+            stop,
+            limit,
+        }));
+    }
+
+    /// Cancels the exit order from the next bar, or later with `StrategyConfig::latency`.
+    ///
+    /// Same as PineScript `strategy.cancel`.
+    pub fn cancel_exit(&mut self) {
+        self.delay_order(StrategyOrderAction::CancelExit);
+    }
+
+    /// Places an entry order, replacing the pending order with the same id. Order is active from the next bar, or later with `StrategyConfig::latency`.
+    ///
+    /// Same as PineScript `strategy.entry(id, direction, limit=..., stop=...)`.
+    pub fn place_entry(&mut self, order: StrategyEntryOrder) {
+        self.delay_order(StrategyOrderAction::PlaceEntry(order));
+    }
+
+    /// Cancels the pending entry order with `id`.
+    ///
+    /// Same as PineScript `strategy.cancel(id)`.
+    pub fn cancel_entry(&mut self, id: &str) {
+        self.delay_order(StrategyOrderAction::CancelEntry(id.to_string()));
+    }
+}
+
+impl Strategy {
+    /// Closes the last trade at `price` and updates metrics. `exit_id` is used if no id was set with `set_exit_id`.
+    fn close_last_trade(&mut self, price: f64, tick: usize, exit_id: &str) {
+        if self.exit_order_trade == Some(self.trades.len() - 1) {
+            self.exit_order = None;
+            self.exit_order_trade = None;
+        }
+
+        let last_trade = self.trades.last_mut().unwrap();
+        let commission = self.config.execution.commission(
+            last_trade.fill_size.unwrap(),
+            price,
+            last_trade.point_value,
+        );
+
+        self.ledger.fill(
+            tick,
+            -last_trade.signed_size(),
+            price,
+            last_trade.point_value,
+        );
+        self.ledger.fee(tick, commission);
+
+        last_trade.commission += commission;
+        last_trade.update_excursions(price, price);
+        last_trade.exit_price = Some(price);
+        last_trade.exit_tick = Some(tick);
+        last_trade.is_closed = true;
+        last_trade.pnl = last_trade.pnl(price);
+        let pnl = last_trade.pnl;
+
+        if last_trade.exit_id.is_none() {
+            last_trade.exit_id = Some(exit_id.to_string());
+        }
+        if let Some((id, comment)) = self.pending_exit_id.take() {
+            last_trade.exit_id = Some(id);
+            last_trade.exit_comment = comment;
+        }
+
+        self.events.on_trade_exit = Some(StrategyOnTradeExitEvent {
+            trade: last_trade.clone(),
+        });
+
+        self.metrics.net_profit += pnl;
+        self.metrics.open_profit = 0.0;
+
+        if pnl > 0.0 {
+            self.metrics.gross_profit += pnl;
+            self.metrics.winning_trades += 1;
+        } else if pnl < 0.0 {
+            self.metrics.gross_loss += pnl.abs();
+            self.metrics.losing_trades += 1;
+        }
+
+        if last_trade.direction == TradeDirection::Long {
+            self.metrics.long_net_profit += pnl;
+        } else {
+            self.metrics.short_net_profit += pnl;
+        }
+
+        self.metrics.closed_trades += 1;
+
+        self.risk.on_fill();
+        self.risk.on_trade_exit(tick, pnl);
+    }
+
+    fn delay_order(&mut self, action: StrategyOrderAction) {
+        let tick = self.ctx.bar.index();
+        let (tick, time) = match &self.config.latency {
+            Some(latency) => latency.schedule(
+                self.ctx.data.as_ref(),
+                tick,
+                self.config.on_bar_close,
+                self.bar_magnifier.is_some(),
+            ),
+            None => (tick, None),
+        };
+        self.delayed_orders
+            .push(StrategyDelayedOrder { tick, time, action });
+    }
+
+    /// Submits delayed orders scheduled before the current tick. Orders scheduled on the current tick are submitted if enabled for their kind.
+    fn submit_delayed_orders(&mut self, current_signals: bool, current_exits: bool) {
+        let tick = self.ctx.bar.index();
+        let (ready, delayed): (Vec<_>, Vec<_>) = std::mem::take(&mut self.delayed_orders)
+            .into_iter()
+            .partition(|order| {
+                let is_signal = matches!(order.action, StrategyOrderAction::Signal(_));
+                order.tick < tick
+                    || order.tick == tick
+                        && (is_signal && current_signals || !is_signal && current_exits)
+            });
+        self.delayed_orders = delayed;
+
+        for order in ready {
+            match order.action {
+                StrategyOrderAction::Signal(signal) => {
+                    // Hold keeps the unfilled remainder of the previous signal.
+                    if signal != StrategySignal::Hold || !self.is_signal_carried {
+                        self.unfilled_signal = signal;
+                        self.unfilled_signal_time = order.time;
+                        self.is_signal_carried = false;
+                    }
+                }
+                StrategyOrderAction::SetExit(exit_order) => {
+                    if self.exit_order.is_none() {
+                        self.exit_order_trade = None;
+                    }
+                    self.exit_order = Some(exit_order);
+                }
+                StrategyOrderAction::CancelExit => {
+                    self.exit_order = None;
+                    self.exit_order_trade = None;
+                }
+                StrategyOrderAction::PlaceEntry(entry_order) => {
+                    self.entry_orders.retain(|order| order.id != entry_order.id);
+                    self.triggered_entries.retain(|id| *id != entry_order.id);
+                    self.entry_orders.push(entry_order);
+                }
+                StrategyOrderAction::CancelEntry(id) => {
+                    self.entry_orders.retain(|order| order.id != id);
+                    self.triggered_entries
+                        .retain(|triggered_id| *triggered_id != id);
+                }
+            }
+        }
+    }
+
+    /// Opens a new trade at `price`. Entry id is taken from `entry_id` or `set_entry_id`.
+    fn open_trade(
+        &mut self,
+        direction: TradeDirection,
+        price: f64,
+        volume: Option<f64>,
+        fill_capacity: f64,
+        entry_id: Option<String>,
+    ) -> EntryFill {
+        let tick = self.ctx.bar.index();
+        let point_value = self.ctx.syminfo.point_value;
+        let equity =
+            self.config.initial_capital + self.metrics.net_profit + self.metrics.open_profit;
+        let leverage = self
+            .config
+            .margin
+            .map_or(1.0, |margin| margin.leverage(direction));
+
+        let mut size = 1.0;
+
+        if let Some(position_sizer) = &self.position_sizer {
+            // No entry until the sizer indicator is available.
+            match position_sizer.size(equity, price * point_value, point_value, &self.trades) {
+                Some(sized) => {
+                    size = f64::min(sized, fill_size(equity * leverage, price * point_value));
+                }
+                None => return EntryFill::NotSized,
+            }
+        } else if self.config.buy_with_equity {
+            size = fill_size(equity * leverage, price * point_value);
+        }
+
+        let (size, rule) = self.risk.limit_size(size);
+        let requested_size = self.ctx.syminfo.round_qty(size);
+
+        if let Some(rule) = rule {
+            self.events.on_risk_rule = Some(StrategyOnRiskRuleEvent { rule, trade: None });
+        }
+
+        // Rest of the entry larger than the volume limit is filled on subsequent bars.
+        let filled_size = if requested_size > fill_capacity {
+            self.ctx.syminfo.round_qty(fill_capacity)
+        } else {
+            requested_size
+        };
+
+        // Order is rejected if its quantity is below `syminfo.min_qty`.
+        if filled_size <= 0.0 && size > 0.0 {
+            if requested_size > 0.0 {
+                return EntryFill::Unfilled;
+            }
+            return EntryFill::Rejected;
+        }
+
+        let price = self
+            .ctx
+            .syminfo
+            .round_to_mintick(self.config.execution.fill_price(
+                price,
+                filled_size,
+                volume,
+                direction == TradeDirection::Long,
+            ));
+
+        let commission = self
+            .config
+            .execution
+            .commission(filled_size, price, point_value);
+
+        let mut trade = Trade::new(direction);
+        trade.point_value = point_value;
+        trade.unfilled_size = requested_size;
+        trade.commission = commission;
+        trade.add_fill(tick, price, filled_size);
+
+        self.ledger
+            .fill(tick, trade.signed_size(), price, point_value);
+        self.ledger.fee(tick, commission);
+
+        if let Some(entry_id) = entry_id {
+            trade.entry_id = entry_id;
+        } else if let Some((id, comment)) = self.pending_entry_id.take() {
+            trade.entry_id = id;
+            trade.entry_comment = comment;
+        }
+
+        self.risk.on_fill();
+        self.events.on_trade_entry = Some(StrategyOnTradeEntryEvent {
+            trade: trade.clone(),
+        });
+        self.trades.push(trade);
+
+        return EntryFill::Filled;
+    }
+
+    /// Closes `size` of the last trade at `price`. The rest stays open as a separate trade, same as PineScript partial exits.
+    fn close_last_trade_partially(&mut self, price: f64, tick: usize, exit_id: &str, size: f64) {
+        let is_exit_order_bound = self.exit_order_trade == Some(self.trades.len() - 1);
+        let exit_order = if is_exit_order_bound {
+            self.exit_order.take()
+        } else {
+            None
+        };
+
+        let last_trade = self.trades.last_mut().unwrap();
+        let mut remaining_trade = last_trade.clone();
+        let ratio = size / last_trade.fill_size.unwrap();
+
+        last_trade.fill_size = Some(size);
+        last_trade.funding *= ratio;
+        last_trade.commission *= ratio;
+        last_trade.max_drawdown *= ratio;
+        last_trade.max_runup *= ratio;
+
+        remaining_trade.fill_size = Some(remaining_trade.fill_size.unwrap() - size);
+        remaining_trade.funding *= 1.0 - ratio;
+        remaining_trade.commission *= 1.0 - ratio;
+        remaining_trade.max_drawdown *= 1.0 - ratio;
+        remaining_trade.max_runup *= 1.0 - ratio;
+
+        self.close_last_trade(price, tick, exit_id);
+        self.trades.push(remaining_trade);
+
+        if is_exit_order_bound {
+            self.exit_order = exit_order;
+            self.exit_order_trade = Some(self.trades.len() - 1);
+        }
+    }
+
+    /// Fills remainder of the open trade entry, that was limited by bar volume.
+    fn process_unfilled_entry(&mut self, price: f64, fill_capacity: f64) {
+        let tick = self.ctx.bar.index();
+        let volume = self.ctx.bar.volume();
+
+        if let Some(trade) = self.trades.last_mut() {
+            let is_filled_on_current_bar = trade.fills.last().is_some_and(|fill| fill.tick == tick);
+
+            if !trade.is_active() || trade.unfilled_size <= 0.0 || is_filled_on_current_bar {
+                return;
+            }
+
+            let size = self
+                .ctx
+                .syminfo
+                .round_qty(f64::min(trade.unfilled_size, fill_capacity));
+
+            if size > 0.0 {
+                let is_buy = trade.direction == TradeDirection::Long;
+                let price = self.ctx.syminfo.round_to_mintick(
+                    self.config
+                        .execution
+                        .fill_price(price, size, volume, is_buy),
+                );
+                let commission = self
+                    .config
+                    .execution
+                    .commission(size, price, trade.point_value);
+                let signed_size = if is_buy { size } else { -size };
+
+                trade.add_fill(tick, price, size);
+                trade.commission += commission;
+                self.ledger
+                    .fill(tick, signed_size, price, trade.point_value);
+                self.ledger.fee(tick, commission);
+                self.risk.on_fill();
+            }
+        }
+    }
+
+    /// Closes the open trade at `price`, if a risk rule requires it.
+    fn trigger_risk_rule(&mut self, rule: StrategyRiskRule, price: f64) {
+        let tick = self.ctx.bar.index();
+        let mut trade: Option<Trade> = None;
+
+        if self.trades.last().is_some_and(|trade| trade.is_active()) {
+            self.close_last_trade(
+                self.ctx.syminfo.round_to_mintick(price),
+                tick,
+                &format!("{:?}", rule),
+            );
+            trade = self.trades.last().cloned();
+        }
+
+        self.events.on_risk_rule = Some(StrategyOnRiskRuleEvent { rule, trade });
+    }
+
+    /// Updates equity at the bar close.
+    ///
+    /// Equity is taken from the decimal ledger and net profit is derived from it, so float error of trade profits doesn't accumulate over a backtest.
+    fn update_equity(&mut self, close: Option<f64>) {
+        match close {
+            Some(close) => {
+                let equity = self.ledger.equity(close);
+                let net_profit =
+                    equity - self.ledger.initial_capital - to_decimal(self.metrics.open_profit);
+                self.metrics.equity = equity.to_f64().unwrap_or(f64::NAN);
+                self.metrics.net_profit = net_profit.to_f64().unwrap_or(f64::NAN);
+            }
+            None => {
+                self.metrics.equity = self.config.initial_capital
+                    + self.metrics.net_profit
+                    + self.metrics.open_profit;
+            }
+        }
+    }
+
+    /// Price at which equity of the open trade falls below its margin, triggering a margin call.
+    ///
+    /// Returns `None` if margin is disabled or there is no open trade.
+    pub fn liquidation_price(&self) -> Option<f64> {
+        let margin = self.config.margin?;
+        let trade = self.trades.last().filter(|trade| trade.is_active())?;
+
+        let size = trade.fill_size.unwrap() * trade.point_value;
+        let entry_price = trade.entry_price.unwrap();
+        let net_equity = self.config.initial_capital + self.metrics.net_profit + trade.funding
+            - trade.commission;
+        let margin = margin.margin(trade.direction);
+
+        if size <= 0.0 {
+            return None;
+        }
+
+        let price = match trade.direction {
+            TradeDirection::Long => (size * entry_price - net_equity) / (size * (1.0 - margin)),
+            TradeDirection::Short => (size * entry_price + net_equity) / (size * (1.0 + margin)),
+        };
+
+        return Some(f64::max(price, 0.0));
+    }
+
+    /// Quantity liquidated by a margin call of the open trade at `price`. Same as PineScript, contracts needed to cover the margin shortfall are truncated to `qty_step` and multiplied by 4, but at least one lot is liquidated.
+    fn margin_call_size(&self, price: f64) -> f64 {
+        let margin = self.config.margin.unwrap();
+        let trade = self.trades.last().unwrap();
+        let contract_value = price * trade.point_value;
+
+        let equity = self.config.initial_capital + self.metrics.net_profit + trade.pnl(price);
+        let required_margin =
+            margin.margin(trade.direction) * trade.fill_size.unwrap() * contract_value;
+        let shortfall = required_margin - equity;
+
+        if shortfall <= 0.0 {
+            return 0.0;
+        }
+
+        let cover_size = self.ctx.syminfo.round_qty(shortfall / contract_value);
+        let min_size = f64::max(self.ctx.syminfo.min_qty, self.ctx.syminfo.qty_step);
+        return f64::max(4.0 * cover_size, min_size);
+    }
+
+    /// Overrides funding rates, e.g. from an external funding rate series. Rates are applied from the current bar onwards.
+    pub fn set_funding_rate(&mut self, long_rate: f64, short_rate: f64) {
+        let funding = self.config.funding.take().unwrap_or_default();
+        self.config.funding = Some(StrategyFundingConfig {
+            long_rate,
+            short_rate,
+            rate_series: None,
+            ..funding
+        });
+    }
+
+    /// Number of funding payments due on the current bar.
+    fn funding_periods(&self, interval: StrategyFundingInterval) -> usize {
+        let tick = self.ctx.bar.index();
+
+        return match interval {
+            StrategyFundingInterval::Bars(bars) => {
+                usize::from(bars > 0 && tick.is_multiple_of(bars))
+            }
+            StrategyFundingInterval::Time(interval) => {
+                if interval.is_zero() {
+                    return 0;
+                }
+                let Some(time) = self.ctx.bar.time() else {
+                    return 0;
+                };
+                let period = |time: Duration| time.as_nanos() / interval.as_nanos();
+                let previous_time = if tick > self.ctx.first_bar_index {
+                    self.ctx.data.get_time(tick - 1)
+                } else {
+                    None
+                };
+
+                match previous_time {
+                    Some(previous_time) => {
+                        period(time).saturating_sub(period(previous_time)) as usize
+                    }
+                    // First bar is charged only if it opens at a funding time.
+                    None => usize::from(time.as_nanos() % interval.as_nanos() == 0),
+                }
+            }
+        };
+    }
+
+    /// Charges funding of the open trade, if the current bar is a funding bar.
+    fn process_funding(&mut self) {
+        let tick = self.ctx.bar.index();
+        let on_bar_close = self.config.on_bar_close;
+
+        if let Some(funding) = &self.config.funding {
+            let periods = self.funding_periods(funding.interval);
+            if periods == 0 {
+                return;
+            }
+
+            let (long_rate, short_rate) = match &funding.rate_series {
+                Some(name) => match self.ctx.bar.series(name) {
+                    Some(rate) => (rate, -rate),
+                    None => return,
+                },
+                None => (funding.long_rate, funding.short_rate),
+            };
+
+            if let Some(trade) = self.trades.last_mut() {
+                // Trade filled on current bar close was not held during the bar.
+                if !trade.is_active() || on_bar_close && trade.is_at_entry(tick) {
+                    return;
+                }
+
+                let Some(close) = self.ctx.bar.close() else {
+                    return;
+                };
+
+                let amount = -trade.fill_size.unwrap()
+                    * trade.point_value
+                    * close
+                    * match trade.direction {
+                        TradeDirection::Long => long_rate,
+                        TradeDirection::Short => short_rate,
+                    }
+                    * periods as f64;
+
+                trade.funding += amount;
+                self.metrics.net_funding += amount;
+                self.ledger.funding(tick, amount);
+            }
+        }
+    }
+
+    /// Price path of the current bar, from lower-timeframe data if available.
+    fn intrabar_path(&mut self) -> Vec<f64> {
+        let tick = self.ctx.bar.index();
+
+        if let Some(bar_magnifier) = &mut self.bar_magnifier {
+            if let Some(start_time) = self
+                .intrabar_start_time
+                .or_else(|| self.ctx.data.get_time(tick))
+            {
+                let end_time = if tick < self.ctx.last_bar_index() {
+                    self.ctx.data.get_time(tick + 1)
+                } else {
+                    None
+                };
+                if let Some(path) = bar_magnifier.path(start_time, end_time) {
+                    return path;
+                }
+            }
+        }
+
+        return intrabar_path(
+            self.ctx.bar.open().unwrap(),
+            self.ctx.bar.high().unwrap(),
+            self.ctx.bar.low().unwrap(),
+            self.ctx.bar.close().unwrap(),
+        )
+        .to_vec();
+    }
+
+    /// Prices that trigger pending orders within the current bar.
+    ///
+    /// Each level is triggered when price falls to it (`true`) or rises to it (`false`).
+    fn intrabar_levels(&mut self, skipped_entries: &[String]) -> Vec<(IntrabarOrder, f64, bool)> {
+        let tick = self.ctx.bar.index();
+        let mut levels: Vec<(IntrabarOrder, f64, bool)> = Vec::new();
+
+        let active_direction = self
+            .trades
+            .last()
+            .filter(|trade| trade.is_active())
+            .map(|trade| trade.direction);
+
+        if let Some(direction) = active_direction {
+            let is_long = direction == TradeDirection::Long;
+
+            if self.exit_order.is_some() && self.exit_order_trade.is_none() {
+                self.exit_order_trade = Some(self.trades.len() - 1);
+            }
+
+            if let Some(exit_order) = &self.exit_order {
+                if let Some(stop) = exit_order.stop {
+                    levels.push((IntrabarOrder::Stop, stop, is_long));
+                }
+                if let Some(limit) = exit_order.limit {
+                    levels.push((IntrabarOrder::Limit, limit, !is_long));
+                }
+            }
+            if let Some(liquidation_price) = self.liquidation_price() {
+                levels.push((IntrabarOrder::Liquidation, liquidation_price, is_long));
+            }
+        }
+
+        if self.risk.entry_blocked_by(tick).is_none() {
+            for (index, order) in self.entry_orders.iter().enumerate() {
+                // Adding to the open position is not supported.
+                if active_direction == Some(order.direction)
+                    || !self.config.allow_entry_in.allows(order.direction)
+                    || skipped_entries.contains(&order.id)
+                {
+                    continue;
+                }
+
+                let is_long = order.direction == TradeDirection::Long;
+
+                match (order.stop, order.limit) {
+                    // Market order is filled at the current price.
+                    (None, None) => {
+                        levels.push((IntrabarOrder::Entry(index), f64::INFINITY, true));
+                    }
+                    (Some(stop), None) => {
+                        levels.push((IntrabarOrder::Entry(index), stop, !is_long));
+                    }
+                    (None, Some(limit)) => {
+                        levels.push((IntrabarOrder::Entry(index), limit, is_long));
+                    }
+                    (Some(stop), Some(limit)) => {
+                        if self.triggered_entries.contains(&order.id) {
+                            levels.push((IntrabarOrder::Entry(index), limit, is_long));
+                        } else {
+                            levels.push((IntrabarOrder::EntryStop(index), stop, !is_long));
+                        }
+                    }
+                }
+            }
+        }
+
+        return levels;
+    }
+
+    /// Fills pending orders, in order their prices are reached within the current bar.
+    fn process_intrabar_orders(&mut self) {
+        let tick = self.ctx.bar.index();
+        let volume = self.ctx.bar.volume();
+
+        // Entries that could not be filled on the current bar.
+        let mut skipped_entries: Vec<String> = Vec::new();
+
+        if self.intrabar_levels(&skipped_entries).is_empty() {
+            return;
+        }
+
+        let path = self.intrabar_path();
+        let mut prev_price = path[0];
+
+        for price in path {
+            // Margin is checked once per price, so a partial liquidation doesn't repeat at the same price.
+            let mut is_margin_checked = false;
+
+            loop {
+                let levels = self.intrabar_levels(&skipped_entries);
+
+                // Level that is closest to the previous price is reached first. On a gap, it is filled at the previous price.
+                let hit = levels
+                    .iter()
+                    .filter(|(order, level, falls)| {
+                        if is_margin_checked && *order == IntrabarOrder::Liquidation {
+                            return false;
+                        }
+                        // Previous price counts too, for a level activated within the bar, like a stop-limit entry.
+                        if *falls {
+                            f64::min(prev_price, price) <= *level
+                        } else {
+                            f64::max(prev_price, price) >= *level
+                        }
+                    })
+                    .min_by(|(_, a, a_falls), (_, b, b_falls)| {
+                        let distance = |level: f64, falls: bool| {
+                            if falls {
+                                prev_price - level
+                            } else {
+                                level - prev_price
+                            }
+                        };
+                        distance(*a, *a_falls).total_cmp(&distance(*b, *b_falls))
+                    })
+                    .copied();
+
+                let Some((order, level, falls)) = hit else {
+                    break;
+                };
+
+                let fill_price = if order == IntrabarOrder::Liquidation {
+                    // Margin is checked at the reached price, not at the level, same as PineScript.
+                    price
+                } else if falls {
+                    f64::min(prev_price, level)
+                } else {
+                    f64::max(prev_price, level)
+                };
+                let fill_price = self.ctx.syminfo.round_to_mintick(fill_price);
+
+                match order {
+                    IntrabarOrder::Liquidation => {
+                        is_margin_checked = true;
+
+                        let size = self.margin_call_size(fill_price);
+                        if size <= 0.0 {
+                            continue;
+                        }
+
+                        self.trades.last_mut().unwrap().is_liquidated = true;
+                        if size < self.trades.last().unwrap().fill_size.unwrap() {
+                            self.close_last_trade_partially(fill_price, tick, "Margin call", size);
+                            self.trades.last_mut().unwrap().is_liquidated = false;
+                        } else {
+                            self.close_last_trade(fill_price, tick, "Margin call");
+                        }
+
+                        self.metrics.liquidations += 1;
+                        let trade = self.trades.iter().rev().find(|trade| trade.is_closed);
+                        self.events.on_margin_call = Some(StrategyOnMarginCallEvent {
+                            trade: trade.unwrap().clone(),
+                            liquidation_price: level,
+                        });
+                    }
+                    IntrabarOrder::Stop | IntrabarOrder::Limit => {
+                        let exit_id = self.exit_order.as_ref().unwrap().id.clone();
+                        self.close_last_trade(fill_price, tick, &exit_id);
+                    }
+                    IntrabarOrder::EntryStop(index) => {
+                        let id = self.entry_orders[index].id.clone();
+                        self.triggered_entries.push(id);
+                    }
+                    IntrabarOrder::Entry(index) => {
+                        let order = self.entry_orders.remove(index);
+
+                        // Reversed trade is exited by the opposite entry, same as in PineScript.
+                        if self.trades.last().is_some_and(|trade| trade.is_active()) {
+                            self.close_last_trade(fill_price, tick, &order.id);
+                        }
+
+                        let fill_capacity = self.config.execution.fill_limit(volume);
+
+                        match self.open_trade(
+                            order.direction,
+                            fill_price,
+                            volume,
+                            fill_capacity,
+                            Some(order.id.clone()),
+                        ) {
+                            EntryFill::Filled => {
+                                if let Some(oca_group) = &order.oca_group {
+                                    self.entry_orders.retain(|other| {
+                                        other.oca_group.as_ref() != Some(oca_group)
+                                    });
+                                }
+                                if let Some(bracket) = order.bracket {
+                                    self.exit_order = Some(bracket);
+                                    self.exit_order_trade = Some(self.trades.len() - 1);
+                                }
+                            }
+                            EntryFill::Unfilled | EntryFill::NotSized => {
+                                skipped_entries.push(order.id.clone());
+                                self.entry_orders.insert(index, order);
+                            }
+                            EntryFill::Rejected => {}
+                        }
+                    }
+                }
+
+                prev_price = fill_price;
+            }
+
+            prev_price = price;
+        }
+
+        self.triggered_entries
+            .retain(|id| self.entry_orders.iter().any(|order| order.id == *id));
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum EntryFill {
+    Filled,
+    /// Not filled due to volume limit.
+    Unfilled,
+    /// Position sizer is not ready yet.
+    NotSized,
+    /// Quantity is below `syminfo.min_qty`.
+    Rejected,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum IntrabarOrder {
+    Stop,
+    Limit,
+    Liquidation,
+    /// Entry order at index.
+    Entry(usize),
+    /// Stop of a stop-limit entry order at index, that activates its limit.
+    EntryStop(usize),
+}
+
 impl Incremental<Option<TradeDirection>, ()> for Strategy {
     fn next(&mut self, direction: Option<TradeDirection>) {
+        Incremental::<StrategySignal, ()>::next(self, StrategySignal::from(direction));
+    }
+}
+
+impl Incremental<StrategySignal, ()> for Strategy {
+    fn next(&mut self, signal: StrategySignal) {
         let tick = self.ctx.bar.index();
         let open = self.ctx.bar.open();
         let close = self.ctx.bar.close();
 
+        self.intrabar_start_time = None;
+
+        // Exit orders created on the current bar are submitted at its end, so they don't see its price path.
         if self.config.on_bar_close {
-            self.unfilled_trade_direction = direction;
+            self.delay_order(StrategyOrderAction::Signal(signal));
+            self.submit_delayed_orders(true, false);
+
+            // Order is filled on the current bar close, so the sizer can use it.
+            if let Some(position_sizer) = &mut self.position_sizer {
+                position_sizer.next(());
+            }
         }
 
         self.events.on_trade_entry = None;
         self.events.on_trade_exit = None;
+        self.events.on_margin_call = None;
+        self.events.on_risk_rule = None;
+
+        let date = self.ctx.bar.datetime().map(|datetime| datetime.date());
+        if let Some(rule) = self.risk.next_bar(date, self.metrics.equity) {
+            self.trigger_risk_rule(rule, open.unwrap());
+        }
+
+        if self.config.on_bar_close {
+            self.process_intrabar_orders();
+        } else {
+            self.submit_delayed_orders(false, false);
+        }
+
+        let mut orderbook_price = if self.config.on_bar_close {
+            close
+        } else {
+            open
+        }
+        .map(|price| self.ctx.syminfo.round_to_mintick(price));
+
+        // Signal delayed by time is filled within the bar.
+        if let Some(time) = self.unfilled_signal_time.take() {
+            if self.unfilled_signal != StrategySignal::Hold {
+                if let Some(price) = self
+                    .bar_magnifier
+                    .as_ref()
+                    .and_then(|bar_magnifier| bar_magnifier.open_at(time))
+                {
+                    orderbook_price = Some(self.ctx.syminfo.round_to_mintick(price));
+                    self.intrabar_start_time = Some(time);
+                }
+            }
+        }
+
+        let volume = self.ctx.bar.volume();
+        let mut fill_capacity = self.config.execution.fill_limit(volume);
+
+        if self.unfilled_signal != StrategySignal::Hold {
+            let mut unfilled_signal = self.config.allow_entry_in.restrict(self.unfilled_signal);
+
+            if unfilled_signal.direction().is_some() {
+                if let Some(rule) = self.risk.entry_blocked_by(tick) {
+                    unfilled_signal = unfilled_signal.without_entry();
+                    self.events.on_risk_rule = Some(StrategyOnRiskRuleEvent { rule, trade: None });
+                }
+            }
 
-        if let Some(unfilled_trade_direction) = self.unfilled_trade_direction {
             let is_continous = self.config.continous;
 
             let mut close_trade = false;
             let mut create_new_trade = false;
+            let mut is_signal_carried = false;
 
-            let orderbook_price = if self.config.on_bar_close {
-                close
-            } else {
-                open
-            };
-
-            if let Some(last_trade) = self.trades.last_mut() {
-                let is_same_direction = last_trade.direction == unfilled_trade_direction;
+            if let Some(last_trade) = self.trades.last() {
+                if let Some(unfilled_trade_direction) = unfilled_signal.direction() {
+                    let is_same_direction = last_trade.direction == unfilled_trade_direction;
 
-                close_trade = !is_same_direction && !last_trade.is_closed;
+                    close_trade = !is_same_direction && !last_trade.is_closed;
 
-                if is_continous {
-                    create_new_trade = !is_same_direction && close_trade;
+                    if is_continous {
+                        // A closed trade was exited or liquidated, so any signal opens a new one.
+                        create_new_trade =
+                            !is_same_direction && close_trade || last_trade.is_closed;
+                    } else {
+                        create_new_trade = last_trade.is_closed
+                            && (is_same_direction || !is_same_direction && !close_trade);
+                    }
                 } else {
-                    create_new_trade = last_trade.is_closed
-                        && (is_same_direction || !is_same_direction && !close_trade);
+                    close_trade =
+                        !last_trade.is_closed && unfilled_signal.closes(last_trade.direction);
                 }
 
                 if close_trade {
-                    last_trade.exit_price = orderbook_price;
-                    last_trade.exit_tick = Some(tick);
-                    last_trade.is_closed = true;
-                    last_trade.pnl = last_trade.pnl(last_trade.exit_price.unwrap());
-                    let pnl = last_trade.pnl;
-
-                    self.events.on_trade_exit =
-                        Some(StrategyOnTradeExitEvent { trade: *last_trade });
-
-                    self.metrics.net_profit += pnl;
-                    self.metrics.open_profit = 0.0;
-
-                    if pnl > 0.0 {
-                        self.metrics.gross_profit += pnl;
-                        self.metrics.winning_trades += 1;
-                    } else if pnl < 0.0 {
-                        self.metrics.gross_loss += pnl.abs();
-                        self.metrics.losing_trades += 1;
-                    }
+                    // Reversed trade is exited by the opposite entry, same as in PineScript.
+                    let exit_id = unfilled_signal
+                        .direction()
+                        .map_or("Close".to_string(), |direction| direction.to_string());
+                    let size = last_trade.fill_size.unwrap();
+                    let is_buy = last_trade.direction == TradeDirection::Short;
 
-                    if last_trade.direction == TradeDirection::Long {
-                        self.metrics.long_net_profit += pnl;
+                    // Position larger than the volume limit is closed over multiple bars.
+                    let exit_size = if size > fill_capacity {
+                        self.ctx.syminfo.round_qty(fill_capacity)
                     } else {
-                        self.metrics.short_net_profit += pnl;
-                    }
+                        size
+                    };
+                    let price =
+                        self.ctx
+                            .syminfo
+                            .round_to_mintick(self.config.execution.fill_price(
+                                orderbook_price.unwrap(),
+                                exit_size,
+                                volume,
+                                is_buy,
+                            ));
 
-                    self.metrics.closed_trades += 1;
+                    if exit_size >= size {
+                        self.close_last_trade(price, tick, &exit_id);
+                    } else {
+                        if exit_size > 0.0 {
+                            self.close_last_trade_partially(price, tick, &exit_id, exit_size);
+                        }
+                        is_signal_carried = true;
+                        create_new_trade = false;
+                    }
+                    fill_capacity -= exit_size;
                 }
             } else {
-                create_new_trade = true;
+                create_new_trade = unfilled_signal.direction().is_some();
             }
 
             if create_new_trade {
-                let mut trade = Trade::new(unfilled_trade_direction);
-
-                trade.fill_size = Some(1.0);
-
-                if self.config.buy_with_equity {
-                    let equity = self.config.initial_capital
-                        + self.metrics.net_profit
-                        + self.metrics.open_profit;
-
-                    trade.fill_size = Some(fill_size(equity, orderbook_price.unwrap()));
+                let direction = unfilled_signal.direction().unwrap();
+                let entry = self.open_trade(
+                    direction,
+                    orderbook_price.unwrap(),
+                    volume,
+                    fill_capacity,
+                    None,
+                );
+                if entry == EntryFill::Unfilled {
+                    is_signal_carried = true;
                 }
+            }
 
-                trade.entry_price = orderbook_price;
-                trade.entry_tick = Some(tick);
-
-                self.trades.push(trade);
-                self.events.on_trade_entry = Some(StrategyOnTradeEntryEvent { trade: trade });
+            self.is_signal_carried = is_signal_carried;
+            if !is_signal_carried {
+                self.unfilled_signal = StrategySignal::Hold;
             }
+        }
 
-            self.unfilled_trade_direction = None;
+        if let Some(orderbook_price) = orderbook_price {
+            self.process_unfilled_entry(orderbook_price, fill_capacity);
         }
 
         if !self.config.on_bar_close {
-            self.unfilled_trade_direction = direction;
+            self.process_intrabar_orders();
+            self.delay_order(StrategyOrderAction::Signal(signal));
+            self.submit_delayed_orders(true, true);
+
+            // Order is filled on the next bar open, so the sizer must not see the next bar.
+            if let Some(position_sizer) = &mut self.position_sizer {
+                position_sizer.next(());
+            }
         }
 
+        self.process_funding();
+
         if let Some(last_trade) = self.trades.last_mut() {
+            // Trade filled on current bar close was not held during the bar.
+            if last_trade.is_active() && !(self.config.on_bar_close && last_trade.is_at_entry(tick))
+            {
+                last_trade
+                    .update_excursions(self.ctx.bar.low().unwrap(), self.ctx.bar.high().unwrap());
+            }
+
             if !last_trade.is_closed {
                 self.metrics.open_profit = last_trade.pnl(close.unwrap());
             }
         }
 
-        self.metrics.equity =
-            self.config.initial_capital + self.metrics.net_profit + self.metrics.open_profit;
+        self.update_equity(close);
+
+        if let Some(rule) = self.risk.check(self.metrics.equity) {
+            self.trigger_risk_rule(rule, close.unwrap());
+            self.update_equity(close);
+        }
+
+        // Bar without a close price has no value to mark the position at.
+        if let Some(close) = close {
+            self.ledger.snapshot(tick, close);
+        }
+
+        if self.config.on_bar_close {
+            self.submit_delayed_orders(true, true);
+        }
     }
 }
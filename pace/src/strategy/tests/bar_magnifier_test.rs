@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::{context::Context, incremental::Incremental},
+        strategy::{
+            bar_magnifier::{intrabar_path, BarMagnifier},
+            signal::StrategySignal,
+            strategy::{Strategy, StrategyConfig},
+        },
+        testing::{comparison::FloatComparison, fixture::Fixture},
+    };
+
+    /// Enters long on the second bar open at `100.0` with stop at `95.0` and target at `110.0`.
+    fn run(bars: &[(u64, f64, f64, f64, f64)], bar_magnifier: Option<BarMagnifier>) -> Strategy {
+        let ctx = Context::new(Fixture::timed_ohlc_data(bars));
+        let mut strategy = Strategy::new(ctx.clone(), StrategyConfig::default());
+        strategy.bar_magnifier = bar_magnifier;
+
+        for tick in ctx.clone() {
+            if tick == 0 {
+                strategy.set_exit("Bracket", Some(95.0), Some(110.0));
+            }
+            strategy.next(if tick == 0 {
+                StrategySignal::Long
+            } else {
+                StrategySignal::Hold
+            });
+        }
+
+        return strategy;
+    }
+
+    #[test]
+    fn path() {
+        assert_eq!(
+            intrabar_path(100.0, 105.0, 90.0, 95.0),
+            [100.0, 105.0, 90.0, 95.0]
+        );
+        assert_eq!(
+            intrabar_path(100.0, 115.0, 90.0, 95.0),
+            [100.0, 90.0, 115.0, 95.0]
+        );
+    }
+
+    #[test]
+    fn stop_and_limit_in_same_bar_without_magnifier() {
+        let strategy = run(
+            &[
+                (0, 100.0, 100.0, 100.0, 100.0),
+                (86400, 100.0, 112.0, 90.0, 100.0),
+                (172800, 100.0, 100.0, 100.0, 100.0),
+            ],
+            None,
+        );
+        let trade = &strategy.trades[0];
+        assert_eq!(trade.exit_tick, Some(1));
+        assert!(trade.exit_price.unwrap().compare(95.0));
+        assert_eq!(trade.exit_id.as_deref(), Some("Bracket"));
+        assert!(strategy.exit_order.is_none());
+    }
+
+    #[test]
+    fn stop_and_limit_in_same_bar_with_magnifier() {
+        let magnifier = BarMagnifier::new(Fixture::timed_ohlc_data(&[
+            (0, 100.0, 100.0, 100.0, 100.0),
+            (86400, 100.0, 112.0, 100.0, 110.0),
+            (86400 + 43200, 110.0, 110.0, 90.0, 100.0),
+            (172800, 100.0, 100.0, 100.0, 100.0),
+        ]));
+        let strategy = run(
+            &[
+                (0, 100.0, 100.0, 100.0, 100.0),
+                (86400, 100.0, 112.0, 90.0, 100.0),
+                (172800, 100.0, 100.0, 100.0, 100.0),
+            ],
+            Some(magnifier),
+        );
+        let trade = &strategy.trades[0];
+        assert_eq!(trade.exit_tick, Some(1));
+        assert!(trade.exit_price.unwrap().compare(110.0));
+    }
+
+    #[test]
+    fn stop_gap() {
+        let strategy = run(
+            &[
+                (0, 100.0, 100.0, 100.0, 100.0),
+                (86400, 100.0, 101.0, 99.0, 100.0),
+                (172800, 90.0, 92.0, 88.0, 91.0),
+            ],
+            None,
+        );
+        let trade = &strategy.trades[0];
+        assert_eq!(trade.exit_tick, Some(2));
+        assert!(trade.exit_price.unwrap().compare(90.0));
+        assert!(trade.pnl.compare(-10.0));
+    }
+
+    #[test]
+    fn missing_lower_timeframe_data() {
+        let magnifier =
+            BarMagnifier::new(Fixture::timed_ohlc_data(&[(0, 100.0, 100.0, 100.0, 100.0)]));
+        let strategy = run(
+            &[
+                (0, 100.0, 100.0, 100.0, 100.0),
+                (86400, 100.0, 112.0, 90.0, 100.0),
+                (172800, 100.0, 100.0, 100.0, 100.0),
+            ],
+            Some(magnifier),
+        );
+        assert!(strategy.trades[0].exit_price.unwrap().compare(95.0));
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::incremental::Incremental,
+        strategy::{
+            execution::StrategyExecutionConfig,
+            signal::StrategySignal,
+            strategy::{Strategy, StrategyConfig},
+        },
+        testing::{comparison::FloatComparison, fixture::Fixture},
+    };
+
+    fn run(
+        volume: &[f64],
+        execution: StrategyExecutionConfig,
+        buy_with_equity: bool,
+        signals: &[StrategySignal],
+    ) -> Strategy {
+        let ctx = Fixture::volume_ctx(10.0, volume);
+        let mut strategy = Strategy::new(
+            ctx.clone(),
+            StrategyConfig {
+                buy_with_equity,
+                execution,
+                ..StrategyConfig::default()
+            },
+        );
+        for tick in ctx.clone() {
+            strategy.next(signals[tick]);
+        }
+        return strategy;
+    }
+
+    #[test]
+    fn fill_limit_and_price() {
+        let execution = StrategyExecutionConfig {
+            max_volume_participation: Some(0.1),
+            market_impact: Some(0.1),
+            ..StrategyExecutionConfig::default()
+        };
+        assert!(execution.fill_limit(Some(500.0)).compare(50.0));
+        assert_eq!(execution.fill_limit(None), f64::INFINITY);
+        assert!(execution
+            .fill_price(10.0, 1.0, Some(100.0), true)
+            .compare(10.1));
+        assert!(execution
+            .fill_price(10.0, 1.0, Some(100.0), false)
+            .compare(9.9));
+        assert!(execution.fill_price(10.0, 1.0, None, true).compare(10.0));
+    }
+
+    #[test]
+    fn entry_is_filled_over_multiple_bars() {
+        let strategy = run(
+            &[100.0, 400.0, 400.0, 400.0, 400.0],
+            StrategyExecutionConfig {
+                max_volume_participation: Some(0.1),
+                ..StrategyExecutionConfig::default()
+            },
+            true,
+            &[
+                StrategySignal::Long,
+                StrategySignal::Hold,
+                StrategySignal::Hold,
+                StrategySignal::Hold,
+                StrategySignal::Hold,
+            ],
+        );
+        let trade = &strategy.trades[0];
+        assert_eq!(trade.entry_tick, Some(1));
+        assert_eq!(trade.fills.len(), 3);
+        assert_eq!(
+            trade.fills.iter().map(|fill| fill.tick).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert!(trade.fill_size.unwrap().compare(100.0));
+        assert!(trade.unfilled_size.compare(0.0));
+        assert!(trade.entry_price.unwrap().compare(10.0));
+    }
+
+    #[test]
+    fn exit_is_split_into_partial_trades() {
+        let strategy = run(
+            &[500.0; 6],
+            StrategyExecutionConfig {
+                max_volume_participation: Some(0.1),
+                ..StrategyExecutionConfig::default()
+            },
+            true,
+            &[
+                StrategySignal::Long,
+                StrategySignal::Hold,
+                StrategySignal::Exit,
+                StrategySignal::Hold,
+                StrategySignal::Hold,
+                StrategySignal::Hold,
+            ],
+        );
+        assert_eq!(strategy.trades.len(), 2);
+
+        let first = &strategy.trades[0];
+        assert!(first.is_closed);
+        assert_eq!(first.exit_tick, Some(3));
+        assert!(first.fill_size.unwrap().compare(50.0));
+
+        let second = &strategy.trades[1];
+        assert!(second.is_closed);
+        assert_eq!(second.exit_tick, Some(4));
+        assert!(second.fill_size.unwrap().compare(50.0));
+        assert_eq!(strategy.metrics.closed_trades, 2);
+    }
+
+    #[test]
+    fn reversal_waits_for_exit() {
+        let strategy = run(
+            &[500.0; 6],
+            StrategyExecutionConfig {
+                max_volume_participation: Some(0.1),
+                ..StrategyExecutionConfig::default()
+            },
+            true,
+            &[
+                StrategySignal::Long,
+                StrategySignal::Hold,
+                StrategySignal::Short,
+                StrategySignal::Hold,
+                StrategySignal::Hold,
+                StrategySignal::Hold,
+            ],
+        );
+        assert_eq!(strategy.trades.len(), 3);
+        assert_eq!(strategy.trades[1].exit_tick, Some(4));
+
+        let short = &strategy.trades[2];
+        assert_eq!(short.entry_tick, Some(5));
+        assert!(short.fill_size.unwrap().compare(50.0));
+        assert!(short.unfilled_size.compare(50.0));
+    }
+
+    #[test]
+    fn market_impact() {
+        let strategy = run(
+            &[100.0; 4],
+            StrategyExecutionConfig {
+                market_impact: Some(0.1),
+                ..StrategyExecutionConfig::default()
+            },
+            false,
+            &[
+                StrategySignal::Long,
+                StrategySignal::Exit,
+                StrategySignal::Hold,
+                StrategySignal::Hold,
+            ],
+        );
+        let trade = &strategy.trades[0];
+        assert!(trade.entry_price.unwrap().compare(10.1));
+        assert!(trade.exit_price.unwrap().compare(9.9));
+        assert!(trade.pnl.compare(-0.2));
+    }
+}
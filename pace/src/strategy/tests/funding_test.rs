@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        core::{
+            context::Context, data_provider::DataProvider,
+            in_memory_data_provider::InMemoryDataProvider, incremental::Incremental,
+        },
+        strategy::{
+            strategy::{Strategy, StrategyConfig, StrategyFundingConfig, StrategyFundingInterval},
+            trade::TradeDirection,
+        },
+        testing::{comparison::FloatComparison, fixture::Fixture},
+    };
+
+    #[test]
+    fn perpetual_funding() {
+        let ctx = Fixture::ohlc_ctx(&[(100.0, 100.0, 100.0, 100.0); 6]);
+        let mut strategy = Strategy::new(
+            ctx.clone(),
+            StrategyConfig {
+                funding: Some(StrategyFundingConfig::perpetual(
+                    0.01,
+                    StrategyFundingInterval::Bars(2),
+                )),
+                ..StrategyConfig::default()
+            },
+        );
+        let signals = [
+            Some(TradeDirection::Long),
+            None,
+            None,
+            Some(TradeDirection::Short),
+            None,
+            None,
+        ];
+        let mut open_profit: Vec<f64> = Vec::new();
+
+        for tick in ctx.clone() {
+            strategy.next(signals[tick]);
+            open_profit.push(strategy.metrics.open_profit);
+        }
+
+        // Long entered at tick 1, charged at tick 2. Short entered at tick 4, receives at tick 4.
+        assert_eq!(open_profit, vec![0.0, 0.0, -1.0, -1.0, 1.0, 1.0]);
+        assert!(strategy.trades[0].pnl.compare(-1.0));
+        assert!(strategy.trades[0].funding.compare(-1.0));
+        assert!(strategy.trades[1].funding.compare(1.0));
+        assert!(strategy.metrics.net_profit.compare(-1.0));
+        assert!(strategy.metrics.net_funding.compare(0.0));
+        assert!(strategy.metrics.equity.compare(1000.0));
+    }
+
+    #[test]
+    fn funding_rate_series() {
+        let ctx = Fixture::ohlc_ctx(&[(100.0, 100.0, 100.0, 100.0); 4]);
+        let mut strategy = Strategy::new(ctx.clone(), StrategyConfig::default());
+        let rates = [0.0, 0.01, 0.02, -0.01];
+
+        for tick in ctx.clone() {
+            strategy.set_funding_rate(rates[tick], -rates[tick]);
+            strategy.next(if tick == 0 {
+                Some(TradeDirection::Long)
+            } else {
+                None
+            });
+        }
+
+        assert!(strategy.trades[0].funding.compare(-2.0));
+        assert!(strategy.metrics.net_funding.compare(-2.0));
+        assert!(strategy.metrics.equity.compare(998.0));
+    }
+
+    #[test]
+    fn missing_close() {
+        for funding in [
+            None,
+            Some(StrategyFundingConfig::perpetual(
+                0.01,
+                StrategyFundingInterval::Bars(1),
+            )),
+        ] {
+            let close = vec![None, Some(1.0), Some(2.0)];
+            let ctx = Context::new(
+                InMemoryDataProvider::new(
+                    close.clone(),
+                    close.clone(),
+                    close.clone(),
+                    close,
+                    vec![Some(1.0); 3],
+                    vec![None; 3],
+                )
+                .to_arc(),
+            );
+            let mut strategy = Strategy::new(
+                ctx.clone(),
+                StrategyConfig {
+                    funding,
+                    ..StrategyConfig::default()
+                },
+            );
+
+            for _ in ctx.clone() {
+                strategy.next(None);
+            }
+
+            // Bar without a close price is not marked.
+            assert_eq!(strategy.ledger.snapshots.len(), 2);
+            assert_eq!(strategy.metrics.equity, 1000.0);
+        }
+    }
+
+    fn flat_data(time: Vec<Option<Duration>>) -> InMemoryDataProvider {
+        let price = vec![Some(100.0); time.len()];
+        return InMemoryDataProvider::new(
+            price.clone(),
+            price.clone(),
+            price.clone(),
+            price,
+            vec![Some(1.0); time.len()],
+            time,
+        );
+    }
+
+    #[test]
+    fn time_interval() {
+        let hours = [0, 4, 8, 12, 16, 40];
+        let ctx = Context::new(
+            flat_data(
+                hours
+                    .iter()
+                    .map(|hours| Some(Duration::from_secs(hours * 3600)))
+                    .collect(),
+            )
+            .to_arc(),
+        );
+        let mut strategy = Strategy::new(
+            ctx.clone(),
+            StrategyConfig {
+                funding: Some(StrategyFundingConfig::perpetual(
+                    0.01,
+                    StrategyFundingInterval::Time(Duration::from_secs(8 * 3600)),
+                )),
+                ..StrategyConfig::default()
+            },
+        );
+        let mut funding: Vec<f64> = Vec::new();
+
+        for tick in ctx.clone() {
+            strategy.next(if tick == 0 {
+                Some(TradeDirection::Long)
+            } else {
+                None
+            });
+            funding.push(strategy.metrics.net_funding);
+        }
+
+        // Long entered at 4h, charged at 8h, 16h and 3 times on the bar after the 24h gap.
+        assert_eq!(funding, vec![0.0, 0.0, -1.0, -1.0, -2.0, -5.0]);
+    }
+
+    #[test]
+    fn rate_series() {
+        let mut data = flat_data(vec![None; 4]);
+        data.add_series(
+            "funding_rate",
+            vec![Some(0.05), Some(0.01), None, Some(0.02)],
+        )
+        .unwrap();
+        // Series name is known only at runtime, e.g. from a data frame column.
+        let name = data.get_series_names()[0].to_string();
+        let ctx = Context::new(data.to_arc());
+        let mut strategy = Strategy::new(
+            ctx.clone(),
+            StrategyConfig {
+                funding: Some(StrategyFundingConfig::perpetual_series(
+                    &name,
+                    StrategyFundingInterval::Bars(1),
+                )),
+                ..StrategyConfig::default()
+            },
+        );
+
+        for tick in ctx.clone() {
+            strategy.next(if tick == 0 {
+                Some(TradeDirection::Long)
+            } else {
+                None
+            });
+        }
+
+        // Bar without a rate is not charged.
+        assert!(strategy.trades[0].funding.compare(-3.0));
+        assert!(strategy.metrics.net_funding.compare(-3.0));
+    }
+}
@@ -0,0 +1,192 @@
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use crate::{
+        core::{context::Context, data_provider::DataProvider, incremental::Incremental},
+        strategy::{
+            bar_magnifier::BarMagnifier,
+            latency::StrategyLatency,
+            signal::StrategySignal,
+            strategy::{Strategy, StrategyConfig},
+        },
+        testing::{comparison::FloatComparison, fixture::Fixture},
+    };
+
+    static HOUR: u64 = 3600;
+    static DAY: u64 = 86400;
+
+    /// Daily bars, where open of bar `i` is `100.0 + i`.
+    fn create_daily_data(count: usize) -> Arc<dyn DataProvider + Send + Sync> {
+        let bars: Vec<(u64, f64, f64, f64, f64)> = (0..count)
+            .map(|i| {
+                let price = 100.0 + i as f64;
+                (i as u64 * DAY, price, price, price, price)
+            })
+            .collect();
+        return Fixture::timed_ohlc_data(&bars);
+    }
+
+    fn run(
+        ctx: Context,
+        config: StrategyConfig,
+        bar_magnifier: Option<BarMagnifier>,
+        signals: &[StrategySignal],
+    ) -> Strategy {
+        let mut strategy = Strategy::new(ctx.clone(), config);
+        strategy.bar_magnifier = bar_magnifier;
+        for tick in ctx.clone() {
+            strategy.next(signals[tick]);
+        }
+        return strategy;
+    }
+
+    fn long_on_first_bar(count: usize) -> Vec<StrategySignal> {
+        let mut signals = vec![StrategySignal::Hold; count];
+        signals[0] = StrategySignal::Long;
+        return signals;
+    }
+
+    #[test]
+    fn bars_next_bar_open() {
+        let strategy = run(
+            Context::new(create_daily_data(5)),
+            StrategyConfig {
+                latency: Some(StrategyLatency::Bars(2)),
+                ..StrategyConfig::default()
+            },
+            None,
+            &long_on_first_bar(5),
+        );
+        let trade = &strategy.trades[0];
+        assert_eq!(trade.entry_tick, Some(3));
+        assert!(trade.entry_price.unwrap().compare(103.0));
+    }
+
+    #[test]
+    fn bars_on_bar_close() {
+        let strategy = run(
+            Context::new(create_daily_data(5)),
+            StrategyConfig {
+                on_bar_close: true,
+                latency: Some(StrategyLatency::Bars(1)),
+                ..StrategyConfig::default()
+            },
+            None,
+            &long_on_first_bar(5),
+        );
+        assert_eq!(strategy.trades[0].entry_tick, Some(1));
+    }
+
+    #[test]
+    fn time_without_intrabar_data() {
+        let strategy = run(
+            Context::new(create_daily_data(5)),
+            StrategyConfig {
+                latency: Some(StrategyLatency::Time(Duration::from_secs(12 * HOUR))),
+                ..StrategyConfig::default()
+            },
+            None,
+            &long_on_first_bar(5),
+        );
+        let trade = &strategy.trades[0];
+        assert_eq!(trade.entry_tick, Some(2));
+        assert!(trade.entry_price.unwrap().compare(102.0));
+    }
+
+    #[test]
+    fn time_with_intrabar_data() {
+        let intrabar: Vec<(u64, f64, f64, f64, f64)> = (0..20)
+            .map(|i| {
+                let price = 200.0 + i as f64;
+                (i as u64 * 6 * HOUR, price, price, price, price)
+            })
+            .collect();
+        let strategy = run(
+            Context::new(create_daily_data(5)),
+            StrategyConfig {
+                latency: Some(StrategyLatency::Time(Duration::from_secs(12 * HOUR))),
+                ..StrategyConfig::default()
+            },
+            Some(BarMagnifier::new(Fixture::timed_ohlc_data(&intrabar))),
+            &long_on_first_bar(5),
+        );
+        let trade = &strategy.trades[0];
+        assert_eq!(trade.entry_tick, Some(1));
+        // 6th intrabar bar starts 1.5 days after the first one.
+        assert!(trade.entry_price.unwrap().compare(206.0));
+    }
+
+    #[test]
+    fn exit_order_is_active_from_next_bar() {
+        let ctx = Context::new(Fixture::timed_ohlc_data(&[
+            (0, 100.0, 100.0, 100.0, 100.0),
+            (DAY, 100.0, 100.0, 90.0, 100.0),
+            (2 * DAY, 100.0, 100.0, 90.0, 100.0),
+            (3 * DAY, 100.0, 100.0, 100.0, 100.0),
+        ]));
+        let mut strategy = Strategy::new(
+            ctx.clone(),
+            StrategyConfig {
+                on_bar_close: true,
+                ..StrategyConfig::default()
+            },
+        );
+        for tick in ctx.clone() {
+            if tick == 1 {
+                strategy.set_exit("Stop", Some(95.0), None);
+            }
+            strategy.next(if tick == 0 {
+                StrategySignal::Long
+            } else {
+                StrategySignal::Hold
+            });
+        }
+        let trade = &strategy.trades[0];
+        assert_eq!(trade.exit_tick, Some(2));
+        assert!(trade.exit_price.unwrap().compare(95.0));
+    }
+
+    fn run_cancelled_exit(latency: Option<StrategyLatency>) -> Strategy {
+        let ctx = Context::new(Fixture::timed_ohlc_data(&[
+            (0, 100.0, 100.0, 100.0, 100.0),
+            (DAY, 100.0, 100.0, 100.0, 100.0),
+            (2 * DAY, 100.0, 100.0, 100.0, 100.0),
+            (3 * DAY, 100.0, 100.0, 90.0, 100.0),
+            (4 * DAY, 100.0, 100.0, 100.0, 100.0),
+        ]));
+        let mut strategy = Strategy::new(
+            ctx.clone(),
+            StrategyConfig {
+                latency,
+                ..StrategyConfig::default()
+            },
+        );
+        for tick in ctx.clone() {
+            if tick == 0 {
+                strategy.set_exit("Stop", Some(95.0), None);
+            }
+            if tick == 2 {
+                strategy.cancel_exit();
+            }
+            strategy.next(if tick == 0 {
+                StrategySignal::Long
+            } else {
+                StrategySignal::Hold
+            });
+        }
+        return strategy;
+    }
+
+    #[test]
+    fn cancellation_is_delayed() {
+        let strategy = run_cancelled_exit(None);
+        assert!(!strategy.trades[0].is_closed);
+
+        let strategy = run_cancelled_exit(Some(StrategyLatency::Bars(1)));
+        let trade = &strategy.trades[0];
+        assert_eq!(trade.entry_tick, Some(2));
+        assert_eq!(trade.exit_tick, Some(3));
+        assert!(trade.exit_price.unwrap().compare(95.0));
+    }
+}
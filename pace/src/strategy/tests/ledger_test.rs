@@ -0,0 +1,210 @@
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use crate::{
+        core::incremental::Incremental,
+        strategy::{
+            execution::{StrategyCommission, StrategyExecutionConfig},
+            ledger::{to_decimal, Ledger, LedgerEntryKind},
+            signal::StrategySignal,
+            strategy::{Strategy, StrategyConfig, StrategyFundingConfig, StrategyFundingInterval},
+            trade::TradeDirection,
+        },
+        testing::{comparison::FloatComparison, fixture::Fixture},
+    };
+
+    #[test]
+    fn fill_average_cost_and_realized_pnl() {
+        let mut ledger = Ledger::new(1000.0);
+
+        ledger.fill(0, 1.0, 100.0, 1.0);
+        ledger.fill(1, 1.0, 110.0, 1.0);
+        assert_eq!(ledger.position, to_decimal(2.0));
+        assert_eq!(ledger.avg_cost, to_decimal(105.0));
+        assert_eq!(ledger.cash, to_decimal(790.0));
+
+        // Reverses the position, so the remaining short opens at the fill price.
+        ledger.fill(2, -3.0, 120.0, 1.0);
+        assert_eq!(ledger.realized_pnl, to_decimal(30.0));
+        assert_eq!(ledger.position, to_decimal(-1.0));
+        assert_eq!(ledger.avg_cost, to_decimal(120.0));
+        assert_eq!(ledger.unrealized_pnl(100.0), to_decimal(20.0));
+        assert_eq!(ledger.equity(100.0), to_decimal(1050.0));
+
+        ledger.fill(3, 1.0, 100.0, 1.0);
+        assert_eq!(ledger.position, Decimal::ZERO);
+        assert_eq!(ledger.avg_cost, Decimal::ZERO);
+        assert_eq!(ledger.realized_pnl, to_decimal(50.0));
+        assert_eq!(ledger.cash, to_decimal(1050.0));
+        assert_eq!(ledger.entries.len(), 4);
+    }
+
+    #[test]
+    fn no_float_drift() {
+        let mut ledger = Ledger::new(1000.0);
+        let mut cash = 1000.0;
+
+        for tick in 0..1000 {
+            ledger.fill(tick, 0.1, 0.1, 1.0);
+            ledger.fill(tick, -0.1, 0.3, 1.0);
+            cash += 0.1 * 0.3 - 0.1 * 0.1;
+        }
+
+        assert_eq!(ledger.cash, to_decimal(1020.0));
+        assert_eq!(ledger.realized_pnl, to_decimal(20.0));
+        assert_ne!(cash, 1020.0);
+    }
+
+    #[test]
+    fn fees_and_funding() {
+        let mut ledger = Ledger::new(1000.0);
+
+        ledger.fill(0, 2.0, 50.0, 1.0);
+        ledger.fee(0, 1.5);
+        ledger.funding(1, -0.5);
+        ledger.snapshot(1, 60.0);
+
+        let snapshot = ledger.snapshots.last().unwrap();
+        assert_eq!(snapshot.fees, to_decimal(1.5));
+        assert_eq!(snapshot.funding, to_decimal(-0.5));
+        assert_eq!(snapshot.cash, to_decimal(898.0));
+        assert_eq!(snapshot.equity, to_decimal(1018.0));
+        assert_eq!(
+            ledger
+                .entries
+                .iter()
+                .map(|entry| entry.kind)
+                .collect::<Vec<_>>(),
+            vec![
+                LedgerEntryKind::Fill,
+                LedgerEntryKind::Fee,
+                LedgerEntryKind::Funding
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_strategy_equity() {
+        let ctx = Fixture::ohlc_ctx(&[
+            (100.0, 105.0, 95.0, 102.0),
+            (102.0, 110.0, 100.0, 108.0),
+            (108.0, 112.0, 101.0, 104.0),
+            (104.0, 106.0, 90.0, 92.0),
+            (92.0, 99.0, 91.0, 97.0),
+            (97.0, 103.0, 96.0, 101.0),
+            (101.0, 104.0, 98.0, 99.0),
+        ]);
+        let mut strategy = Strategy::new(
+            ctx.clone(),
+            StrategyConfig {
+                buy_with_equity: true,
+                funding: Some(StrategyFundingConfig::perpetual(
+                    0.001,
+                    StrategyFundingInterval::Bars(1),
+                )),
+                execution: StrategyExecutionConfig {
+                    commission: Some(StrategyCommission::Percent(0.001)),
+                    ..StrategyExecutionConfig::default()
+                },
+                ..StrategyConfig::default()
+            },
+        );
+        let signals = [
+            Some(TradeDirection::Long),
+            None,
+            Some(TradeDirection::Short),
+            None,
+            Some(TradeDirection::Long),
+            None,
+            None,
+        ];
+
+        for tick in ctx.clone() {
+            strategy.next(signals[tick]);
+            let close = ctx.bar.close().unwrap();
+            assert!(strategy
+                .ledger
+                .equity_f64(close)
+                .compare(strategy.metrics.equity));
+        }
+
+        assert_eq!(strategy.ledger.snapshots.len(), 7);
+        assert!(strategy.metrics.net_funding.compare(
+            rust_decimal::prelude::ToPrimitive::to_f64(&strategy.ledger.funding).unwrap()
+        ));
+        assert!(strategy.ledger.fees > Decimal::ZERO);
+    }
+
+    #[test]
+    fn commission() {
+        let ctx = Fixture::ohlc_ctx(&[
+            (100.0, 100.0, 100.0, 100.0),
+            (100.0, 100.0, 100.0, 100.0),
+            (110.0, 110.0, 110.0, 110.0),
+            (110.0, 110.0, 110.0, 110.0),
+        ]);
+        let mut strategy = Strategy::new(
+            ctx.clone(),
+            StrategyConfig {
+                execution: StrategyExecutionConfig {
+                    commission: Some(StrategyCommission::Percent(0.001)),
+                    ..StrategyExecutionConfig::default()
+                },
+                ..StrategyConfig::default()
+            },
+        );
+        let signals = [
+            Some(TradeDirection::Long),
+            None,
+            Some(TradeDirection::Short),
+            None,
+        ];
+
+        for tick in ctx.clone() {
+            strategy.next(signals[tick]);
+        }
+
+        let trade = &strategy.trades[0];
+        assert!(trade.commission.compare(0.1 + 0.11));
+        assert!(trade.pnl.compare(10.0 - 0.21));
+        assert!(strategy.closedtrades().commission(0).unwrap().compare(0.21));
+        assert_eq!(strategy.ledger.realized_pnl, to_decimal(10.0));
+        // Reversal pays commission of both the exit and the new entry.
+        assert_eq!(strategy.ledger.fees, to_decimal(0.32));
+        assert_eq!(strategy.ledger.cash, to_decimal(1119.68));
+        assert_eq!(strategy.ledger.equity(110.0), to_decimal(1009.68));
+        assert!(strategy.metrics.equity.compare(1009.68));
+    }
+
+    #[test]
+    fn strategy_net_profit_no_drift() {
+        let bars: Vec<(f64, f64, f64, f64)> = (0..2000)
+            .map(|tick| if tick % 2 == 0 { 0.1 } else { 0.3 })
+            .map(|price| (price, price, price, price))
+            .collect();
+        let ctx = Fixture::ohlc_ctx(&bars);
+        let mut strategy = Strategy::new(
+            ctx.clone(),
+            StrategyConfig {
+                on_bar_close: true,
+                ..StrategyConfig::default()
+            },
+        );
+
+        let mut net_profit = 0.0;
+        for tick in ctx.clone() {
+            if tick % 2 == 0 {
+                strategy.next(StrategySignal::Long);
+            } else {
+                strategy.next(StrategySignal::Exit);
+                net_profit += strategy.trades.last().unwrap().pnl;
+            }
+        }
+
+        assert_eq!(strategy.metrics.closed_trades, 1000);
+        assert_eq!(strategy.metrics.net_profit, 200.0);
+        assert_eq!(strategy.metrics.equity, 1200.0);
+        assert_ne!(net_profit, 200.0);
+    }
+}
@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        asset::symbol_info::SymbolInfo,
+        core::{context::Context, error::PaceError, incremental::Incremental},
+        strategy::{
+            strategy::{Strategy, StrategyConfig, StrategyMarginConfig},
+            trade::TradeDirection,
+        },
+        testing::{comparison::FloatComparison, fixture::Fixture},
+    };
+
+    fn create_strategy(ctx: Context, margin: f64) -> Strategy {
+        return Strategy::new(
+            ctx,
+            StrategyConfig {
+                buy_with_equity: true,
+                initial_capital: 1000.0,
+                margin: Some(StrategyMarginConfig {
+                    margin_long: margin,
+                    margin_short: margin,
+                }),
+                ..StrategyConfig::default()
+            },
+        );
+    }
+
+    #[test]
+    fn partial_margin_call() {
+        let ctx = Fixture::ohlc_ctx(&[
+            (100.0, 100.0, 100.0, 100.0),
+            (100.0, 100.0, 100.0, 100.0),
+            (80.0, 80.0, 80.0, 80.0),
+            (80.0, 80.0, 80.0, 80.0),
+        ]);
+        let mut strategy = create_strategy(ctx.clone(), 0.5);
+        let signals = [Some(TradeDirection::Long), None, None, None];
+
+        for tick in ctx.clone() {
+            strategy.next(signals[tick]);
+
+            if tick == 1 {
+                // Fully leveraged, so equity equals margin at the entry price.
+                assert!(strategy.trades[0].fill_size.unwrap().compare(20.0));
+                assert!(strategy.liquidation_price().unwrap().compare(100.0));
+                assert!(strategy.events.on_margin_call.is_none());
+            }
+            if tick == 2 {
+                // Equity is 600 and margin is 800, so 4 * 200 / 80 contracts are liquidated.
+                let e = strategy.events.on_margin_call.as_ref().unwrap();
+                assert!(e.liquidation_price.compare(100.0));
+                assert!(e.trade.is_liquidated);
+                assert!(e.trade.fill_size.unwrap().compare(10.0));
+            }
+            if tick == 3 {
+                assert!(strategy.events.on_margin_call.is_none());
+            }
+        }
+
+        assert_eq!(strategy.trades.len(), 2);
+        let liquidated = &strategy.trades[0];
+        assert!(liquidated.is_closed);
+        assert_eq!(liquidated.exit_tick, Some(2));
+        assert!(liquidated.exit_price.unwrap().compare(80.0));
+        assert!(liquidated.pnl.compare(-200.0));
+
+        let remaining = &strategy.trades[1];
+        assert!(!remaining.is_closed);
+        assert!(!remaining.is_liquidated);
+        assert!(remaining.fill_size.unwrap().compare(10.0));
+
+        assert!(strategy.metrics.equity.compare(600.0));
+        assert_eq!(strategy.metrics.liquidations, 1);
+        assert!(strategy.liquidation_price().unwrap().compare(40.0));
+    }
+
+    #[test]
+    fn margin_call_truncated_to_qty_step() {
+        let ctx = Fixture::ohlc_ctx_with_syminfo(
+            &[
+                (100.0, 100.0, 100.0, 100.0),
+                (100.0, 100.0, 100.0, 100.0),
+                (80.0, 80.0, 80.0, 80.0),
+            ],
+            SymbolInfo {
+                qty_step: 1.0,
+                ..SymbolInfo::default()
+            },
+        );
+        let mut strategy = create_strategy(ctx.clone(), 0.5);
+        let signals = [Some(TradeDirection::Long), None, None];
+
+        for tick in ctx.clone() {
+            strategy.next(signals[tick]);
+        }
+
+        // Shortfall of 200 covers 2.5 contracts at 80, truncated to 2, so 4 * 2 contracts are liquidated.
+        assert_eq!(strategy.trades.len(), 2);
+        assert!(strategy.trades[0].fill_size.unwrap().compare(8.0));
+        assert!(strategy.trades[1].fill_size.unwrap().compare(12.0));
+    }
+
+    #[test]
+    fn long_liquidation() {
+        let ctx = Fixture::ohlc_ctx(&[
+            (100.0, 100.0, 100.0, 100.0),
+            (100.0, 100.0, 100.0, 100.0),
+            (40.0, 45.0, 35.0, 40.0),
+            (45.0, 50.0, 40.0, 45.0),
+        ]);
+        let mut strategy = create_strategy(ctx.clone(), 0.5);
+        let signals = [Some(TradeDirection::Long), None, None, None];
+
+        for tick in ctx.clone() {
+            strategy.next(signals[tick]);
+
+            if tick == 2 {
+                let e = strategy.events.on_margin_call.as_ref().unwrap();
+                assert!(e.trade.is_liquidated);
+                assert!(strategy.events.on_trade_exit.is_some());
+            }
+        }
+
+        // Contracts needed to cover the shortfall exceed the position, so it's closed entirely.
+        let trade = &strategy.trades[0];
+        assert_eq!(strategy.trades.len(), 1);
+        assert!(trade.is_closed);
+        assert_eq!(trade.exit_tick, Some(2));
+        assert!(trade.exit_price.unwrap().compare(40.0));
+        assert!(strategy.metrics.equity.compare(-200.0));
+        assert_eq!(strategy.metrics.liquidations, 1);
+        assert!(strategy.liquidation_price().is_none());
+    }
+
+    #[test]
+    fn short_liquidation_gap() {
+        let ctx = Fixture::ohlc_ctx(&[
+            (100.0, 100.0, 100.0, 100.0),
+            (100.0, 100.0, 100.0, 100.0),
+            (130.0, 135.0, 125.0, 130.0),
+        ]);
+        let mut strategy = create_strategy(ctx.clone(), 0.25);
+        let signals = [Some(TradeDirection::Short), None, None];
+
+        for tick in ctx.clone() {
+            strategy.next(signals[tick]);
+        }
+
+        // size = 4000 / 100 = 40. Bar opened above liquidation price, so the position is closed at open.
+        let trade = &strategy.trades[0];
+        assert!(trade.is_liquidated);
+        assert_eq!(trade.exit_tick, Some(2));
+        assert!(trade.exit_price.unwrap().compare(130.0));
+        assert!(trade.pnl.compare(-1200.0));
+    }
+
+    #[test]
+    fn invalid_margin() {
+        let margin = StrategyMarginConfig {
+            margin_short: 0.0,
+            ..StrategyMarginConfig::default()
+        };
+        let error = margin.validate().unwrap_err();
+        assert!(matches!(error, PaceError::InvalidData(_)));
+        assert!(StrategyMarginConfig::default().validate().is_ok());
+
+        let ctx = Fixture::ohlc_ctx(&[(100.0, 100.0, 100.0, 100.0)]);
+        let error = Strategy::try_new(
+            ctx.clone(),
+            StrategyConfig {
+                margin: Some(margin),
+                ..StrategyConfig::default()
+            },
+        )
+        .err()
+        .unwrap();
+        assert!(matches!(error, PaceError::InvalidData(_)));
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let ctx = Fixture::ohlc_ctx(&[
+            (100.0, 100.0, 100.0, 100.0),
+            (100.0, 100.0, 100.0, 100.0),
+            (10.0, 10.0, 1.0, 5.0),
+        ]);
+        let mut strategy = Strategy::new(
+            ctx.clone(),
+            StrategyConfig {
+                buy_with_equity: true,
+                ..StrategyConfig::default()
+            },
+        );
+        let signals = [Some(TradeDirection::Long), None, None];
+
+        for tick in ctx.clone() {
+            strategy.next(signals[tick]);
+        }
+
+        assert!(strategy.liquidation_price().is_none());
+        assert!(!strategy.trades[0].is_closed);
+        assert_eq!(strategy.metrics.liquidations, 0);
+    }
+}
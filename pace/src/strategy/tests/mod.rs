@@ -1 +1,15 @@
+mod bar_magnifier_test;
+mod execution_test;
+mod funding_test;
+mod latency_test;
+mod ledger_test;
+mod margin_test;
+mod multi_strategy_test;
+mod order_test;
+mod position_sizer_test;
+mod rebalance_test;
+mod risk_test;
+mod signal_test;
 mod strategy_test;
+mod symbol_info_test;
+mod trade_list_test;
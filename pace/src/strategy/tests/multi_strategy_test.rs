@@ -0,0 +1,218 @@
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use crate::{
+        core::{error::PaceError, incremental::Incremental},
+        strategy::{
+            execution::{StrategyCommission, StrategyExecutionConfig},
+            ledger::LedgerEntryKind,
+            multi_strategy::{MultiStrategy, MultiStrategyConfig, StrategySleeveConfig},
+            signal::StrategySignal,
+            strategy::{Strategy, StrategyConfig},
+        },
+        testing::{comparison::FloatComparison, fixture::Fixture},
+    };
+
+    fn sleeve(name: &str, allocation: f64) -> StrategySleeveConfig {
+        return StrategySleeveConfig {
+            name: name.to_string(),
+            allocation,
+            strategy: StrategyConfig {
+                buy_with_equity: true,
+                ..StrategyConfig::default()
+            },
+        };
+    }
+
+    fn bars() -> Vec<(f64, f64, f64, f64)> {
+        return vec![
+            (100.0, 100.0, 100.0, 100.0),
+            (100.0, 110.0, 100.0, 110.0),
+            (110.0, 120.0, 110.0, 120.0),
+            (120.0, 120.0, 90.0, 90.0),
+            (90.0, 100.0, 90.0, 100.0),
+        ];
+    }
+
+    #[test]
+    fn sleeves_match_standalone_strategies() {
+        let signals_a = [
+            StrategySignal::Long,
+            StrategySignal::Hold,
+            StrategySignal::Exit,
+            StrategySignal::Hold,
+            StrategySignal::Hold,
+        ];
+        let signals_b = [
+            StrategySignal::Hold,
+            StrategySignal::Short,
+            StrategySignal::Hold,
+            StrategySignal::Long,
+            StrategySignal::Hold,
+        ];
+
+        let ctx = Fixture::ohlc_ctx(&bars());
+        let mut multi = MultiStrategy::new(
+            ctx.clone(),
+            MultiStrategyConfig {
+                initial_capital: 10000.0,
+                sleeves: vec![sleeve("rsi", 0.6), sleeve("macd", 0.4)],
+                ..MultiStrategyConfig::default()
+            },
+        )
+        .unwrap();
+        for tick in ctx.clone() {
+            multi.next(vec![signals_a[tick], signals_b[tick]]);
+        }
+
+        for (allocation, signals, name) in [(6000.0, signals_a, "rsi"), (4000.0, signals_b, "macd")]
+        {
+            let ctx = Fixture::ohlc_ctx(&bars());
+            let mut strategy = Strategy::new(
+                ctx.clone(),
+                StrategyConfig {
+                    buy_with_equity: true,
+                    initial_capital: allocation,
+                    ..StrategyConfig::default()
+                },
+            );
+            for tick in ctx.clone() {
+                strategy.next(signals[tick]);
+            }
+            let sleeve = multi.sleeve(name).unwrap();
+            assert!(sleeve
+                .strategy
+                .metrics
+                .equity
+                .compare(strategy.metrics.equity));
+            assert_eq!(sleeve.strategy.trades, strategy.trades);
+        }
+
+        let equity: f64 = multi
+            .sleeves
+            .iter()
+            .map(|sleeve| sleeve.strategy.metrics.equity)
+            .sum();
+        assert!(multi.metrics.equity.compare(equity));
+        assert_eq!(multi.metrics.closed_trades, 2);
+    }
+
+    #[test]
+    fn netted_position() {
+        let ctx = Fixture::ohlc_ctx(&bars());
+        let mut multi = MultiStrategy::new(
+            ctx.clone(),
+            MultiStrategyConfig {
+                initial_capital: 2000.0,
+                sleeves: vec![sleeve("long", 0.5), sleeve("short", 0.25)],
+                ..MultiStrategyConfig::default()
+            },
+        )
+        .unwrap();
+        let mut net_position = Vec::new();
+        let mut gross_position = Vec::new();
+
+        for tick in ctx.clone() {
+            if tick == 0 {
+                multi.next(vec![StrategySignal::Long, StrategySignal::Short]);
+            } else {
+                multi.next(vec![]);
+            }
+            net_position.push(multi.metrics.net_position);
+            gross_position.push(multi.metrics.gross_position);
+        }
+
+        assert_eq!(net_position, vec![0.0, 5.0, 5.0, 5.0, 5.0]);
+        assert_eq!(gross_position, vec![0.0, 15.0, 15.0, 15.0, 15.0]);
+        assert!(multi.unallocated_capital().compare(500.0));
+        // Long 10 and short 5 from 100. Equity peaks at 120 and bottoms at 90.
+        assert!(multi.metrics.equity.compare(2000.0));
+        assert!(multi.metrics.equity_max.compare(2000.0 + 5.0 * 20.0));
+        assert!(multi.metrics.max_drawdown.compare(5.0 * 30.0));
+        // Long 10 and short 5 are executed as a single buy of 5.
+        assert_eq!(multi.ledger.position, Decimal::from(5));
+        assert_eq!(
+            multi
+                .ledger
+                .entries
+                .iter()
+                .filter(|entry| entry.kind == LedgerEntryKind::Fill)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn netted_commission() {
+        let ctx = Fixture::ohlc_ctx(&bars()[0..3]);
+        let mut multi = MultiStrategy::new(
+            ctx.clone(),
+            MultiStrategyConfig {
+                initial_capital: 2000.0,
+                sleeves: vec![sleeve("long", 0.5), sleeve("short", 0.25)],
+                execution: StrategyExecutionConfig {
+                    commission: Some(StrategyCommission::Percent(0.001)),
+                    ..StrategyExecutionConfig::default()
+                },
+            },
+        )
+        .unwrap();
+
+        for tick in ctx.clone() {
+            match tick {
+                0 => multi.next(vec![StrategySignal::Long, StrategySignal::Short]),
+                1 => multi.next(vec![StrategySignal::Exit, StrategySignal::Hold]),
+                _ => multi.next(vec![]),
+            }
+        }
+
+        // Buy of 5 at 100, then sell of 10 at 110.
+        assert_eq!(multi.ledger.position, Decimal::from(-5));
+        assert!(multi.metrics.net_position.compare(-5.0));
+        assert!(multi.metrics.commission.compare(0.5 + 1.1));
+        // Commission of the netted buy is split by quantity traded, 10 long and 5 short.
+        assert!(multi.sleeves[0].commission.compare(0.5 * 10.0 / 15.0 + 1.1));
+        assert!(multi.sleeves[1].commission.compare(0.5 * 5.0 / 15.0));
+        assert_eq!(multi.sleeves[0].strategy.metrics.equity, 1100.0);
+        assert!(multi.sleeves[0]
+            .equity()
+            .compare(1100.0 - multi.sleeves[0].commission));
+        // Sleeve a gains 100, sleeve b loses 100 at 120.
+        assert!(multi.metrics.equity.compare(2000.0 - 1.6));
+        assert!(multi.metrics.net_profit.compare(100.0 - 1.6));
+    }
+
+    #[test]
+    fn invalid_allocation() {
+        let ctx = Fixture::ohlc_ctx(&bars());
+
+        for sleeves in [
+            vec![sleeve("a", 0.5), sleeve("b", -0.1)],
+            vec![sleeve("a", f64::NAN)],
+            vec![sleeve("a", 0.6), sleeve("b", 0.5)],
+        ] {
+            let error = MultiStrategy::new(
+                ctx.clone(),
+                MultiStrategyConfig {
+                    initial_capital: 1000.0,
+                    sleeves,
+                    ..MultiStrategyConfig::default()
+                },
+            )
+            .err()
+            .unwrap();
+            assert!(matches!(error, PaceError::InvalidData(_)));
+        }
+
+        assert!(MultiStrategy::new(
+            ctx.clone(),
+            MultiStrategyConfig {
+                initial_capital: 1000.0,
+                sleeves: (0..10).map(|i| sleeve(&i.to_string(), 0.1)).collect(),
+                ..MultiStrategyConfig::default()
+            },
+        )
+        .is_ok());
+    }
+}
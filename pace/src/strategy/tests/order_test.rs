@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::incremental::Incremental,
+        strategy::{
+            order::StrategyEntryOrder,
+            signal::StrategySignal,
+            strategy::{Strategy, StrategyConfig},
+            trade::TradeDirection,
+        },
+        testing::{comparison::FloatComparison, fixture::Fixture},
+    };
+
+    /// Places `orders` before the first bar and returns the strategy after the last bar.
+    fn run(bars: &[(f64, f64, f64, f64)], orders: Vec<StrategyEntryOrder>) -> Strategy {
+        let ctx = Fixture::ohlc_ctx(bars);
+        let mut strategy = Strategy::new(ctx.clone(), StrategyConfig::default());
+        for tick in ctx.clone() {
+            if tick == 0 {
+                for order in orders.clone() {
+                    strategy.place_entry(order);
+                }
+            }
+            strategy.next(StrategySignal::Hold);
+        }
+        return strategy;
+    }
+
+    #[test]
+    fn bracket_with_market_entry() {
+        let strategy = run(
+            &[
+                (100.0, 100.0, 100.0, 100.0),
+                (100.0, 112.0, 99.0, 105.0),
+                (105.0, 105.0, 105.0, 105.0),
+            ],
+            vec![StrategyEntryOrder::bracket(
+                "Bracket",
+                TradeDirection::Long,
+                None,
+                100.0,
+                95.0,
+                110.0,
+            )],
+        );
+        assert_eq!(strategy.trades.len(), 1);
+        let trade = &strategy.trades[0];
+        assert_eq!(trade.entry_id, "Bracket");
+        assert_eq!(trade.entry_tick, Some(1));
+        assert!(trade.entry_price.unwrap().compare(100.0));
+        assert_eq!(trade.exit_id.as_deref(), Some("Bracket exit"));
+        assert_eq!(trade.exit_tick, Some(1));
+        assert!(trade.exit_price.unwrap().compare(110.0));
+        assert!(strategy.exit_order.is_none());
+    }
+
+    #[test]
+    fn bracket_exits_activate_when_entry_fills() {
+        let order = StrategyEntryOrder::bracket(
+            "Breakout",
+            TradeDirection::Long,
+            Some(105.0),
+            100.0,
+            95.0,
+            110.0,
+        );
+        assert_eq!(order.stop, Some(105.0));
+
+        let strategy = run(
+            &[
+                (100.0, 100.0, 100.0, 100.0),
+                (100.0, 101.0, 90.0, 100.0),
+                (100.0, 108.0, 94.0, 96.0),
+                (96.0, 97.0, 93.0, 94.0),
+            ],
+            vec![order],
+        );
+        assert_eq!(strategy.trades.len(), 1);
+        let trade = &strategy.trades[0];
+        assert_eq!(trade.entry_tick, Some(2));
+        assert!(trade.entry_price.unwrap().compare(105.0));
+        assert_eq!(trade.exit_tick, Some(3));
+        assert!(trade.exit_price.unwrap().compare(95.0));
+    }
+
+    fn breakout_orders(oca_group: Option<&str>) -> Vec<StrategyEntryOrder> {
+        return vec![
+            StrategyEntryOrder {
+                stop: Some(105.0),
+                oca_group: oca_group.map(|x| x.to_string()),
+                ..StrategyEntryOrder::new("Long", TradeDirection::Long)
+            },
+            StrategyEntryOrder {
+                stop: Some(95.0),
+                oca_group: oca_group.map(|x| x.to_string()),
+                ..StrategyEntryOrder::new("Short", TradeDirection::Short)
+            },
+        ];
+    }
+
+    #[test]
+    fn one_cancels_other() {
+        let bars = [
+            (100.0, 100.0, 100.0, 100.0),
+            (100.0, 106.0, 99.0, 104.0),
+            (104.0, 104.0, 90.0, 92.0),
+        ];
+
+        let strategy = run(&bars, breakout_orders(Some("Breakout")));
+        assert_eq!(strategy.trades.len(), 1);
+        assert!(strategy.trades[0].is_active());
+        assert!(strategy.entry_orders.is_empty());
+
+        let strategy = run(&bars, breakout_orders(None));
+        assert_eq!(strategy.trades.len(), 2);
+        let reversed = &strategy.trades[0];
+        assert_eq!(reversed.exit_tick, Some(2));
+        assert_eq!(reversed.exit_id.as_deref(), Some("Short"));
+        assert!(reversed.exit_price.unwrap().compare(95.0));
+        assert_eq!(strategy.trades[1].direction, TradeDirection::Short);
+    }
+
+    #[test]
+    fn stop_limit_entry() {
+        let order = StrategyEntryOrder {
+            stop: Some(105.0),
+            limit: Some(103.0),
+            ..StrategyEntryOrder::new("StopLimit", TradeDirection::Long)
+        };
+
+        // Limit is reached before the stop on the second bar, but it's active only after the stop.
+        let strategy = run(
+            &[
+                (100.0, 100.0, 100.0, 100.0),
+                (100.0, 106.0, 99.0, 104.0),
+                (104.0, 104.0, 102.0, 103.0),
+            ],
+            vec![order.clone()],
+        );
+        assert_eq!(strategy.trades.len(), 1);
+        assert_eq!(strategy.trades[0].entry_tick, Some(2));
+        assert!(strategy.trades[0].entry_price.unwrap().compare(103.0));
+        assert!(strategy.entry_orders.is_empty());
+
+        // Price at the stop is below the limit, so it's filled right away.
+        let strategy = run(
+            &[(100.0, 100.0, 100.0, 100.0), (100.0, 108.0, 100.0, 107.0)],
+            vec![StrategyEntryOrder {
+                limit: Some(106.0),
+                ..order
+            }],
+        );
+        assert_eq!(strategy.trades[0].entry_tick, Some(1));
+        assert!(strategy.trades[0].entry_price.unwrap().compare(105.0));
+    }
+
+    #[test]
+    fn cancel_entry() {
+        let ctx = Fixture::ohlc_ctx(&[
+            (100.0, 100.0, 100.0, 100.0),
+            (100.0, 100.0, 100.0, 100.0),
+            (100.0, 110.0, 100.0, 100.0),
+        ]);
+        let mut strategy = Strategy::new(ctx.clone(), StrategyConfig::default());
+        for tick in ctx.clone() {
+            if tick == 0 {
+                strategy.place_entry(StrategyEntryOrder {
+                    stop: Some(105.0),
+                    ..StrategyEntryOrder::new("Long", TradeDirection::Long)
+                });
+            }
+            if tick == 1 {
+                strategy.cancel_entry("Long");
+            }
+            strategy.next(StrategySignal::Hold);
+        }
+        assert!(strategy.trades.is_empty());
+        assert!(strategy.entry_orders.is_empty());
+    }
+}
@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::incremental::Incremental,
+        strategy::{
+            position_sizer::{kelly_fraction, PositionSizer, StrategyPositionSizer},
+            signal::StrategySignal,
+            strategy::{Strategy, StrategyConfig},
+            trade::{Trade, TradeDirection},
+        },
+        testing::{comparison::FloatComparison, fixture::Fixture},
+    };
+
+    fn run(
+        bars: &[(f64, f64, f64, f64)],
+        position_sizer: StrategyPositionSizer,
+        signals: &[StrategySignal],
+    ) -> Strategy {
+        let ctx = Fixture::ohlc_ctx(bars);
+        let mut strategy = Strategy::new(
+            ctx.clone(),
+            StrategyConfig {
+                position_sizer: Some(position_sizer),
+                ..StrategyConfig::default()
+            },
+        );
+        for tick in ctx.clone() {
+            strategy.next(signals[tick]);
+        }
+        return strategy;
+    }
+
+    fn closed_trade(pnl: f64) -> Trade {
+        let mut trade = Trade::new(TradeDirection::Long);
+        trade.is_closed = true;
+        trade.pnl = pnl;
+        return trade;
+    }
+
+    #[test]
+    fn kelly_criterion() {
+        assert!(kelly_fraction(0.5, 2.0).compare(0.25));
+        assert!(kelly_fraction(0.6, 1.0).compare(0.2));
+        assert!(kelly_fraction(0.3, 1.0).compare(-0.4));
+        assert!(kelly_fraction(0.5, 0.0).compare(0.0));
+    }
+
+    #[test]
+    fn fixed_fractional() {
+        let strategy = run(
+            &[(100.0, 100.0, 100.0, 100.0); 3],
+            StrategyPositionSizer::FixedFractional {
+                risk: 0.01,
+                stop_distance: 2.0,
+            },
+            &[
+                StrategySignal::Long,
+                StrategySignal::Hold,
+                StrategySignal::Hold,
+            ],
+        );
+        assert!(strategy.trades[0].fill_size.unwrap().compare(5.0));
+    }
+
+    #[test]
+    fn fixed_fractional_limited_by_buying_power() {
+        let strategy = run(
+            &[(100.0, 100.0, 100.0, 100.0); 3],
+            StrategyPositionSizer::FixedFractional {
+                risk: 0.1,
+                stop_distance: 1.0,
+            },
+            &[
+                StrategySignal::Long,
+                StrategySignal::Hold,
+                StrategySignal::Hold,
+            ],
+        );
+        assert!(strategy.trades[0].fill_size.unwrap().compare(10.0));
+    }
+
+    #[test]
+    fn atr_waits_for_warm_up() {
+        let strategy = run(
+            &[(100.0, 101.0, 99.0, 100.0); 4],
+            StrategyPositionSizer::Atr {
+                risk: 0.02,
+                length: 2,
+                multiplier: 2.0,
+            },
+            &[
+                StrategySignal::Long,
+                StrategySignal::Long,
+                StrategySignal::Hold,
+                StrategySignal::Hold,
+            ],
+        );
+        assert_eq!(strategy.trades.len(), 1);
+        let trade = &strategy.trades[0];
+        assert_eq!(trade.entry_tick, Some(2));
+        // ATR = 2, stop distance = 4, risk = 20
+        assert!(trade.fill_size.unwrap().compare(5.0));
+    }
+
+    #[test]
+    fn volatility_target() {
+        let strategy = run(
+            &[
+                (100.0, 100.0, 100.0, 100.0),
+                (110.0, 110.0, 110.0, 110.0),
+                (100.0, 100.0, 100.0, 100.0),
+                (100.0, 100.0, 100.0, 100.0),
+            ],
+            StrategyPositionSizer::VolatilityTarget {
+                target: 0.01,
+                length: 2,
+            },
+            &[
+                StrategySignal::Long,
+                StrategySignal::Long,
+                StrategySignal::Long,
+                StrategySignal::Hold,
+            ],
+        );
+        assert_eq!(strategy.trades.len(), 1);
+        let trade = &strategy.trades[0];
+        assert_eq!(trade.entry_tick, Some(3));
+
+        let volatility = (0.1 - (100.0 / 110.0 - 1.0)) / f64::sqrt(2.0);
+        assert!(trade
+            .fill_size
+            .unwrap()
+            .compare(1000.0 * 0.01 / volatility / 100.0));
+    }
+
+    #[test]
+    fn kelly() {
+        let ctx = Fixture::ohlc_ctx(&[(1.0, 1.0, 1.0, 1.0)]);
+        let sizer = PositionSizer::new(
+            ctx,
+            StrategyPositionSizer::Kelly {
+                fraction: 0.5,
+                max_fraction: 0.2,
+                min_trades: 4,
+            },
+        );
+
+        let mut trades = vec![closed_trade(20.0), closed_trade(-10.0), closed_trade(20.0)];
+        // Not enough trades, so the cap is used.
+        assert!(sizer
+            .size(1000.0, 100.0, 1.0, &trades)
+            .unwrap()
+            .compare(2.0));
+
+        // Win rate = 0.5, payoff = 2, kelly = 0.25, half-kelly = 0.125
+        trades.push(closed_trade(-10.0));
+        assert!(sizer
+            .size(1000.0, 100.0, 1.0, &trades)
+            .unwrap()
+            .compare(1.25));
+
+        // Negative kelly means no position.
+        trades.extend([
+            closed_trade(-10.0),
+            closed_trade(-10.0),
+            closed_trade(-10.0),
+        ]);
+        assert!(sizer
+            .size(1000.0, 100.0, 1.0, &trades)
+            .unwrap()
+            .compare(0.0));
+    }
+}
@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use crate::{
+        core::{
+            context::Context, data_provider::DataProvider,
+            in_memory_data_provider::InMemoryDataProvider, incremental::Incremental,
+        },
+        strategy::{
+            execution::StrategyCommission,
+            rebalance::{RebalanceSchedule, Rebalancer, RebalancerConfig},
+        },
+        testing::comparison::FloatComparison,
+    };
+
+    /// 2024-01-29 00:00:00 UTC.
+    const START_TIME: u64 = 1706486400;
+
+    /// Creates a daily data provider, where every bar has `open == close`.
+    fn create_data(prices: &[f64]) -> Arc<dyn DataProvider + Send + Sync> {
+        let values: Vec<Option<f64>> = prices.iter().map(|x| Some(*x)).collect();
+        let time = (0..prices.len())
+            .map(|i| Some(Duration::from_secs(START_TIME + i as u64 * 86400)))
+            .collect();
+        return InMemoryDataProvider::new(
+            values.clone(),
+            values.clone(),
+            values.clone(),
+            values.clone(),
+            values,
+            time,
+        )
+        .to_arc();
+    }
+
+    fn run(
+        a: &[f64],
+        b: &[f64],
+        config: RebalancerConfig,
+        weights: &[Option<Vec<f64>>],
+    ) -> Rebalancer {
+        let data_a = create_data(a);
+        let ctx = Context::new(Arc::clone(&data_a));
+        let mut rebalancer = Rebalancer::new(ctx.clone(), vec![data_a, create_data(b)], config);
+
+        for tick in ctx.clone() {
+            rebalancer.next(weights.get(tick).cloned().flatten());
+        }
+
+        return rebalancer;
+    }
+
+    #[test]
+    fn monthly_schedule() {
+        let rebalancer = run(
+            &[100.0, 110.0, 120.0, 150.0, 150.0, 150.0],
+            &[100.0; 6],
+            RebalancerConfig {
+                weights: vec![0.5, 0.5],
+                schedule: Some(RebalanceSchedule::Monthly),
+                ..RebalancerConfig::default()
+            },
+            &[],
+        );
+
+        // Initial allocation on January 29 and rebalance on February 1.
+        let ticks: Vec<usize> = rebalancer.events.iter().map(|event| event.tick).collect();
+        assert_eq!(ticks, vec![0, 3]);
+
+        let event = &rebalancer.events[1];
+        assert!(event.equity.compare(1250.0));
+        assert!(event.traded_value.compare(250.0));
+        assert!(event.turnover.compare(0.2));
+
+        assert!(rebalancer.positions[0].compare(625.0 / 150.0));
+        assert!(rebalancer.positions[1].compare(6.25));
+        assert!(rebalancer.metrics.equity.compare(1250.0));
+        assert!(rebalancer.metrics.net_profit.compare(250.0));
+        assert!(rebalancer.metrics.traded_value.compare(1250.0));
+        assert!(rebalancer.metrics.turnover.compare(1.2));
+    }
+
+    #[test]
+    fn weekly_and_daily_schedule() {
+        // January 29 is Monday.
+        let prices = [100.0; 10];
+        let weekly = run(
+            &prices,
+            &prices,
+            RebalancerConfig {
+                weights: vec![0.5, 0.5],
+                schedule: Some(RebalanceSchedule::Weekly),
+                ..RebalancerConfig::default()
+            },
+            &[],
+        );
+        assert_eq!(weekly.metrics.rebalances, 2);
+        assert_eq!(weekly.events[1].tick, 7);
+
+        let daily = run(
+            &prices,
+            &prices,
+            RebalancerConfig {
+                weights: vec![0.5, 0.5],
+                schedule: Some(RebalanceSchedule::Daily),
+                ..RebalancerConfig::default()
+            },
+            &[],
+        );
+        assert_eq!(daily.metrics.rebalances, 10);
+    }
+
+    #[test]
+    fn drift_threshold() {
+        let rebalancer = run(
+            &[100.0, 110.0, 120.0, 130.0, 130.0],
+            &[100.0; 5],
+            RebalancerConfig {
+                weights: vec![0.5, 0.5],
+                drift_threshold: Some(0.05),
+                ..RebalancerConfig::default()
+            },
+            &[],
+        );
+
+        let ticks: Vec<usize> = rebalancer.events.iter().map(|event| event.tick).collect();
+        assert_eq!(ticks, vec![0, 3]);
+        assert!(rebalancer.weights()[0].compare(0.5));
+    }
+
+    #[test]
+    fn commission() {
+        let rebalancer = run(
+            &[100.0, 100.0],
+            &[100.0, 100.0],
+            RebalancerConfig {
+                weights: vec![0.5, 0.5],
+                schedule: Some(RebalanceSchedule::Bars(10)),
+                commission: Some(StrategyCommission::Percent(0.01)),
+                ..RebalancerConfig::default()
+            },
+            &[],
+        );
+
+        assert!(rebalancer.metrics.costs.compare(10.0));
+        assert!(rebalancer.cash.compare(-10.0));
+        assert!(rebalancer.metrics.equity.compare(990.0));
+    }
+
+    #[test]
+    fn signal_weights() {
+        let rebalancer = run(
+            &[100.0, 100.0, 100.0, 200.0],
+            &[100.0; 4],
+            RebalancerConfig {
+                weights: vec![0.5, 0.5],
+                schedule: Some(RebalanceSchedule::Bars(2)),
+                ..RebalancerConfig::default()
+            },
+            &[None, Some(vec![1.0, 0.0]), None, None],
+        );
+
+        // New weights are applied on the next scheduled rebalance.
+        let ticks: Vec<usize> = rebalancer.events.iter().map(|event| event.tick).collect();
+        assert_eq!(ticks, vec![0, 2]);
+        assert!(rebalancer.positions[0].compare(10.0));
+        assert!(rebalancer.positions[1].compare(0.0));
+        assert!(rebalancer.metrics.equity.compare(2000.0));
+    }
+}
@@ -0,0 +1,200 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::{context::Context, incremental::Incremental},
+        strategy::{
+            risk::{StrategyRiskConfig, StrategyRiskRule, StrategyRiskValue},
+            signal::StrategySignal,
+            strategy::{Strategy, StrategyConfig},
+        },
+        testing::{comparison::FloatComparison, fixture::Fixture},
+    };
+
+    fn run(
+        ctx: Context,
+        risk: StrategyRiskConfig,
+        signals: &[StrategySignal],
+    ) -> (Strategy, Vec<Option<StrategyRiskRule>>) {
+        let mut strategy = Strategy::new(
+            ctx.clone(),
+            StrategyConfig {
+                on_bar_close: true,
+                risk,
+                ..StrategyConfig::default()
+            },
+        );
+        let mut rules: Vec<Option<StrategyRiskRule>> = Vec::new();
+
+        for tick in ctx.clone() {
+            strategy.next(signals[tick]);
+            rules.push(strategy.events.on_risk_rule.as_ref().map(|e| e.rule));
+        }
+
+        return (strategy, rules);
+    }
+
+    #[test]
+    fn max_drawdown() {
+        let ctx = Fixture::ohlc_ctx(&[
+            (100.0, 100.0, 100.0, 100.0),
+            (150.0, 150.0, 150.0, 150.0),
+            (120.0, 120.0, 120.0, 120.0),
+            (90.0, 90.0, 90.0, 90.0),
+            (100.0, 100.0, 100.0, 100.0),
+        ]);
+        let (strategy, rules) = run(
+            ctx,
+            StrategyRiskConfig {
+                max_drawdown: Some(StrategyRiskValue::Cash(50.0)),
+                ..StrategyRiskConfig::default()
+            },
+            &[
+                StrategySignal::Long,
+                StrategySignal::Hold,
+                StrategySignal::Hold,
+                StrategySignal::Hold,
+                StrategySignal::Long,
+            ],
+        );
+
+        assert_eq!(
+            rules,
+            vec![
+                None,
+                None,
+                None,
+                Some(StrategyRiskRule::MaxDrawdown),
+                Some(StrategyRiskRule::MaxDrawdown),
+            ]
+        );
+        assert_eq!(strategy.trades.len(), 1);
+        assert_eq!(strategy.trades[0].exit_tick, Some(3));
+        assert!(strategy.metrics.equity.compare(990.0));
+        assert_eq!(strategy.risk.halted_by, Some(StrategyRiskRule::MaxDrawdown));
+    }
+
+    #[test]
+    fn max_intraday_loss() {
+        let ctx = Fixture::daily_price_ctx(&[100.0, 100.0, 80.0, 70.0, 70.0, 70.0], 3);
+        let (strategy, rules) = run(
+            ctx,
+            StrategyRiskConfig {
+                max_intraday_loss: Some(StrategyRiskValue::Percent(0.01)),
+                ..StrategyRiskConfig::default()
+            },
+            &[
+                StrategySignal::Long,
+                StrategySignal::Hold,
+                StrategySignal::Short,
+                StrategySignal::Hold,
+                StrategySignal::Short,
+                StrategySignal::Hold,
+            ],
+        );
+
+        assert_eq!(
+            rules,
+            vec![
+                None,
+                None,
+                Some(StrategyRiskRule::MaxIntradayLoss),
+                None,
+                None,
+                None
+            ]
+        );
+        // Short opened by reversal at tick 2 is closed immediately and trading is stopped until the next day.
+        assert_eq!(strategy.trades.len(), 3);
+        assert_eq!(strategy.trades[0].exit_tick, Some(2));
+        assert_eq!(strategy.trades[1].entry_tick, Some(2));
+        assert_eq!(strategy.trades[1].exit_tick, Some(2));
+        assert_eq!(strategy.trades[2].entry_tick, Some(4));
+    }
+
+    #[test]
+    fn loss_cooldown() {
+        let ctx = Fixture::ohlc_ctx(&[
+            (100.0, 100.0, 100.0, 100.0),
+            (90.0, 90.0, 90.0, 90.0),
+            (90.0, 90.0, 90.0, 90.0),
+            (90.0, 90.0, 90.0, 90.0),
+            (90.0, 90.0, 90.0, 90.0),
+        ]);
+        let (strategy, rules) = run(
+            ctx,
+            StrategyRiskConfig {
+                loss_cooldown: Some(2),
+                ..StrategyRiskConfig::default()
+            },
+            &[
+                StrategySignal::Long,
+                StrategySignal::Exit,
+                StrategySignal::Long,
+                StrategySignal::Long,
+                StrategySignal::Long,
+            ],
+        );
+
+        assert_eq!(
+            rules,
+            vec![
+                None,
+                None,
+                Some(StrategyRiskRule::LossCooldown),
+                Some(StrategyRiskRule::LossCooldown),
+                None
+            ]
+        );
+        assert_eq!(strategy.trades.len(), 2);
+        assert_eq!(strategy.trades[1].entry_tick, Some(4));
+    }
+
+    #[test]
+    fn max_position_size_and_orders() {
+        let ctx = Fixture::daily_price_ctx(&[10.0; 6], 3);
+        let mut strategy = Strategy::new(
+            ctx.clone(),
+            StrategyConfig {
+                on_bar_close: true,
+                buy_with_equity: true,
+                risk: StrategyRiskConfig {
+                    max_position_size: Some(50.0),
+                    max_intraday_filled_orders: Some(2),
+                    ..StrategyRiskConfig::default()
+                },
+                ..StrategyConfig::default()
+            },
+        );
+        let signals = [
+            StrategySignal::Long,
+            StrategySignal::Short,
+            StrategySignal::Long,
+            StrategySignal::Long,
+            StrategySignal::Hold,
+            StrategySignal::Hold,
+        ];
+        let mut rules: Vec<Option<StrategyRiskRule>> = Vec::new();
+
+        for tick in ctx.clone() {
+            strategy.next(signals[tick]);
+            rules.push(strategy.events.on_risk_rule.as_ref().map(|e| e.rule));
+        }
+
+        assert_eq!(
+            rules,
+            vec![
+                Some(StrategyRiskRule::MaxPositionSize),
+                Some(StrategyRiskRule::MaxIntradayFilledOrders),
+                Some(StrategyRiskRule::MaxIntradayFilledOrders),
+                Some(StrategyRiskRule::MaxPositionSize),
+                None,
+                None,
+            ]
+        );
+        assert!(strategy.trades[0].fill_size.unwrap().compare(50.0));
+        assert_eq!(strategy.trades[0].exit_tick, Some(1));
+        // Reversal at tick 1 opened a short, which was closed by the orders limit.
+        assert_eq!(strategy.trades[1].exit_tick, Some(1));
+        assert_eq!(strategy.trades[2].entry_tick, Some(3));
+    }
+}
@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::incremental::Incremental,
+        strategy::{
+            signal::{StrategyDirection, StrategySignal},
+            strategy::{Strategy, StrategyConfig},
+            trade::TradeDirection,
+        },
+        testing::fixture::Fixture,
+    };
+
+    fn _test(
+        config: StrategyConfig,
+        signals: &[StrategySignal],
+        expected: &[(TradeDirection, usize, Option<usize>)],
+    ) {
+        let ctx = Fixture::ohlc_ctx(&vec![(1.0, 1.0, 1.0, 1.0); signals.len()]);
+        let mut strategy = Strategy::new(ctx.clone(), config);
+
+        for tick in ctx.clone() {
+            strategy.next(signals[tick]);
+        }
+
+        let actual = strategy
+            .trades
+            .iter()
+            .map(|trade| (trade.direction, trade.entry_tick.unwrap(), trade.exit_tick))
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn exit_signals() {
+        _test(
+            StrategyConfig::default(),
+            &[
+                StrategySignal::Long,
+                StrategySignal::Hold,
+                StrategySignal::ExitShort,
+                StrategySignal::Exit,
+                StrategySignal::Short,
+                StrategySignal::ExitLong,
+                StrategySignal::ExitShort,
+                StrategySignal::Long,
+                StrategySignal::Hold,
+            ],
+            &[
+                (TradeDirection::Long, 1, Some(4)),
+                (TradeDirection::Short, 5, Some(7)),
+                (TradeDirection::Long, 8, None),
+            ],
+        );
+    }
+
+    #[test]
+    fn long_only() {
+        _test(
+            StrategyConfig {
+                allow_entry_in: StrategyDirection::Long,
+                ..StrategyConfig::default()
+            },
+            &[
+                StrategySignal::Short,
+                StrategySignal::Long,
+                StrategySignal::Hold,
+                StrategySignal::Short,
+                StrategySignal::Hold,
+                StrategySignal::Long,
+                StrategySignal::Hold,
+            ],
+            &[
+                (TradeDirection::Long, 2, Some(4)),
+                (TradeDirection::Long, 6, None),
+            ],
+        );
+    }
+
+    #[test]
+    fn short_only_on_bar_close() {
+        _test(
+            StrategyConfig {
+                allow_entry_in: StrategyDirection::Short,
+                on_bar_close: true,
+                ..StrategyConfig::default()
+            },
+            &[
+                StrategySignal::Short,
+                StrategySignal::Long,
+                StrategySignal::Long,
+                StrategySignal::Short,
+            ],
+            &[
+                (TradeDirection::Short, 0, Some(1)),
+                (TradeDirection::Short, 3, None),
+            ],
+        );
+    }
+
+    #[test]
+    fn trade_direction_compatibility() {
+        assert_eq!(
+            StrategySignal::from(Some(TradeDirection::Long)),
+            StrategySignal::Long
+        );
+        assert_eq!(StrategySignal::from(None), StrategySignal::Hold);
+        assert_eq!(
+            StrategyDirection::Long.restrict(StrategySignal::Short),
+            StrategySignal::ExitLong
+        );
+        assert_eq!(
+            StrategyDirection::All.restrict(StrategySignal::Short),
+            StrategySignal::Short
+        );
+    }
+}
@@ -171,6 +171,7 @@ mod tests {
                     on_bar_close: true,
                     initial_capital: 1000.0,
                     buy_with_equity: true,
+                    ..StrategyConfig::default()
                 },
             ),
             &[None, None, None, None, None],
@@ -226,6 +227,7 @@ mod tests {
                     on_bar_close: true,
                     initial_capital: 1000.0,
                     buy_with_equity: true,
+                    ..StrategyConfig::default()
                 },
             ),
             &[
@@ -1579,6 +1581,7 @@ mod tests {
                     on_bar_close: false,
                     initial_capital: 1000.0,
                     buy_with_equity: true,
+                    ..StrategyConfig::default()
                 },
             ),
             &[
@@ -2876,6 +2879,7 @@ mod tests {
                     on_bar_close: true,
                     initial_capital: 1000.0,
                     buy_with_equity: true,
+                    ..StrategyConfig::default()
                 },
             ),
             &[
@@ -4340,6 +4344,7 @@ mod tests {
                     on_bar_close: false,
                     initial_capital: 1000.0,
                     buy_with_equity: true,
+                    ..StrategyConfig::default()
                 },
             ),
             &[
@@ -5719,6 +5724,7 @@ mod tests {
                     on_bar_close: true,
                     initial_capital: 1000.0,
                     buy_with_equity: true,
+                    ..StrategyConfig::default()
                 },
             ),
             &[None, None, None, None, None],
@@ -5809,6 +5815,7 @@ mod tests {
                     on_bar_close: true,
                     initial_capital: 1000.0,
                     buy_with_equity: true,
+                    ..StrategyConfig::default()
                 },
             ),
             &[
@@ -6025,6 +6032,7 @@ mod tests {
                     on_bar_close: false,
                     initial_capital: 1000.0,
                     buy_with_equity: true,
+                    ..StrategyConfig::default()
                 },
             ),
             &[
@@ -6283,6 +6291,7 @@ mod tests {
                     on_bar_close: true,
                     buy_with_equity: true,
                     initial_capital: 1000.0,
+                    ..StrategyConfig::default()
                 },
             ),
             &[
@@ -6617,6 +6626,7 @@ mod tests {
                     on_bar_close: false,
                     initial_capital: 1000.0,
                     buy_with_equity: true,
+                    ..StrategyConfig::default()
                 },
             ),
             &[
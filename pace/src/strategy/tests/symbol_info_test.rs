@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        asset::symbol_info::SymbolInfo,
+        core::incremental::Incremental,
+        strategy::{
+            strategy::{Strategy, StrategyConfig},
+            trade::TradeDirection,
+        },
+        testing::{comparison::FloatComparison, fixture::Fixture},
+    };
+
+    #[test]
+    fn rounding() {
+        let syminfo = SymbolInfo {
+            mintick: 0.25,
+            min_qty: 1.0,
+            qty_step: 0.5,
+            ..SymbolInfo::default()
+        };
+
+        assert!(syminfo.round_to_mintick(100.13).compare(100.25));
+        assert!(syminfo.round_to_mintick(100.12).compare(100.0));
+        assert!(syminfo.round_qty(2.99).compare(2.5));
+        assert!(syminfo.round_qty(0.9).compare(0.0));
+        assert!(SymbolInfo::default().round_qty(0.3).compare(0.3));
+        assert!(SymbolInfo {
+            qty_step: 0.1,
+            ..SymbolInfo::default()
+        }
+        .round_qty(0.3)
+        .compare(0.3));
+    }
+
+    #[test]
+    fn futures_point_value() {
+        let ctx = Fixture::ohlc_ctx_with_syminfo(
+            &[
+                (4000.0, 4000.0, 4000.0, 4000.0),
+                (4000.1, 4010.0, 3990.0, 4005.0),
+                (4010.0, 4020.0, 4000.0, 4015.0),
+                (4020.0, 4020.0, 4020.0, 4020.0),
+            ],
+            SymbolInfo {
+                ticker: "ES".to_string(),
+                mintick: 0.25,
+                point_value: 50.0,
+                min_qty: 1.0,
+                qty_step: 1.0,
+                ..SymbolInfo::default()
+            },
+        );
+        let mut strategy = Strategy::new(
+            ctx.clone(),
+            StrategyConfig {
+                initial_capital: 500000.0,
+                buy_with_equity: true,
+                ..StrategyConfig::default()
+            },
+        );
+        let signals = [
+            Some(TradeDirection::Long),
+            None,
+            Some(TradeDirection::Short),
+            None,
+        ];
+
+        for tick in ctx.clone() {
+            strategy.next(signals[tick]);
+        }
+
+        let trade = &strategy.trades[0];
+        // 500000 / (4000 * 50) = 2.5 contracts, rounded down to 2
+        assert!(trade.fill_size.unwrap().compare(2.0));
+        assert!(trade.entry_price.unwrap().compare(4000.0));
+        assert!(trade.exit_price.unwrap().compare(4020.0));
+        assert!(trade.pnl.compare(2.0 * 20.0 * 50.0));
+    }
+
+    #[test]
+    fn order_below_min_qty() {
+        let ctx = Fixture::ohlc_ctx_with_syminfo(
+            &[(100.0, 100.0, 100.0, 100.0); 3],
+            SymbolInfo {
+                min_qty: 20.0,
+                ..SymbolInfo::default()
+            },
+        );
+        let mut strategy = Strategy::new(
+            ctx.clone(),
+            StrategyConfig {
+                buy_with_equity: true,
+                ..StrategyConfig::default()
+            },
+        );
+
+        for tick in ctx.clone() {
+            strategy.next(if tick == 0 {
+                Some(TradeDirection::Long)
+            } else {
+                None
+            });
+        }
+
+        assert!(strategy.trades.is_empty());
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::incremental::Incremental,
+        strategy::{
+            signal::StrategySignal,
+            strategy::{Strategy, StrategyConfig},
+        },
+        testing::{comparison::FloatComparison, fixture::Fixture},
+    };
+
+    #[test]
+    fn opentrades_closedtrades() {
+        let ctx = Fixture::ohlc_ctx(&[
+            (100.0, 100.0, 100.0, 100.0),
+            (100.0, 110.0, 90.0, 105.0),
+            (105.0, 120.0, 95.0, 110.0),
+            (110.0, 115.0, 100.0, 112.0),
+            (112.0, 113.0, 108.0, 110.0),
+            (110.0, 111.0, 109.0, 110.0),
+        ]);
+        let mut strategy = Strategy::new(ctx.clone(), StrategyConfig::default());
+        let signals = [
+            StrategySignal::Long,
+            StrategySignal::Hold,
+            StrategySignal::Short,
+            StrategySignal::Hold,
+            StrategySignal::Exit,
+            StrategySignal::Hold,
+        ];
+
+        for tick in ctx.clone() {
+            if tick == 0 {
+                strategy.set_entry_id("Breakout", Some("RSI < 30"));
+            }
+            if tick == 4 {
+                strategy.set_exit_id("Exit short", None);
+            }
+
+            strategy.next(signals[tick]);
+
+            if tick == 0 {
+                assert_eq!(strategy.opentrades().count(), 0);
+                assert!(strategy.position_size().compare(0.0));
+                assert!(strategy.position_avg_price().is_none());
+            }
+            if tick == 2 {
+                let opentrades = strategy.opentrades();
+                assert_eq!(opentrades.count(), 1);
+                assert_eq!(opentrades.entry_id(0), Some("Breakout"));
+                assert_eq!(opentrades.entry_comment(0), Some("RSI < 30"));
+                assert_eq!(opentrades.entry_bar_index(0), Some(1));
+                assert!(opentrades.profit(0).unwrap().compare(10.0));
+                assert!(opentrades.max_drawdown(0).unwrap().compare(10.0));
+                assert!(opentrades.max_runup(0).unwrap().compare(20.0));
+                assert!(strategy.position_size().compare(1.0));
+                assert!(strategy.position_avg_price().unwrap().compare(100.0));
+                assert_eq!(strategy.position_entry_name(), Some("Breakout"));
+            }
+            if tick == 3 {
+                assert!(strategy.position_size().compare(-1.0));
+                assert_eq!(strategy.position_entry_name(), Some("Short"));
+            }
+        }
+
+        let closedtrades = strategy.closedtrades();
+        assert_eq!(strategy.opentrades().count(), 0);
+        assert_eq!(closedtrades.count(), 2);
+
+        assert_eq!(closedtrades.exit_id(0), Some("Short"));
+        assert_eq!(closedtrades.exit_bar_index(0), Some(3));
+        assert!(closedtrades.exit_price(0).unwrap().compare(110.0));
+        assert!(closedtrades.profit(0).unwrap().compare(10.0));
+
+        assert_eq!(closedtrades.entry_id(1), Some("Short"));
+        assert_eq!(closedtrades.exit_id(1), Some("Exit short"));
+        assert!(closedtrades.size(1).unwrap().compare(-1.0));
+        assert!(closedtrades.profit(1).unwrap().compare(0.0));
+        assert!(closedtrades.max_drawdown(1).unwrap().compare(5.0));
+        assert!(closedtrades.max_runup(1).unwrap().compare(10.0));
+        assert!(closedtrades.profit(2).is_none());
+    }
+}
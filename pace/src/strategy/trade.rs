@@ -6,6 +6,15 @@ pub enum TradeDirection {
     Short = 1,
 }
 
+impl std::fmt::Display for TradeDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            TradeDirection::Long => write!(f, "Long"),
+            TradeDirection::Short => write!(f, "Short"),
+        };
+    }
+}
+
 impl TradeDirection {
     pub fn get_opposite(&self) -> Self {
         return match self {
@@ -38,7 +47,15 @@ pub fn trade_direction_from_f64(value: Option<f64>) -> Option<TradeDirection> {
     };
 }
 
+/// Single fill of a trade entry.
 #[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TradeFill {
+    pub tick: usize,
+    pub price: f64,
+    pub size: f64,
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct Trade {
     pub direction: TradeDirection,
     pub is_closed: bool,
@@ -48,6 +65,30 @@ pub struct Trade {
     pub exit_price: Option<f64>,
     pub fill_size: Option<f64>,
     pub pnl: f64,
+    /// `true` if the trade was closed by forced liquidation.
+    pub is_liquidated: bool,
+    /// Accrued funding. Negative value means funding was paid. Included in `pnl`.
+    pub funding: f64,
+    /// Paid commission of all fills. Subtracted from `pnl`. Same as PineScript `strategy.closedtrades.commission`.
+    pub commission: f64,
+    /// Value of a `1.0` price move for a single contract. Same as PineScript `syminfo.pointvalue`.
+    pub point_value: f64,
+    /// Largest loss during the trade. Same as PineScript `strategy.closedtrades.max_drawdown`.
+    pub max_drawdown: f64,
+    /// Largest profit during the trade. Same as PineScript `strategy.closedtrades.max_runup`.
+    pub max_runup: f64,
+    /// Same as PineScript `strategy.closedtrades.entry_id`.
+    pub entry_id: String,
+    /// Same as PineScript `strategy.closedtrades.entry_comment`.
+    pub entry_comment: Option<String>,
+    /// Same as PineScript `strategy.closedtrades.exit_id`.
+    pub exit_id: Option<String>,
+    /// Same as PineScript `strategy.closedtrades.exit_comment`.
+    pub exit_comment: Option<String>,
+    /// Entry fills. Entry limited by bar volume is filled over multiple bars.
+    pub fills: Vec<TradeFill>,
+    /// Quantity of the entry order that was not filled yet.
+    pub unfilled_size: f64,
 }
 
 impl Trade {
@@ -61,16 +102,31 @@ impl Trade {
             exit_tick: None,
             fill_size: None,
             pnl: 0.0,
+            is_liquidated: false,
+            funding: 0.0,
+            commission: 0.0,
+            point_value: 1.0,
+            max_drawdown: 0.0,
+            max_runup: 0.0,
+            entry_id: direction.to_string(),
+            entry_comment: None,
+            exit_id: None,
+            exit_comment: None,
+            fills: Vec::new(),
+            unfilled_size: 0.0,
         };
     }
 
+    /// Profit at `current_price`, including accrued funding and paid commission.
     pub fn pnl(&self, current_price: f64) -> f64 {
         return trade_pnl(
             self.fill_size.unwrap(),
             self.entry_price.unwrap(),
             current_price,
             self.direction == TradeDirection::Long,
-        );
+        ) * self.point_value
+            + self.funding
+            - self.commission;
     }
 
     pub fn is_at_entry(&self, current_tick: usize) -> bool {
@@ -81,10 +137,49 @@ impl Trade {
         return self.exit_tick.is_some() && self.exit_tick.unwrap() == current_tick;
     }
 
+    /// Adds an entry fill. Entry price becomes the average price of all fills.
+    pub fn add_fill(&mut self, tick: usize, price: f64, size: f64) {
+        let filled_size = self.fill_size.unwrap_or(0.0);
+        let total_size = filled_size + size;
+
+        if filled_size > 0.0 && total_size > 0.0 {
+            self.entry_price =
+                Some((self.entry_price.unwrap_or(price) * filled_size + price * size) / total_size);
+        } else {
+            self.entry_price = Some(price);
+        }
+        if self.entry_tick.is_none() {
+            self.entry_tick = Some(tick);
+        }
+
+        self.fill_size = Some(total_size);
+        self.unfilled_size = f64::max(self.unfilled_size - size, 0.0);
+        self.fills.push(TradeFill { tick, price, size });
+    }
+
     pub fn is_active(&self) -> bool {
         return self.entry_tick.is_some() && !self.is_closed;
     }
 
+    /// Signed quantity. Positive for long trades, negative for short trades.
+    pub fn signed_size(&self) -> f64 {
+        let size = self.fill_size.unwrap_or(0.0);
+        return match self.direction {
+            TradeDirection::Long => size,
+            TradeDirection::Short => -size,
+        };
+    }
+
+    /// Updates `max_drawdown` and `max_runup` with lowest and highest price reached during a bar.
+    pub fn update_excursions(&mut self, low: f64, high: f64) {
+        let (adverse_price, favorable_price) = match self.direction {
+            TradeDirection::Long => (low, high),
+            TradeDirection::Short => (high, low),
+        };
+        self.max_drawdown = f64::max(self.max_drawdown, -self.pnl(adverse_price));
+        self.max_runup = f64::max(self.max_runup, self.pnl(favorable_price));
+    }
+
     pub fn to_colored_string(&self, current_tick: usize) -> ColoredString {
         if !self.is_closed {
             if self.direction == TradeDirection::Long {
@@ -0,0 +1,109 @@
+use super::trade::Trade;
+
+/// Read-only list of trades, indexed from the oldest one.
+///
+/// Mirrors PineScript `strategy.opentrades.*` and `strategy.closedtrades.*` functions.
+pub struct TradeList<'a> {
+    trades: Vec<&'a Trade>,
+    current_price: Option<f64>,
+}
+
+impl<'a> TradeList<'a> {
+    /// `current_price` is used to compute profit of open trades.
+    pub fn new(trades: Vec<&'a Trade>, current_price: Option<f64>) -> Self {
+        return Self {
+            trades,
+            current_price,
+        };
+    }
+
+    /// Same as PineScript `strategy.opentrades` / `strategy.closedtrades`.
+    pub fn count(&self) -> usize {
+        return self.trades.len();
+    }
+
+    pub fn get(&self, index: usize) -> Option<&'a Trade> {
+        return self.trades.get(index).copied();
+    }
+
+    pub fn last(&self) -> Option<&'a Trade> {
+        return self.trades.last().copied();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a Trade> + '_ {
+        return self.trades.iter().copied();
+    }
+
+    /// Same as PineScript `strategy.opentrades.entry_price(trade_num)`.
+    pub fn entry_price(&self, index: usize) -> Option<f64> {
+        return self.get(index)?.entry_price;
+    }
+
+    /// Same as PineScript `strategy.opentrades.entry_bar_index(trade_num)`.
+    pub fn entry_bar_index(&self, index: usize) -> Option<usize> {
+        return self.get(index)?.entry_tick;
+    }
+
+    /// Same as PineScript `strategy.opentrades.entry_id(trade_num)`.
+    pub fn entry_id(&self, index: usize) -> Option<&'a str> {
+        return self.get(index).map(|trade| trade.entry_id.as_str());
+    }
+
+    /// Same as PineScript `strategy.opentrades.entry_comment(trade_num)`.
+    pub fn entry_comment(&self, index: usize) -> Option<&'a str> {
+        return self.get(index)?.entry_comment.as_deref();
+    }
+
+    /// Same as PineScript `strategy.closedtrades.exit_price(trade_num)`.
+    pub fn exit_price(&self, index: usize) -> Option<f64> {
+        return self.get(index)?.exit_price;
+    }
+
+    /// Same as PineScript `strategy.closedtrades.exit_bar_index(trade_num)`.
+    pub fn exit_bar_index(&self, index: usize) -> Option<usize> {
+        return self.get(index)?.exit_tick;
+    }
+
+    /// Same as PineScript `strategy.closedtrades.exit_id(trade_num)`.
+    pub fn exit_id(&self, index: usize) -> Option<&'a str> {
+        return self.get(index)?.exit_id.as_deref();
+    }
+
+    /// Same as PineScript `strategy.closedtrades.exit_comment(trade_num)`.
+    pub fn exit_comment(&self, index: usize) -> Option<&'a str> {
+        return self.get(index)?.exit_comment.as_deref();
+    }
+
+    /// Signed quantity. Positive for long trades, negative for short trades.
+    ///
+    /// Same as PineScript `strategy.opentrades.size(trade_num)`.
+    pub fn size(&self, index: usize) -> Option<f64> {
+        return self.get(index).map(|trade| trade.signed_size());
+    }
+
+    /// Profit of a closed trade, or unrealized profit of an open trade at the current price.
+    ///
+    /// Same as PineScript `strategy.opentrades.profit(trade_num)`.
+    pub fn profit(&self, index: usize) -> Option<f64> {
+        let trade = self.get(index)?;
+        if trade.is_closed {
+            return Some(trade.pnl);
+        }
+        return self.current_price.map(|price| trade.pnl(price));
+    }
+
+    /// Same as PineScript `strategy.opentrades.commission(trade_num)`.
+    pub fn commission(&self, index: usize) -> Option<f64> {
+        return self.get(index).map(|trade| trade.commission);
+    }
+
+    /// Same as PineScript `strategy.opentrades.max_drawdown(trade_num)`.
+    pub fn max_drawdown(&self, index: usize) -> Option<f64> {
+        return self.get(index).map(|trade| trade.max_drawdown);
+    }
+
+    /// Same as PineScript `strategy.opentrades.max_runup(trade_num)`.
+    pub fn max_runup(&self, index: usize) -> Option<f64> {
+        return self.get(index).map(|trade| trade.max_runup);
+    }
+}
@@ -25,6 +25,6 @@ impl Incremental<(), Option<i32>> for HighestBars {
         if !self.ctx.bar.at_length(self.length) {
             return None;
         }
-        return highest_bars(self.ctx.highs(self.length), self.length);
+        return highest_bars(&self.ctx.highs(self.length), self.length);
     }
 }
@@ -24,6 +24,6 @@ impl Incremental<(), Option<i32>> for LowestBars {
         if !self.ctx.bar.at_length(self.length) {
             return None;
         }
-        return lowest_bars(self.ctx.lows(self.length), self.length);
+        return lowest_bars(&self.ctx.lows(self.length), self.length);
     }
 }
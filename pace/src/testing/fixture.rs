@@ -1,11 +1,9 @@
-use std::{
-    path::{Path, PathBuf},
-    sync::Arc,
-};
+use std::{path::Path, sync::Arc, time::Duration};
 
-use polars::prelude::DataFrame;
+use polars::prelude::{df, DataFrame, NamedFrom};
 
 use crate::{
+    asset::symbol_info::SymbolInfo,
     core::{
         context::Context, data_provider::DataProvider,
         in_memory_data_provider::InMemoryDataProvider,
@@ -18,10 +16,108 @@ pub struct Fixture {}
 
 impl Fixture {
     pub fn load_ctx(path: &Path) -> (DataFrame, Context) {
-        let df = read_df(&path);
-        let ctx = Context::new(InMemoryDataProvider::from_df(&df).to_arc());
+        let df = read_df(&path).unwrap();
+        let ctx = Context::new(InMemoryDataProvider::from_df(&df).unwrap().to_arc());
         return (df, ctx);
     }
+
+    /// Creates a context from `(open, high, low, close)` bars, with volume of `1.0`.
+    pub fn ohlc_ctx(bars: &[(f64, f64, f64, f64)]) -> Context {
+        let values = |f: fn(&(f64, f64, f64, f64)) -> f64| -> Vec<Option<f64>> {
+            bars.iter().map(|bar| Some(f(bar))).collect()
+        };
+        return Context::new(
+            InMemoryDataProvider::new(
+                values(|bar| bar.0),
+                values(|bar| bar.1),
+                values(|bar| bar.2),
+                values(|bar| bar.3),
+                vec![Some(1.0); bars.len()],
+                vec![None; bars.len()],
+            )
+            .to_arc(),
+        );
+    }
+
+    /// Creates a context from `(open, high, low, close)` bars, with given symbol info.
+    pub fn ohlc_ctx_with_syminfo(bars: &[(f64, f64, f64, f64)], syminfo: SymbolInfo) -> Context {
+        return Context::build(Self::ohlc_ctx(bars).data, syminfo);
+    }
+
+    /// Creates a context with `bars_per_day` bars per day, where every bar has `open == high == low == close == volume`.
+    pub fn daily_price_ctx(prices: &[f64], bars_per_day: usize) -> Context {
+        let values: Vec<Option<f64>> = prices.iter().map(|x| Some(*x)).collect();
+        let time = (0..prices.len())
+            .map(|i| Some(Duration::from_secs((i * 86400 / bars_per_day) as u64)))
+            .collect();
+        return Context::new(
+            InMemoryDataProvider::new(
+                values.clone(),
+                values.clone(),
+                values.clone(),
+                values.clone(),
+                values,
+                time,
+            )
+            .to_arc(),
+        );
+    }
+
+    /// Creates a context with constant `price` and given bar volumes.
+    pub fn volume_ctx(price: f64, volume: &[f64]) -> Context {
+        let price = vec![Some(price); volume.len()];
+        return Context::new(
+            InMemoryDataProvider::new(
+                price.clone(),
+                price.clone(),
+                price.clone(),
+                price,
+                volume.iter().map(|x| Some(*x)).collect(),
+                vec![None; volume.len()],
+            )
+            .to_arc(),
+        );
+    }
+
+    /// Creates `count` bars starting at `start_time` seconds, spaced by `interval` seconds,
+    /// where bar `i` has open of `1.0 + i` and a `funding_rate` series of `(i + 1) / 100`.
+    pub fn ohlcv_df(count: usize, start_time: u64, interval: u64) -> DataFrame {
+        let values = |offset: f64, step: f64| -> Vec<f64> {
+            (0..count)
+                .map(|i| (i as f64 * step + offset) / 10.0)
+                .collect()
+        };
+        return df!(
+            "time" => (0..count).map(|i| (start_time + i as u64 * interval) as f64).collect::<Vec<f64>>(),
+            "open" => values(10.0, 10.0),
+            "high" => values(15.0, 10.0),
+            "low" => values(5.0, 10.0),
+            "close" => values(12.0, 10.0),
+            "volume" => values(100.0, 100.0),
+            "funding_rate" => (0..count).map(|i| (i + 1) as f64 / 100.0).collect::<Vec<f64>>()
+        )
+        .unwrap();
+    }
+
+    /// Creates a data provider from `(time_secs, open, high, low, close)` bars, with volume of `1.0`.
+    pub fn timed_ohlc_data(
+        bars: &[(u64, f64, f64, f64, f64)],
+    ) -> Arc<dyn DataProvider + Send + Sync> {
+        let values = |f: fn(&(u64, f64, f64, f64, f64)) -> f64| -> Vec<Option<f64>> {
+            bars.iter().map(|bar| Some(f(bar))).collect()
+        };
+        return InMemoryDataProvider::new(
+            values(|bar| bar.1),
+            values(|bar| bar.2),
+            values(|bar| bar.3),
+            values(|bar| bar.4),
+            vec![Some(1.0); bars.len()],
+            bars.iter()
+                .map(|bar| Some(Duration::from_secs(bar.0)))
+                .collect(),
+        )
+        .to_arc();
+    }
 }
 
 pub trait DataFrameFixtureUtils {
@@ -31,10 +127,10 @@ pub trait DataFrameFixtureUtils {
 
 impl DataFrameFixtureUtils for DataFrame {
     fn test_target(&self) -> Vec<Option<f64>> {
-        return self.column("_target_").unwrap().to_f64();
+        return self.column("_target_").unwrap().to_f64().unwrap();
     }
 
     fn test_trade_dir_target(&self) -> Vec<Option<TradeDirection>> {
-        return self.column("_target_").unwrap().to_trade_dir();
+        return self.column("_target_").unwrap().to_trade_dir().unwrap();
     }
 }
@@ -2,3 +2,4 @@ pub mod array_snapshot;
 pub mod comparison;
 pub mod fixture;
 pub mod pace;
+pub mod temp_dir;